@@ -0,0 +1,813 @@
+//! Support for the _Audio Data Transport Stream_ framing defined in ISO/IEC 14496-3 (and
+//! originally in ISO/IEC 13818-7), as commonly found in raw `.aac` files and in MPEG-TS.
+//!
+//! [`AdtsHeader`](struct.AdtsHeader.html) parses the fixed and variable header of a single
+//! frame, [`AdtsFrames`](struct.AdtsFrames.html) iterates over the frames in a buffer,
+//! [`AdtsStats`](struct.AdtsStats.html) summarises a stream's duration and bitrate, and
+//! [`AdtsIndex`](struct.AdtsIndex.html) supports seeking by time.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::adts::*;
+//! # let buf = [0xff, 0xf1, 0x50, 0x80, 0x01, 0x1f, 0xfc, 0x00];
+//! let stats = AdtsStats::from_slice(&buf).unwrap();
+//! assert_eq!(1, stats.frame_count());
+//! println!("duration {:?}, average bitrate {:?}", stats.duration(), stats.average_bitrate());
+//! ```
+
+use crate::bits::{BitReader, EndOfData};
+use crate::describe::{yes_no, Describe, Describer, Description, Value};
+use crate::options::Violation;
+#[cfg(feature = "alloc")]
+use crate::options::{ParseOptions, Parsed};
+use crate::trace::TraceEntry;
+use crate::{AudioObjectType, ChannelConfiguration, SamplingFrequencyIndex};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::io;
+
+/// The value of the 12-bit `syncword` field that starts every ADTS frame.
+pub const ADTS_SYNC_WORD: u16 = 0xfff;
+
+/// The number of bytes in an ADTS header that has no CRC field.
+pub const ADTS_HEADER_LENGTH: usize = 7;
+
+/// The number of bytes in an ADTS header that includes the 16-bit CRC field.
+pub const ADTS_HEADER_WITH_CRC_LENGTH: usize = 9;
+
+/// The number of audio samples (per channel, at the core sampling rate) decoded from each
+/// `raw_data_block()`.  ADTS does not support the 960-sample frame length.
+pub const SAMPLES_PER_RAW_DATA_BLOCK: u32 = 1024;
+
+/// Errors that may be produced while parsing an ADTS header
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum AdtsHeaderError {
+    /// The 12-bit `syncword` did not have the expected value, `0xfff`; the field read is given.
+    BadSyncWord(TraceEntry),
+    /// The buffer was too short to hold the header (or, for
+    /// [`AdtsFrames`](struct.AdtsFrames.html), the frame), ending while reading the given
+    /// element.
+    NotEnoughData(TraceEntry),
+    /// The `sampling_frequency_index` field held the escape value `15`, which is not permitted
+    /// in ADTS headers.
+    BadSamplingFrequencyIndex(u8),
+    /// The `frame_length` field gave a value shorter than the header itself; the field read is
+    /// given.
+    BadFrameLength(TraceEntry),
+    /// The header did not conform to the spec, when parsed with
+    /// [`Strictness::Strict`](../options/enum.Strictness.html).
+    Violation(Violation),
+}
+
+impl fmt::Display for AdtsHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdtsHeaderError::BadSyncWord(at) => write!(f, "bad syncword, {}", at),
+            AdtsHeaderError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            AdtsHeaderError::BadSamplingFrequencyIndex(v) => write!(
+                f,
+                "sampling_frequency_index {} is not permitted in an ADTS header",
+                v
+            ),
+            AdtsHeaderError::BadFrameLength(at) => {
+                write!(f, "frame length shorter than the header, {}", at)
+            }
+            AdtsHeaderError::Violation(v) => v.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for AdtsHeaderError {}
+
+impl From<EndOfData> for AdtsHeaderError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        AdtsHeaderError::NotEnoughData(at)
+    }
+}
+impl From<Violation> for AdtsHeaderError {
+    fn from(v: Violation) -> Self {
+        AdtsHeaderError::Violation(v)
+    }
+}
+
+/// The value of the ADTS header `ID` field
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MpegVersion {
+    /// `ID` value `0`
+    Mpeg4,
+    /// `ID` value `1`, indicating the stream conforms to ISO/IEC 13818-7
+    Mpeg2,
+}
+
+/// A parsed ADTS frame header
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::adts::*;
+/// let header = AdtsHeader::from_bytes(&[0xff, 0xf1, 0x50, 0x80, 0x2e, 0x7f, 0xfc]).unwrap();
+/// assert_eq!(AudioObjectType::AAC_LC, header.audio_object_type);
+/// assert_eq!(SamplingFrequencyIndex::FREQ_44100, header.sampling_frequency_index);
+/// assert_eq!(ChannelConfiguration::STEREO, header.channel_configuration);
+/// assert_eq!(371, header.frame_length);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(frozen, eq, from_py_object, module = "mpeg4_audio_const")
+)]
+pub struct AdtsHeader {
+    pub mpeg_version: MpegVersion,
+    /// The value of the 2-bit `layer` field, which should always be `0`
+    pub layer: u8,
+    /// `true` if the header includes a CRC (i.e. the `protection_absent` bit is _not_ set)
+    pub protected: bool,
+    /// The audio object type, derived from the 2-bit `profile_ObjectType` field by adding `1`
+    pub audio_object_type: AudioObjectType,
+    pub sampling_frequency_index: SamplingFrequencyIndex,
+    pub private_bit: bool,
+    /// The channel configuration from the 3-bit ADTS field (so values above `7` can't occur)
+    pub channel_configuration: ChannelConfiguration,
+    pub original_copy: bool,
+    pub home: bool,
+    pub copyright_identification_bit: bool,
+    pub copyright_identification_start: bool,
+    /// The length of the whole frame in bytes, including the header
+    pub frame_length: u16,
+    /// The 11-bit `adts_buffer_fullness` value; `0x7ff` signals a variable-rate stream
+    pub buffer_fullness: u16,
+    /// The number of `raw_data_block()`s in the frame, _minus one_, as signalled in the header
+    pub number_of_raw_data_blocks_in_frame: u8,
+    /// The CRC value, if the header is `protected`
+    pub crc: Option<u16>,
+}
+
+impl AdtsHeader {
+    /// Parses the header at the start of the given buffer.  The buffer must contain at least
+    /// [`ADTS_HEADER_LENGTH`](constant.ADTS_HEADER_LENGTH.html) bytes, or
+    /// [`ADTS_HEADER_WITH_CRC_LENGTH`](constant.ADTS_HEADER_WITH_CRC_LENGTH.html) bytes if
+    /// the header signals the presence of a CRC.
+    pub fn from_bytes(buf: &[u8]) -> Result<AdtsHeader, AdtsHeaderError> {
+        Self::read(&mut BitReader::new(buf))
+    }
+
+    /// Parses the header as for [`from_bytes()`](#method.from_bytes), checking for reserved
+    /// values and other violations of the spec as the given options require.
+    #[cfg(feature = "alloc")]
+    pub fn from_bytes_with_options(
+        buf: &[u8],
+        options: &ParseOptions,
+    ) -> Result<Parsed<AdtsHeader>, AdtsHeaderError> {
+        options.parse(buf, Self::read)
+    }
+
+    fn read(r: &mut BitReader<'_>) -> Result<AdtsHeader, AdtsHeaderError> {
+        if r.named("syncword").read_u16(12)? != ADTS_SYNC_WORD {
+            return Err(AdtsHeaderError::BadSyncWord(r.last_read()));
+        }
+        let mpeg_version = if r.named("ID").read_bool()? {
+            MpegVersion::Mpeg2
+        } else {
+            MpegVersion::Mpeg4
+        };
+        let layer = r.named("layer").read_u8(2)?;
+        if layer != 0 {
+            r.violation(Violation::UnexpectedValue);
+        }
+        let protected = !r.named("protection_absent").read_bool()?;
+        // profile values 0..=3 map to a-o-t values 1..=4, none of which can be rejected
+        let audio_object_type =
+            AudioObjectType::try_from(r.named("profile_ObjectType").read_u8(2)? + 1).unwrap();
+        if mpeg_version == MpegVersion::Mpeg2 && audio_object_type == AudioObjectType::AAC_LTP {
+            // the fourth profile is reserved in ISO/IEC 13818-7
+            r.violation(Violation::ReservedValue);
+        }
+        let sfi = r.named("sampling_frequency_index").read_u8(4)?;
+        let sampling_frequency_index = SamplingFrequencyIndex::try_from(sfi)
+            .map_err(|_| AdtsHeaderError::BadSamplingFrequencyIndex(sfi))?;
+        if sampling_frequency_index.frequency().is_none() {
+            r.violation(Violation::ReservedValue);
+        }
+        let private_bit = r.named("private_bit").read_bool()?;
+        // a 3-bit value can't be rejected
+        let channel_configuration =
+            ChannelConfiguration::try_from(r.named("channel_configuration").read_u8(3)?).unwrap();
+        Ok(AdtsHeader {
+            mpeg_version,
+            layer,
+            protected,
+            audio_object_type,
+            sampling_frequency_index,
+            private_bit,
+            channel_configuration,
+            original_copy: r.named("original_copy").read_bool()?,
+            home: r.named("home").read_bool()?,
+            copyright_identification_bit: r.named("copyright_identification_bit").read_bool()?,
+            copyright_identification_start: r
+                .named("copyright_identification_start")
+                .read_bool()?,
+            frame_length: {
+                let frame_length = r.named("frame_length").read_u16(13)?;
+                let header_length = if protected {
+                    ADTS_HEADER_WITH_CRC_LENGTH
+                } else {
+                    ADTS_HEADER_LENGTH
+                };
+                if usize::from(frame_length) < header_length {
+                    return Err(AdtsHeaderError::BadFrameLength(r.last_read()));
+                }
+                frame_length
+            },
+            buffer_fullness: r.named("adts_buffer_fullness").read_u16(11)?,
+            number_of_raw_data_blocks_in_frame: r
+                .named("number_of_raw_data_blocks_in_frame")
+                .read_u8(2)?,
+            crc: if protected {
+                Some(r.named("crc_check").read_u16(16)?)
+            } else {
+                None
+            },
+        })
+    }
+
+    /// The length of this header in bytes, which depends on whether a CRC is present.
+    pub fn header_length(&self) -> usize {
+        if self.protected {
+            ADTS_HEADER_WITH_CRC_LENGTH
+        } else {
+            ADTS_HEADER_LENGTH
+        }
+    }
+
+    /// The length of the frame payload in bytes, excluding the header.
+    pub fn payload_length(&self) -> usize {
+        usize::from(self.frame_length) - self.header_length()
+    }
+
+    /// The number of audio samples per channel that decoding this frame will produce at the
+    /// core sampling rate (implicit SBR would produce twice as many, at twice the rate, so the
+    /// frame duration is unaffected).
+    pub fn sample_count(&self) -> u32 {
+        SAMPLES_PER_RAW_DATA_BLOCK * (u32::from(self.number_of_raw_data_blocks_in_frame) + 1)
+    }
+
+    /// The duration of the frame, or `None` if the sampling frequency index is reserved.
+    pub fn duration(&self) -> Option<Duration> {
+        self.sampling_frequency_index
+            .frequency()
+            .map(|rate| samples_to_duration(u64::from(self.sample_count()), rate))
+    }
+}
+
+impl AdtsHeader {
+    /// A multi-line, human-readable description of this header, similar to that given by media
+    /// inspection tools, for diagnostic output.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::adts::*;
+    /// let header = AdtsHeader::from_bytes(&[0xff, 0xf1, 0x50, 0x80, 0x2e, 0x7f, 0xfc]).unwrap();
+    /// let description = header.describe().to_string();
+    /// assert!(description.starts_with("ADTS header\n  MPEG version: MPEG-4\n"));
+    /// assert!(description.contains("\n  Frame length: 371 bytes\n"));
+    /// ```
+    pub fn describe(&self) -> impl fmt::Display + '_ {
+        Description(self)
+    }
+}
+
+impl Describe for AdtsHeader {
+    fn describe_to(&self, d: &mut Describer<'_, '_>) -> fmt::Result {
+        d.section("ADTS header", |d| {
+            let version = match self.mpeg_version {
+                MpegVersion::Mpeg4 => "MPEG-4",
+                MpegVersion::Mpeg2 => "MPEG-2",
+            };
+            d.field("MPEG version", version)?;
+            d.field("Layer", self.layer)?;
+            match self.crc {
+                Some(crc) => d.field("CRC", format_args!("{:#06x}", crc))?,
+                None => d.field("CRC", "absent")?,
+            }
+            d.field("Object type", Value(self.audio_object_type))?;
+            d.field("Sampling frequency", Value(self.sampling_frequency_index))?;
+            d.field("Private bit", yes_no(self.private_bit))?;
+            d.field("Channel configuration", Value(self.channel_configuration))?;
+            d.field("Original/copy", yes_no(self.original_copy))?;
+            d.field("Home", yes_no(self.home))?;
+            d.field(
+                "Copyright identification bit",
+                yes_no(self.copyright_identification_bit),
+            )?;
+            d.field(
+                "Copyright identification start",
+                yes_no(self.copyright_identification_start),
+            )?;
+            d.field("Frame length", format_args!("{} bytes", self.frame_length))?;
+            if self.buffer_fullness == 0x7ff {
+                d.field("Buffer fullness", "0x7ff (variable rate)")?;
+            } else {
+                d.field("Buffer fullness", self.buffer_fullness)?;
+            }
+            d.field(
+                "Raw data blocks",
+                u32::from(self.number_of_raw_data_blocks_in_frame) + 1,
+            )
+        })
+    }
+}
+
+fn samples_to_duration(samples: u64, rate: u32) -> Duration {
+    let rate = u64::from(rate);
+    let secs = samples / rate;
+    let nanos = (samples % rate) * 1_000_000_000 / rate;
+    Duration::new(secs, nanos as u32)
+}
+
+/// A single ADTS frame located within a buffer by [`AdtsFrames`](struct.AdtsFrames.html)
+#[derive(Debug)]
+pub struct AdtsFrame<'a> {
+    /// Offset in bytes of the start of the frame header within the buffer
+    pub offset: usize,
+    pub header: AdtsHeader,
+    /// The frame payload following the header
+    pub payload: &'a [u8],
+}
+
+/// Iterator over the consecutive ADTS frames in a buffer.
+///
+/// Iteration stops after the first error, which will be `AdtsHeaderError::NotEnoughData` if
+/// the buffer ends part way through a frame.  The bit offsets given by errors are relative to
+/// the start of the frame concerned, which follows the last frame returned.
+pub struct AdtsFrames<'a> {
+    buf: &'a [u8],
+    offset: usize,
+    failed: bool,
+}
+
+impl<'a> AdtsFrames<'a> {
+    pub fn new(buf: &'a [u8]) -> AdtsFrames<'a> {
+        AdtsFrames {
+            buf,
+            offset: 0,
+            failed: false,
+        }
+    }
+}
+
+impl<'a> Iterator for AdtsFrames<'a> {
+    type Item = Result<AdtsFrame<'a>, AdtsHeaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.offset >= self.buf.len() {
+            return None;
+        }
+        let rest = &self.buf[self.offset..];
+        let result = AdtsHeader::from_bytes(rest).and_then(|header| {
+            let len = usize::from(header.frame_length);
+            if rest.len() < len {
+                let start = header.header_length();
+                return Err(AdtsHeaderError::NotEnoughData(TraceEntry {
+                    bit_offset: start * 8,
+                    bit_length: (len - start) * 8,
+                    field: "raw_data_block",
+                    value: None,
+                }));
+            }
+            Ok(AdtsFrame {
+                offset: self.offset,
+                payload: &rest[header.header_length()..len],
+                header,
+            })
+        });
+        match result {
+            Ok(ref frame) => self.offset += usize::from(frame.header.frame_length),
+            Err(_) => self.failed = true,
+        }
+        Some(result)
+    }
+}
+
+/// Errors produced when reading ADTS frames from an `io::Read` implementation
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AdtsReadError {
+    Io(io::Error),
+    Header(AdtsHeaderError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for AdtsReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdtsReadError::Io(_) => f.write_str("error reading ADTS stream"),
+            AdtsReadError::Header(_) => f.write_str("invalid ADTS header"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for AdtsReadError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            AdtsReadError::Io(e) => Some(e),
+            AdtsReadError::Header(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for AdtsReadError {
+    fn from(e: io::Error) -> Self {
+        AdtsReadError::Io(e)
+    }
+}
+#[cfg(feature = "std")]
+impl From<AdtsHeaderError> for AdtsReadError {
+    fn from(e: AdtsHeaderError) -> Self {
+        AdtsReadError::Header(e)
+    }
+}
+
+/// Reads each frame header from the given source in turn, skipping the frame payloads, and
+/// passes the header and its byte offset within the stream to the given callback.
+#[cfg(feature = "std")]
+fn read_headers<R, F>(mut r: R, mut f: F) -> Result<(), AdtsReadError>
+where
+    R: io::Read,
+    F: FnMut(u64, &AdtsHeader),
+{
+    let mut buf = [0u8; ADTS_HEADER_WITH_CRC_LENGTH];
+    let mut offset = 0;
+    loop {
+        let first = r.read(&mut buf[..1])?;
+        if first == 0 {
+            return Ok(());
+        }
+        r.read_exact(&mut buf[1..ADTS_HEADER_LENGTH])?;
+        let protection_absent = buf[1] & 1 == 1;
+        let header_len = if protection_absent {
+            ADTS_HEADER_LENGTH
+        } else {
+            r.read_exact(&mut buf[ADTS_HEADER_LENGTH..])?;
+            ADTS_HEADER_WITH_CRC_LENGTH
+        };
+        let header = AdtsHeader::from_bytes(&buf[..header_len])?;
+        let skip = header.payload_length() as u64;
+        let skipped = io::copy(&mut io::Read::take(&mut r, skip), &mut io::sink())?;
+        if skipped < skip {
+            return Err(AdtsReadError::Io(io::ErrorKind::UnexpectedEof.into()));
+        }
+        f(offset, &header);
+        offset += u64::from(header.frame_length);
+    }
+}
+
+/// Summary statistics for an ADTS stream: frame count, total duration, and average and peak
+/// bitrate.
+///
+/// Statistics can be gathered from a complete buffer with
+/// [`from_slice()`](#method.from_slice), from an `io::Read` with
+/// [`from_reader()`](#method.from_reader), or incrementally by passing each frame header to
+/// [`push()`](#method.push).
+///
+/// Frames with a reserved sampling frequency index have no known duration; they are counted,
+/// but do not contribute to the duration or bitrate figures.
+#[derive(Debug, Default, Clone)]
+pub struct AdtsStats {
+    frame_count: u64,
+    byte_count: u64,
+    timed_byte_count: u64,
+    completed_duration: Duration,
+    current_rate: Option<u32>,
+    current_samples: u64,
+    peak_bitrate: Option<u32>,
+}
+
+impl AdtsStats {
+    pub fn new() -> AdtsStats {
+        AdtsStats::default()
+    }
+
+    /// Gathers statistics for all the frames in the given buffer, which must contain only
+    /// complete ADTS frames.
+    pub fn from_slice(buf: &[u8]) -> Result<AdtsStats, AdtsHeaderError> {
+        let mut stats = AdtsStats::new();
+        for frame in AdtsFrames::new(buf) {
+            stats.push(&frame?.header);
+        }
+        Ok(stats)
+    }
+
+    /// Gathers statistics for all the frames read from the given source, skipping over frame
+    /// payloads without retaining them.  Reading ends successfully when end-of-file is reached
+    /// exactly at a frame boundary.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: io::Read>(r: R) -> Result<AdtsStats, AdtsReadError> {
+        let mut stats = AdtsStats::new();
+        read_headers(r, |_offset, header| stats.push(header))?;
+        Ok(stats)
+    }
+
+    /// Accounts for one further frame in the stream.
+    pub fn push(&mut self, header: &AdtsHeader) {
+        self.frame_count += 1;
+        self.byte_count += u64::from(header.frame_length);
+        let rate = match header.sampling_frequency_index.frequency() {
+            Some(rate) => rate,
+            None => return,
+        };
+        self.timed_byte_count += u64::from(header.frame_length);
+        if self.current_rate != Some(rate) {
+            if let Some(prev) = self.current_rate {
+                self.completed_duration += samples_to_duration(self.current_samples, prev);
+            }
+            self.current_rate = Some(rate);
+            self.current_samples = 0;
+        }
+        let samples = header.sample_count();
+        self.current_samples += u64::from(samples);
+        let bitrate = u64::from(header.frame_length) * 8 * u64::from(rate) / u64::from(samples);
+        let bitrate = bitrate.min(u64::from(u32::MAX)) as u32;
+        if self.peak_bitrate.is_none_or(|peak| bitrate > peak) {
+            self.peak_bitrate = Some(bitrate);
+        }
+    }
+
+    /// The number of frames seen.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// The total size in bytes of the frames seen, including headers.
+    pub fn byte_count(&self) -> u64 {
+        self.byte_count
+    }
+
+    /// The total playback duration of the frames seen.
+    pub fn duration(&self) -> Duration {
+        match self.current_rate {
+            Some(rate) => self.completed_duration + samples_to_duration(self.current_samples, rate),
+            None => self.completed_duration,
+        }
+    }
+
+    /// The average bitrate in bits per second over the whole stream (counting header bytes as
+    /// well as payload), or `None` if no frames of known duration have been seen.
+    pub fn average_bitrate(&self) -> Option<u32> {
+        let nanos = self.duration().as_nanos();
+        if nanos == 0 {
+            return None;
+        }
+        let bitrate = u128::from(self.timed_byte_count) * 8 * 1_000_000_000 / nanos;
+        Some(bitrate.min(u128::from(u32::MAX)) as u32)
+    }
+
+    /// The highest bitrate, in bits per second, of any single frame seen, or `None` if no
+    /// frames of known duration have been seen.
+    pub fn peak_bitrate(&self) -> Option<u32> {
+        self.peak_bitrate
+    }
+}
+
+/// The position and presentation time of one frame, as recorded in an
+/// [`AdtsIndex`](struct.AdtsIndex.html)
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct AdtsIndexEntry {
+    /// Offset in bytes of the start of the frame header from the start of the stream
+    pub offset: u64,
+    /// The presentation time of the first sample in the frame, relative to the start of the
+    /// stream
+    pub time: Duration,
+}
+
+/// An index of the frames in an ADTS stream (requiring the `alloc` feature), supporting lookup
+/// of the frame to start decoding from in order to seek to a given time, without rescanning the
+/// stream.
+///
+/// ```rust
+/// # use mpeg4_audio_const::adts::*;
+/// # use std::time::Duration;
+/// # let mut buf = vec![0xff, 0xf1, 0x50, 0x80, 0x01, 0x1f, 0xfc, 0x00];
+/// # buf.extend(buf.clone());
+/// let index = AdtsIndex::from_slice(&buf).unwrap();
+/// let entry = index.lookup(Duration::from_millis(30)).unwrap();
+/// assert_eq!(8, entry.offset);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default, Clone)]
+pub struct AdtsIndex {
+    entries: Vec<AdtsIndexEntry>,
+    stats: AdtsStats,
+}
+
+#[cfg(feature = "alloc")]
+impl AdtsIndex {
+    pub fn new() -> AdtsIndex {
+        AdtsIndex::default()
+    }
+
+    /// Indexes all the frames in the given buffer, which must contain only complete ADTS frames.
+    pub fn from_slice(buf: &[u8]) -> Result<AdtsIndex, AdtsHeaderError> {
+        let mut index = AdtsIndex::new();
+        for frame in AdtsFrames::new(buf) {
+            let frame = frame?;
+            index.push(frame.offset as u64, &frame.header);
+        }
+        Ok(index)
+    }
+
+    /// Indexes all the frames read from the given source, skipping over frame payloads without
+    /// retaining them.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: io::Read>(r: R) -> Result<AdtsIndex, AdtsReadError> {
+        let mut index = AdtsIndex::new();
+        read_headers(r, |offset, header| index.push(offset, header))?;
+        Ok(index)
+    }
+
+    /// Adds an entry for the frame with the given header, found at the given byte offset in the
+    /// stream.  Frames must be pushed in stream order.
+    pub fn push(&mut self, offset: u64, header: &AdtsHeader) {
+        self.entries.push(AdtsIndexEntry {
+            offset,
+            time: self.stats.duration(),
+        });
+        self.stats.push(header);
+    }
+
+    /// All entries in the index, in stream order.
+    pub fn entries(&self) -> &[AdtsIndexEntry] {
+        &self.entries
+    }
+
+    /// The total duration of the indexed frames.
+    pub fn duration(&self) -> Duration {
+        self.stats.duration()
+    }
+
+    /// Statistics for the indexed frames.
+    pub fn stats(&self) -> &AdtsStats {
+        &self.stats
+    }
+
+    /// Finds the frame containing the given time (that is, the last frame whose start time is
+    /// not after `time`), or `None` if the index is empty.  Times beyond the end of the stream
+    /// give the final frame.
+    pub fn lookup(&self, time: Duration) -> Option<&AdtsIndexEntry> {
+        let pos = self.entries.partition_point(|e| e.time <= time);
+        self.entries.get(pos.saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    fn frame(len: u16, protected: bool) -> Vec<u8> {
+        let mut buf = vec![
+            0xff,
+            if protected { 0xf0 } else { 0xf1 },
+            0x50,
+            0x80 | (len >> 11) as u8,
+            (len >> 3) as u8,
+            ((len & 0b111) << 5) as u8 | 0x1f,
+            0xfc,
+        ];
+        buf.resize(usize::from(len), 0);
+        buf
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn header_fields() {
+        let buf = frame(100, true);
+        let header = AdtsHeader::from_bytes(&buf).unwrap();
+        assert_eq!(MpegVersion::Mpeg4, header.mpeg_version);
+        assert!(header.protected);
+        assert_eq!(Some(0), header.crc);
+        assert_eq!(100, header.frame_length);
+        assert_eq!(0x7ff, header.buffer_fullness);
+        assert_eq!(91, header.payload_length());
+        assert_eq!(
+            Err(AdtsHeaderError::NotEnoughData(TraceEntry {
+                bit_offset: 56,
+                bit_length: 16,
+                field: "crc_check",
+                value: None,
+            })),
+            AdtsHeader::from_bytes(&buf[..8])
+        );
+    }
+
+    #[test]
+    fn bad_sync_word() {
+        assert_eq!(
+            Err(AdtsHeaderError::BadSyncWord(TraceEntry {
+                bit_offset: 0,
+                bit_length: 12,
+                field: "syncword",
+                value: Some(0xffe),
+            })),
+            AdtsHeader::from_bytes(&[0xff, 0xe1, 0x50, 0x80, 0x2e, 0x7f, 0xfc])
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn strictness() {
+        use crate::options::ParseOptions;
+        // layer 1
+        let buf = [0xff, 0xf3, 0x50, 0x80, 0x2e, 0x7f, 0xfc];
+        let violation = Violation::UnexpectedValue(TraceEntry {
+            bit_offset: 13,
+            bit_length: 2,
+            field: "layer",
+            value: Some(1),
+        });
+        let parsed = AdtsHeader::from_bytes_with_options(&buf, &ParseOptions::PERMISSIVE).unwrap();
+        assert_eq!(1, parsed.value.layer);
+        assert_eq!(vec![violation], parsed.warnings);
+        assert_eq!(
+            Err(AdtsHeaderError::Violation(violation)),
+            AdtsHeader::from_bytes_with_options(&buf, &ParseOptions::STRICT)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn stats() {
+        let mut buf = frame(200, false);
+        buf.extend(frame(400, false));
+        let stats = AdtsStats::from_slice(&buf).unwrap();
+        assert_eq!(2, stats.frame_count());
+        assert_eq!(600, stats.byte_count());
+        assert_eq!(samples_to_duration(2048, 44100), stats.duration());
+        assert_eq!(Some(103359), stats.average_bitrate());
+        assert_eq!(Some(137812), stats.peak_bitrate());
+
+        let from_reader = AdtsStats::from_reader(&buf[..]).unwrap();
+        assert_eq!(stats.duration(), from_reader.duration());
+        assert_eq!(stats.peak_bitrate(), from_reader.peak_bitrate());
+
+        assert!(AdtsStats::from_slice(&buf[..599]).is_err());
+        assert!(matches!(
+            AdtsStats::from_reader(&buf[..599]),
+            Err(AdtsReadError::Io(_))
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn index() {
+        let mut buf = frame(200, false);
+        buf.extend(frame(400, true));
+        buf.extend(frame(300, false));
+        let index = AdtsIndex::from_slice(&buf).unwrap();
+        let frame_duration = samples_to_duration(1024, 44100);
+        assert_eq!(
+            &[
+                AdtsIndexEntry {
+                    offset: 0,
+                    time: Duration::from_secs(0)
+                },
+                AdtsIndexEntry {
+                    offset: 200,
+                    time: frame_duration
+                },
+                AdtsIndexEntry {
+                    offset: 600,
+                    time: samples_to_duration(2048, 44100)
+                },
+            ],
+            index.entries()
+        );
+        assert_eq!(0, index.lookup(frame_duration / 2).unwrap().offset);
+        assert_eq!(200, index.lookup(frame_duration).unwrap().offset);
+        assert_eq!(600, index.lookup(Duration::from_secs(10)).unwrap().offset);
+        assert_eq!(
+            index.entries(),
+            AdtsIndex::from_reader(&buf[..]).unwrap().entries()
+        );
+        assert_eq!(None, AdtsIndex::new().lookup(Duration::from_secs(0)));
+    }
+}
@@ -0,0 +1,195 @@
+//! Conversions between this crate's types and those of the
+//! [adts-reader](https://crates.io/crates/adts-reader) crate, so that code already using that
+//! crate's parser can hand its results to the rest of this crate.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::adts::AdtsHeader;
+//! # use core::convert::TryFrom;
+//! let buf = [0xff, 0xf1, 0x50, 0x80, 0x2e, 0x7f, 0xfc];
+//! let header = ::adts_reader::AdtsHeader::from_bytes(&buf).unwrap();
+//! assert_eq!(AudioObjectType::AAC_LC, AudioObjectType::from(header.audio_object_type()));
+//! let header = AdtsHeader::try_from(&header).unwrap();
+//! assert_eq!(SamplingFrequencyIndex::FREQ_44100, header.sampling_frequency_index);
+//! ```
+//!
+//! Conversions into the adts-reader types fail where the value is one that an ADTS header can't
+//! signal,
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use core::convert::TryFrom;
+//! assert!(::adts_reader::AudioObjectType::try_from(AudioObjectType::AAC_LTP).is_ok());
+//! assert!(::adts_reader::AudioObjectType::try_from(AudioObjectType::SBR).is_err());
+//! ```
+
+use crate::adts::{AdtsHeader, AdtsHeaderError, MpegVersion};
+use crate::{AudioObjectType, ChannelConfiguration, SamplingFrequencyIndex};
+use core::convert::TryFrom;
+
+impl From<::adts_reader::AudioObjectType> for AudioObjectType {
+    fn from(v: ::adts_reader::AudioObjectType) -> Self {
+        match v {
+            ::adts_reader::AudioObjectType::AacMain => AudioObjectType::AAC_MAIN,
+            ::adts_reader::AudioObjectType::AacLC => AudioObjectType::AAC_LC,
+            ::adts_reader::AudioObjectType::AacSSR => AudioObjectType::AAC_SSR,
+            ::adts_reader::AudioObjectType::AacLTP => AudioObjectType::AAC_LTP,
+        }
+    }
+}
+
+/// Fails, giving back the value, for object types other than the four an ADTS header can
+/// signal.
+impl TryFrom<AudioObjectType> for ::adts_reader::AudioObjectType {
+    type Error = AudioObjectType;
+
+    fn try_from(v: AudioObjectType) -> Result<Self, Self::Error> {
+        Ok(match v {
+            AudioObjectType::AAC_MAIN => ::adts_reader::AudioObjectType::AacMain,
+            AudioObjectType::AAC_LC => ::adts_reader::AudioObjectType::AacLC,
+            AudioObjectType::AAC_SSR => ::adts_reader::AudioObjectType::AacSSR,
+            AudioObjectType::AAC_LTP => ::adts_reader::AudioObjectType::AacLTP,
+            _ => return Err(v),
+        })
+    }
+}
+
+/// Fails, giving back the value, for `FreqReserved0xf` (the escape value).
+impl TryFrom<::adts_reader::SamplingFrequency> for SamplingFrequencyIndex {
+    type Error = u8;
+
+    fn try_from(v: ::adts_reader::SamplingFrequency) -> Result<Self, Self::Error> {
+        SamplingFrequencyIndex::try_from(v as u8).map_err(|_| v as u8)
+    }
+}
+
+impl From<SamplingFrequencyIndex> for ::adts_reader::SamplingFrequency {
+    fn from(v: SamplingFrequencyIndex) -> Self {
+        use ::adts_reader::SamplingFrequency::*;
+        // the escape value can't be represented by a SamplingFrequencyIndex
+        const FREQS: [::adts_reader::SamplingFrequency; 15] = [
+            Freq96000,
+            Freq88200,
+            Freq64000,
+            Freq48000,
+            Freq44100,
+            Freq32000,
+            Freq24000,
+            Freq22050,
+            Freq16000,
+            Freq12000,
+            Freq11025,
+            Freq8000,
+            FreqReserved0xc,
+            FreqReserved0xd,
+            FreqReserved0xe,
+        ];
+        FREQS[usize::from(u8::from(v))]
+    }
+}
+
+impl From<::adts_reader::ChannelConfiguration> for ChannelConfiguration {
+    fn from(v: ::adts_reader::ChannelConfiguration) -> Self {
+        // all values of the 3-bit ADTS field are representable
+        ChannelConfiguration::try_from(v as u8).unwrap()
+    }
+}
+
+/// Fails, giving back the value, for configurations above `7`, which the 3-bit ADTS field can't
+/// signal.
+impl TryFrom<ChannelConfiguration> for ::adts_reader::ChannelConfiguration {
+    type Error = ChannelConfiguration;
+
+    fn try_from(v: ChannelConfiguration) -> Result<Self, Self::Error> {
+        use ::adts_reader::ChannelConfiguration::*;
+        Ok(match u8::from(v) {
+            0 => ObjectTypeSpecificConfig,
+            1 => Mono,
+            2 => Stereo,
+            3 => Three,
+            4 => Four,
+            5 => Five,
+            6 => FiveOne,
+            7 => SevenOne,
+            _ => return Err(v),
+        })
+    }
+}
+
+impl From<::adts_reader::MpegVersion> for MpegVersion {
+    fn from(v: ::adts_reader::MpegVersion) -> Self {
+        match v {
+            ::adts_reader::MpegVersion::Mpeg2 => MpegVersion::Mpeg2,
+            ::adts_reader::MpegVersion::Mpeg4 => MpegVersion::Mpeg4,
+        }
+    }
+}
+
+impl From<MpegVersion> for ::adts_reader::MpegVersion {
+    fn from(v: MpegVersion) -> Self {
+        match v {
+            MpegVersion::Mpeg2 => ::adts_reader::MpegVersion::Mpeg2,
+            MpegVersion::Mpeg4 => ::adts_reader::MpegVersion::Mpeg4,
+        }
+    }
+}
+
+/// Converts a header parsed by adts-reader, failing with
+/// `AdtsHeaderError::BadSamplingFrequencyIndex` if it has the escape value, as
+/// [`AdtsHeader::from_bytes()`](../adts/struct.AdtsHeader.html#method.from_bytes) would.
+///
+/// adts-reader 0.3 does not extract all 11 bits of the `adts_buffer_fullness` field, so the
+/// `buffer_fullness` value is only correct when the upper three bits are zero.  Parse the
+/// header bytes directly with `AdtsHeader::from_bytes()` where this value matters.
+impl TryFrom<&::adts_reader::AdtsHeader<'_>> for AdtsHeader {
+    type Error = AdtsHeaderError;
+
+    fn try_from(h: &::adts_reader::AdtsHeader<'_>) -> Result<Self, Self::Error> {
+        Ok(AdtsHeader {
+            mpeg_version: h.mpeg_version().into(),
+            layer: 0,
+            protected: h.protection() == ::adts_reader::ProtectionIndicator::CrcPresent,
+            audio_object_type: h.audio_object_type().into(),
+            sampling_frequency_index: SamplingFrequencyIndex::try_from(h.sampling_frequency())
+                .map_err(AdtsHeaderError::BadSamplingFrequencyIndex)?,
+            private_bit: h.private_bit() != 0,
+            channel_configuration: h.channel_configuration().into(),
+            original_copy: h.originality() == ::adts_reader::Originality::Copy,
+            home: h.home() != 0,
+            copyright_identification_bit: h.copyright_identification_bit() != 0,
+            copyright_identification_start: h.copyright_identification_start()
+                == ::adts_reader::CopyrightIdentificationStart::Start,
+            frame_length: h.frame_length(),
+            buffer_fullness: h.adts_buffer_fullness(),
+            // adts-reader gives the actual number of blocks, rather than the signalled value
+            number_of_raw_data_blocks_in_frame: h.number_of_raw_data_blocks_in_frame() - 1,
+            crc: h.crc(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header() {
+        let buf = [0xff, 0xf0, 0x8c, 0xc0, 0x2e, 0x60, 0x01, 0x12, 0x34];
+        let theirs = ::adts_reader::AdtsHeader::from_bytes(&buf).unwrap();
+        let ours = AdtsHeader::from_bytes(&buf).unwrap();
+        assert_eq!(Ok(ours), AdtsHeader::try_from(&theirs));
+        for i in 0..=14 {
+            let sfi = SamplingFrequencyIndex::try_from(i).unwrap();
+            let freq = ::adts_reader::SamplingFrequency::from(sfi);
+            // adts-reader treats index 12 (7350 Hz) as reserved
+            if i < 12 {
+                assert_eq!(sfi.frequency(), freq.freq());
+            }
+            assert_eq!(Ok(sfi), SamplingFrequencyIndex::try_from(freq));
+        }
+        assert_eq!(
+            Err(ChannelConfiguration::SIX_ONE),
+            ::adts_reader::ChannelConfiguration::try_from(ChannelConfiguration::SIX_ONE)
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Mappings to the AAC profile values of Android's `MediaCodecInfo.CodecProfileLevel`
+//! (the `AACObject*` constants), as used for the `aac-profile` key of a `MediaFormat`.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::android::*;
+//! assert_eq!(Some(AAC_OBJECT_HE_PS), to_aac_object(AudioObjectType::PS));
+//! assert_eq!(Some(AudioObjectType::ER_AAC_ELD), from_aac_object(AAC_OBJECT_ELD));
+//! ```
+//!
+//! A decoder is configured by setting [`KEY_AAC_PROFILE`](constant.KEY_AAC_PROFILE.html) to
+//! the value given by [`aac_profile()`](fn.aac_profile.html), and `csd-0` to the bytes of the
+//! `AudioSpecificConfig` (or alternatively, for ADTS input, setting `is-adts` to `1`).
+
+use crate::asc::AudioSpecificConfig;
+use crate::AudioObjectType;
+
+/// The `MediaFormat` key (`KEY_AAC_PROFILE`) whose value is one of the `AACObject*` constants
+pub const KEY_AAC_PROFILE: &str = "aac-profile";
+/// The `MediaFormat` key (`KEY_IS_ADTS`) signalling ADTS-framed input to a decoder
+pub const KEY_IS_ADTS: &str = "is-adts";
+
+/// `AACObjectMain`
+pub const AAC_OBJECT_MAIN: i32 = 1;
+/// `AACObjectLC`
+pub const AAC_OBJECT_LC: i32 = 2;
+/// `AACObjectSSR`
+pub const AAC_OBJECT_SSR: i32 = 3;
+/// `AACObjectLTP`
+pub const AAC_OBJECT_LTP: i32 = 4;
+/// `AACObjectHE`, for HE-AAC
+pub const AAC_OBJECT_HE: i32 = 5;
+/// `AACObjectScalable`
+pub const AAC_OBJECT_SCALABLE: i32 = 6;
+/// `AACObjectERLC`
+pub const AAC_OBJECT_ERLC: i32 = 17;
+/// `AACObjectERScalable`
+pub const AAC_OBJECT_ER_SCALABLE: i32 = 20;
+/// `AACObjectLD`
+pub const AAC_OBJECT_LD: i32 = 23;
+/// `AACObjectHE_PS`, for HE-AAC v2
+pub const AAC_OBJECT_HE_PS: i32 = 29;
+/// `AACObjectELD`
+pub const AAC_OBJECT_ELD: i32 = 39;
+/// `AACObjectXHE`, for xHE-AAC (USAC)
+pub const AAC_OBJECT_XHE: i32 = 42;
+
+/// The `AACObject*` value for the given audio object type, or `None` if Android defines no
+/// constant for it.
+///
+/// Android numbers object types as the spec does, so the value is that of the object type.
+pub fn to_aac_object(audio_object_type: AudioObjectType) -> Option<i32> {
+    match audio_object_type {
+        AudioObjectType::AAC_MAIN
+        | AudioObjectType::AAC_LC
+        | AudioObjectType::AAC_SSR
+        | AudioObjectType::AAC_LTP
+        | AudioObjectType::SBR
+        | AudioObjectType::AAC_SCALABLE
+        | AudioObjectType::ER_AAC_LC
+        | AudioObjectType::ER_AAC_SCALABLE
+        | AudioObjectType::ER_AAC_LD
+        | AudioObjectType::PS
+        | AudioObjectType::ER_AAC_ELD
+        | AudioObjectType::USAC => Some(i32::from(u8::from(audio_object_type))),
+        _ => None,
+    }
+}
+
+/// The audio object type for the given `AACObject*` value, or `None` if the value is not one
+/// of the constants Android defines.
+pub fn from_aac_object(value: i32) -> Option<AudioObjectType> {
+    Some(match value {
+        AAC_OBJECT_MAIN => AudioObjectType::AAC_MAIN,
+        AAC_OBJECT_LC => AudioObjectType::AAC_LC,
+        AAC_OBJECT_SSR => AudioObjectType::AAC_SSR,
+        AAC_OBJECT_LTP => AudioObjectType::AAC_LTP,
+        AAC_OBJECT_HE => AudioObjectType::SBR,
+        AAC_OBJECT_SCALABLE => AudioObjectType::AAC_SCALABLE,
+        AAC_OBJECT_ERLC => AudioObjectType::ER_AAC_LC,
+        AAC_OBJECT_ER_SCALABLE => AudioObjectType::ER_AAC_SCALABLE,
+        AAC_OBJECT_LD => AudioObjectType::ER_AAC_LD,
+        AAC_OBJECT_HE_PS => AudioObjectType::PS,
+        AAC_OBJECT_ELD => AudioObjectType::ER_AAC_ELD,
+        AAC_OBJECT_XHE => AudioObjectType::USAC,
+        _ => return None,
+    })
+}
+
+/// The `aac-profile` value for the given configuration, which is `AACObjectHE` or
+/// `AACObjectHE_PS` when the presence of SBR or PS is signalled.
+pub fn aac_profile(asc: &AudioSpecificConfig) -> Option<i32> {
+    to_aac_object(asc.signalled_object_type())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn roundtrip() {
+        for aot in 0..=95 {
+            if let Ok(aot) = AudioObjectType::try_from(aot) {
+                if let Some(value) = to_aac_object(aot) {
+                    assert_eq!(Some(aot), from_aac_object(value));
+                }
+            }
+        }
+        assert_eq!(None, to_aac_object(AudioObjectType::ER_BSAC));
+        let asc = AudioSpecificConfig::from_bytes(&[0x2b, 0x11, 0x88, 0x00]).unwrap();
+        assert_eq!(Some(AAC_OBJECT_HE), aac_profile(&asc));
+    }
+}
@@ -0,0 +1,220 @@
+//! `Arbitrary` implementations, for building structured fuzzer inputs.
+//!
+//! Only values that this crate would produce when parsing are generated: the value types never
+//! take the escape values, and configuration structures have their optional fields present or
+//! absent as the syntax requires, so that (for example) an `AudioSpecificConfig` survives
+//! `to_bytes()` followed by `from_bytes()` unchanged.
+//!
+//! Generated `AudioSpecificConfig`s are limited to the `GASpecificConfig` object types
+//! (excluding `ER_BSAC`), with SBR or PS signalled hierarchically for `AAC_LC`, and with no
+//! program config element or error protection config.
+
+use crate::adts::{AdtsHeader, MpegVersion, ADTS_HEADER_LENGTH, ADTS_HEADER_WITH_CRC_LENGTH};
+use crate::asc::{
+    AudioExtension, AudioSpecificConfig, GaExtension, GaSpecificConfig, ResilienceFlags,
+    SamplingFrequency, SpecificConfig,
+};
+use crate::latm::StreamMuxConfig;
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use core::convert::TryFrom;
+
+/// The object types generated for an `AudioSpecificConfig`
+const GA_OBJECT_TYPES: &[AudioObjectType] = &[
+    AudioObjectType::AAC_MAIN,
+    AudioObjectType::AAC_LC,
+    AudioObjectType::AAC_SSR,
+    AudioObjectType::AAC_LTP,
+    AudioObjectType::AAC_SCALABLE,
+    AudioObjectType::TWIN_VQ,
+    AudioObjectType::ER_AAC_LC,
+    AudioObjectType::ER_AAC_LTP,
+    AudioObjectType::ER_AAC_SCALABLE,
+    AudioObjectType::ER_TWIN_VQ,
+    AudioObjectType::ER_AAC_LD,
+];
+
+impl<'a> Arbitrary<'a> for AudioObjectType {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        // skip the escape value
+        let value = u.int_in_range(0..=94)?;
+        Ok(AudioObjectType::try_from(if value < 31 { value } else { value + 1 }).unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for SamplingFrequencyIndex {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SamplingFrequencyIndex::try_from(u.int_in_range(0..=14)?).unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for ChannelConfiguration {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ChannelConfiguration::try_from(u.int_in_range(0..=15)?).unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for AudioProfileLevelIndication {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(AudioProfileLevelIndication::from(u8::arbitrary(u)?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for SamplingFrequency {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(if u.arbitrary()? {
+            SamplingFrequency::Index(u.arbitrary()?)
+        } else {
+            SamplingFrequency::Explicit(u.int_in_range(0..=0xff_ffff)?)
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ResilienceFlags {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(ResilienceFlags {
+            aac_section_data_resilience: u.arbitrary()?,
+            aac_scalefactor_data_resilience: u.arbitrary()?,
+            aac_spectral_data_resilience: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AudioSpecificConfig {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let audio_object_type = *u.choose(GA_OBJECT_TYPES)?;
+        let sampling_frequency = u.arbitrary()?;
+        let mut channel_configuration =
+            ChannelConfiguration::try_from(u.int_in_range(1..=15)?).unwrap();
+        let extension = if audio_object_type == AudioObjectType::AAC_LC && u.arbitrary()? {
+            let ps_present = u.arbitrary()?;
+            if ps_present {
+                // parametric stereo is carried with a mono core
+                channel_configuration = ChannelConfiguration::MONO;
+            }
+            Some(AudioExtension::Hierarchical {
+                ps_present,
+                sampling_frequency: u.arbitrary()?,
+                channel_configuration: None,
+            })
+        } else {
+            None
+        };
+        let error_resilient = u8::from(audio_object_type) >= 17;
+        let scalable = audio_object_type == AudioObjectType::AAC_SCALABLE
+            || audio_object_type == AudioObjectType::ER_AAC_SCALABLE;
+        let ga = GaSpecificConfig {
+            frame_length_flag: u.arbitrary()?,
+            core_coder_delay: if u.arbitrary()? {
+                Some(u.int_in_range(0..=0x3fff)?)
+            } else {
+                None
+            },
+            program_config_element: None,
+            layer_nr: if scalable {
+                Some(u.int_in_range(0..=7)?)
+            } else {
+                None
+            },
+            // the extension flag is set for (and only for) the error resilient object types
+            extension: if error_resilient {
+                Some(GaExtension {
+                    bsac: None,
+                    resilience: if audio_object_type == AudioObjectType::ER_TWIN_VQ {
+                        None
+                    } else {
+                        Some(u.arbitrary()?)
+                    },
+                    extension_flag3: false,
+                })
+            } else {
+                None
+            },
+        };
+        Ok(AudioSpecificConfig {
+            audio_object_type,
+            sampling_frequency,
+            channel_configuration,
+            extension,
+            specific_config: SpecificConfig::Ga(ga),
+            ep_config: if error_resilient {
+                Some(u.int_in_range(0..=1)?)
+            } else {
+                None
+            },
+            error_protection_specific_config: None,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AdtsHeader {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let protected = u.arbitrary()?;
+        let header_length = if protected {
+            ADTS_HEADER_WITH_CRC_LENGTH
+        } else {
+            ADTS_HEADER_LENGTH
+        };
+        Ok(AdtsHeader {
+            mpeg_version: if u.arbitrary()? {
+                MpegVersion::Mpeg2
+            } else {
+                MpegVersion::Mpeg4
+            },
+            layer: 0,
+            protected,
+            audio_object_type: AudioObjectType::try_from(u.int_in_range(1..=4)?).unwrap(),
+            sampling_frequency_index: u.arbitrary()?,
+            private_bit: u.arbitrary()?,
+            channel_configuration: ChannelConfiguration::try_from(u.int_in_range(0..=7)?).unwrap(),
+            original_copy: u.arbitrary()?,
+            home: u.arbitrary()?,
+            copyright_identification_bit: u.arbitrary()?,
+            copyright_identification_start: u.arbitrary()?,
+            frame_length: u.int_in_range(header_length as u16..=0x1fff)?,
+            buffer_fullness: u.int_in_range(0..=0x7ff)?,
+            number_of_raw_data_blocks_in_frame: u.int_in_range(0..=3)?,
+            crc: if protected {
+                Some(u.arbitrary()?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for StreamMuxConfig {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(StreamMuxConfig::new(u.arbitrary()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let data: Vec<u8> = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect();
+        let mut u = Unstructured::new(&data);
+        for _ in 0..100 {
+            let asc = AudioSpecificConfig::arbitrary(&mut u).unwrap();
+            assert_eq!(
+                Ok(asc.clone()),
+                AudioSpecificConfig::from_bytes(&asc.to_bytes())
+            );
+            let smc = StreamMuxConfig::arbitrary(&mut u).unwrap();
+            assert_eq!(
+                Ok(smc.clone()),
+                StreamMuxConfig::from_bytes(&smc.to_bytes())
+            );
+            let header = AdtsHeader::arbitrary(&mut u).unwrap();
+            assert!(usize::from(header.frame_length) >= header.header_length());
+            assert_ne!(31, u8::from(AudioObjectType::arbitrary(&mut u).unwrap()));
+        }
+    }
+}
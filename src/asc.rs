@@ -0,0 +1,2831 @@
+//! The `AudioSpecificConfig` structure, which carries the decoder configuration for an MPEG-4
+//! audio stream (e.g. within the `esds` box of an MP4 file, or the `config` parameter of an
+//! SDP description).
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")] {
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::asc::*;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! assert_eq!(AudioObjectType::AAC_LC, asc.audio_object_type);
+//! assert_eq!(Some(44100), asc.sample_rate());
+//! assert_eq!(ChannelConfiguration::STEREO, asc.channel_configuration);
+//! assert_eq!("mp4a.40.2", asc.codec_string());
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use crate::bits::BitWriter;
+use crate::bits::{BitReader, BitSink, EndOfData, PatchWriter, SliceWriter};
+#[cfg(feature = "alloc")]
+use crate::bitstream::{read_counted, write_bits, FromBitstream, ToBitstream};
+#[cfg(feature = "alloc")]
+use crate::describe::{yes_no, Describe, Describer, Description, Value};
+use crate::options::Violation;
+#[cfg(feature = "alloc")]
+use crate::options::{ParseOptions, Parsed};
+#[cfg(feature = "alloc")]
+use crate::pce::{PceError, ProgramConfigElement};
+#[cfg(feature = "alloc")]
+use crate::sbr::{SbrError, SbrHeader};
+#[cfg(feature = "alloc")]
+use crate::trace::AscVisitor;
+use crate::trace::TraceEntry;
+#[cfg(feature = "alloc")]
+use crate::AudioProfileLevelIndication;
+use crate::{
+    AudioObjectType, ChannelConfiguration, SamplingFrequencyIndex, AOT_ESCAPE_VALUE,
+    AOT_EXT_FIELD_BITS, AOT_FIELD_BITS, SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE,
+};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::format;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The `syncExtensionType` value introducing backward-compatible extension signalling
+pub const SYNC_EXTENSION_TYPE_AOT: u16 = 0x2b7;
+/// The `syncExtensionType` value introducing backward-compatible PS signalling
+pub const SYNC_EXTENSION_TYPE_PS: u16 = 0x548;
+
+/// Errors that may be produced while parsing an `AudioSpecificConfig`
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum AscError {
+    /// The data ended before the end of the structure, while reading the given element.
+    NotEnoughData(TraceEntry),
+    /// The structure did not conform to the spec, when parsed with
+    /// [`Strictness::Strict`](../options/enum.Strictness.html).
+    Violation(Violation),
+    /// The length of an extension exceeded the `max_explicit_length` of the
+    /// [`Limits`](../options/struct.Limits.html) in use, ending with the given field.
+    LengthLimitExceeded(TraceEntry),
+    /// The program config element within the `GASpecificConfig` could not be parsed.
+    #[cfg(feature = "alloc")]
+    Pce(PceError),
+}
+
+impl fmt::Display for AscError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AscError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            AscError::Violation(v) => v.fmt(f),
+            AscError::LengthLimitExceeded(at) => {
+                write!(f, "length limit exceeded, reading {}", at)
+            }
+            #[cfg(feature = "alloc")]
+            AscError::Pce(_) => f.write_str("invalid program config element"),
+        }
+    }
+}
+
+impl core::error::Error for AscError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "alloc")]
+            AscError::Pce(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EndOfData> for AscError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        AscError::NotEnoughData(at)
+    }
+}
+impl From<Violation> for AscError {
+    fn from(v: Violation) -> Self {
+        AscError::Violation(v)
+    }
+}
+#[cfg(feature = "alloc")]
+impl From<PceError> for AscError {
+    fn from(e: PceError) -> Self {
+        AscError::Pce(e)
+    }
+}
+#[cfg(feature = "alloc")]
+impl From<SbrError> for AscError {
+    fn from(e: SbrError) -> Self {
+        match e {
+            SbrError::NotEnoughData(at) => AscError::NotEnoughData(at),
+        }
+    }
+}
+
+/// A sampling frequency, which may be signalled either as an index into the table of common
+/// frequencies, or explicitly (when the index has the escape value `15`)
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SamplingFrequency {
+    Index(SamplingFrequencyIndex),
+    /// A 24-bit frequency value in Hz
+    Explicit(u32),
+}
+
+impl SamplingFrequency {
+    /// The frequency in Hz, or `None` if signalled using a reserved index value.
+    pub fn frequency(&self) -> Option<u32> {
+        match *self {
+            SamplingFrequency::Index(i) => i.frequency(),
+            SamplingFrequency::Explicit(f) => Some(f),
+        }
+    }
+
+    /// Formats the frequency for descriptions: the index and its frequency, or the explicit
+    /// frequency.
+    #[cfg(feature = "alloc")]
+    fn describe(self) -> String {
+        match self {
+            SamplingFrequency::Index(i) => format!("{}", Value(i)),
+            SamplingFrequency::Explicit(f) => format!("{} Hz (explicit)", f),
+        }
+    }
+
+    /// The equivalent value signalled by index if the frequency is given explicitly but is
+    /// exactly that of an index, or otherwise this value unchanged.
+    pub fn canonical(self) -> SamplingFrequency {
+        match self {
+            SamplingFrequency::Explicit(f) => SamplingFrequencyIndex::exact(f)
+                .map(SamplingFrequency::Index)
+                .unwrap_or(self),
+            SamplingFrequency::Index(_) => self,
+        }
+    }
+
+    /// Reads the fields for the core sampling frequency, or for the extension sampling
+    /// frequency (which differ only in their names).
+    fn read(r: &mut BitReader<'_>, extension: bool) -> Result<SamplingFrequency, EndOfData> {
+        let (index_name, frequency_name) = if extension {
+            (
+                "extensionSamplingFrequencyIndex",
+                "extensionSamplingFrequency",
+            )
+        } else {
+            ("samplingFrequencyIndex", "samplingFrequency")
+        };
+        let index = r.named(index_name).read_u8(4)?;
+        if index == SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE {
+            Ok(SamplingFrequency::Explicit(
+                r.named(frequency_name).read(24)?,
+            ))
+        } else {
+            // only the escape value can be rejected
+            let index = SamplingFrequencyIndex::try_from(index).unwrap();
+            if index.frequency().is_none() {
+                r.violation(Violation::ReservedValue);
+            }
+            Ok(SamplingFrequency::Index(index))
+        }
+    }
+
+    fn write<W: BitSink>(&self, w: &mut W) {
+        match *self {
+            SamplingFrequency::Index(i) => w.write(4, u32::from(u8::from(i))),
+            SamplingFrequency::Explicit(f) => {
+                w.write(4, u32::from(SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE));
+                w.write(24, f);
+            }
+        }
+    }
+}
+
+/// Bits of a structure that this crate does not interpret, held left-aligned in `data` (any
+/// unused bits of the final byte are zero)
+///
+/// The data is normally an owned `Vec<u8>`, but may instead be a `Cow<[u8]>` borrowing from the
+/// data parsed (see
+/// [`AudioSpecificConfig::from_bytes_borrowed()`](struct.AudioSpecificConfig.html#method.from_bytes_borrowed)).
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RawBits<D = Vec<u8>> {
+    pub data: D,
+    /// The number of meaningful bits in `data`
+    pub bit_length: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<D: AsRef<[u8]>> RawBits<D> {
+    fn read_remaining<'a>(r: &mut BitReader<'a>) -> RawBits<D>
+    where
+        D: RawData<'a>,
+    {
+        let start = r.position();
+        let bit_length = r.remaining();
+        r.skip(bit_length).unwrap();
+        RawBits {
+            data: D::from_bits(r.data(), start, start + bit_length),
+            bit_length,
+        }
+    }
+
+    fn write(&self, w: &mut BitWriter) {
+        w.write_bits(self.data.as_ref(), self.bit_length);
+    }
+
+    /// Converts the data to another kind of storage.
+    fn map_data<E>(self, f: impl FnOnce(D) -> E) -> RawBits<E> {
+        RawBits {
+            data: f(self.data),
+            bit_length: self.bit_length,
+        }
+    }
+}
+
+/// The kinds of storage for the data of [`RawBits`](struct.RawBits.html) taken from the data
+/// being parsed
+#[cfg(feature = "alloc")]
+pub trait RawData<'a>: AsRef<[u8]> {
+    /// Holds the bits of `data` from bit position `start` up to `end`, left-aligned.
+    fn from_bits(data: &'a [u8], start: usize, end: usize) -> Self;
+}
+
+/// Always copies the bits.
+#[cfg(feature = "alloc")]
+impl RawData<'_> for Vec<u8> {
+    fn from_bits(data: &[u8], start: usize, end: usize) -> Self {
+        let mut r = BitReader::with_bit_length(data, end);
+        r.skip(start).unwrap();
+        r.read_remaining().0
+    }
+}
+
+/// Borrows the bits when they start and end on byte boundaries, and otherwise copies them
+/// (since they must be shifted, or have the bits following them cleared).
+#[cfg(feature = "alloc")]
+impl<'a> RawData<'a> for Cow<'a, [u8]> {
+    fn from_bits(data: &'a [u8], start: usize, end: usize) -> Self {
+        if start % 8 == 0 && end % 8 == 0 {
+            Cow::Borrowed(&data[start / 8..end / 8])
+        } else {
+            Cow::Owned(Vec::from_bits(data, start, end))
+        }
+    }
+}
+
+/// Signalling of an extension object type (SBR, PS, or the BSAC extension) layered on top of
+/// the underlying audio object type of an `AudioSpecificConfig`
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AudioExtension {
+    /// _Explicit hierarchical signalling_: the first `audioObjectType` field was `SBR` or `PS`,
+    /// followed by the extension sampling frequency and then the underlying object type.
+    Hierarchical {
+        /// `true` if the first `audioObjectType` was `PS` (`29`) rather than `SBR` (`5`)
+        ps_present: bool,
+        sampling_frequency: SamplingFrequency,
+        /// The `extensionChannelConfiguration`, present only when the underlying object type
+        /// is `ER_BSAC`
+        channel_configuration: Option<ChannelConfiguration>,
+    },
+    /// _Explicit backward-compatible signalling_ of SBR, appended after the specific config so
+    /// that decoders unaware of SBR can ignore it.
+    BackwardCompatibleSbr {
+        sbr_present: bool,
+        /// The extension sampling frequency, present when `sbr_present` is `true`
+        sampling_frequency: Option<SamplingFrequency>,
+        /// The `psPresentFlag`, present when the further PS sync extension is given
+        ps_present: Option<bool>,
+    },
+    /// Backward-compatible signalling of the `ER_BSAC` extension.
+    BackwardCompatibleBsac {
+        sbr_present: bool,
+        /// The extension sampling frequency, present when `sbr_present` is `true`
+        sampling_frequency: Option<SamplingFrequency>,
+        channel_configuration: ChannelConfiguration,
+    },
+    /// Backward-compatible signalling of some other extension object type, which carries no
+    /// further fields.
+    BackwardCompatibleOther(AudioObjectType),
+}
+
+impl AudioExtension {
+    /// `true` if this extension signals the presence of SBR data.
+    pub fn sbr_present(&self) -> bool {
+        match *self {
+            AudioExtension::Hierarchical { .. } => true,
+            AudioExtension::BackwardCompatibleSbr { sbr_present, .. } => sbr_present,
+            AudioExtension::BackwardCompatibleBsac { sbr_present, .. } => sbr_present,
+            AudioExtension::BackwardCompatibleOther(_) => false,
+        }
+    }
+
+    /// `true` if this extension signals the presence of parametric stereo data.
+    pub fn ps_present(&self) -> bool {
+        match *self {
+            AudioExtension::Hierarchical { ps_present, .. } => ps_present,
+            AudioExtension::BackwardCompatibleSbr {
+                sbr_present,
+                ps_present,
+                ..
+            } => sbr_present && ps_present == Some(true),
+            _ => false,
+        }
+    }
+
+    /// The extension (i.e. SBR output) sampling frequency, if signalled.
+    pub fn sampling_frequency(&self) -> Option<SamplingFrequency> {
+        match *self {
+            AudioExtension::Hierarchical {
+                sampling_frequency, ..
+            } => Some(sampling_frequency),
+            AudioExtension::BackwardCompatibleSbr {
+                sampling_frequency, ..
+            } => sampling_frequency,
+            AudioExtension::BackwardCompatibleBsac {
+                sampling_frequency, ..
+            } => sampling_frequency,
+            AudioExtension::BackwardCompatibleOther(_) => None,
+        }
+    }
+}
+
+/// The extension fields of the `GASpecificConfig` specific to the `ER_BSAC` object type
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BsacConfig {
+    pub num_of_sub_frame: u8,
+    pub layer_length: u16,
+}
+
+/// The error resilience flags of the `GASpecificConfig` and `ELDSpecificConfig`
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ResilienceFlags {
+    pub aac_section_data_resilience: bool,
+    pub aac_scalefactor_data_resilience: bool,
+    pub aac_spectral_data_resilience: bool,
+}
+
+impl ResilienceFlags {
+    fn read(r: &mut BitReader<'_>) -> Result<ResilienceFlags, EndOfData> {
+        Ok(ResilienceFlags {
+            aac_section_data_resilience: r.named("aacSectionDataResilienceFlag").read_bool()?,
+            aac_scalefactor_data_resilience: r
+                .named("aacScalefactorDataResilienceFlag")
+                .read_bool()?,
+            aac_spectral_data_resilience: r.named("aacSpectralDataResilienceFlag").read_bool()?,
+        })
+    }
+
+    fn write<W: BitSink>(&self, w: &mut W) {
+        w.write_bool(self.aac_section_data_resilience);
+        w.write_bool(self.aac_scalefactor_data_resilience);
+        w.write_bool(self.aac_spectral_data_resilience);
+    }
+}
+
+/// The fields of the `GASpecificConfig` present when its `extensionFlag` is set
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GaExtension {
+    /// Present when the object type is `ER_BSAC`
+    pub bsac: Option<BsacConfig>,
+    /// Present for the `ER_AAC_LC`, `ER_AAC_LTP`, `ER_AAC_SCALABLE` and `ER_AAC_LD` object
+    /// types
+    pub resilience: Option<ResilienceFlags>,
+    pub extension_flag3: bool,
+}
+
+impl GaExtension {
+    fn read(
+        r: &mut BitReader<'_>,
+        audio_object_type: AudioObjectType,
+    ) -> Result<GaExtension, EndOfData> {
+        let bsac = if audio_object_type == AudioObjectType::ER_BSAC {
+            Some(BsacConfig {
+                num_of_sub_frame: r.named("numOfSubFrame").read_u8(5)?,
+                layer_length: r.named("layer_length").read_u16(11)?,
+            })
+        } else {
+            None
+        };
+        let resilience = match audio_object_type {
+            AudioObjectType::ER_AAC_LC
+            | AudioObjectType::ER_AAC_LTP
+            | AudioObjectType::ER_AAC_SCALABLE
+            | AudioObjectType::ER_AAC_LD => Some(ResilienceFlags::read(r)?),
+            _ => None,
+        };
+        let extension_flag3 = r.named("extensionFlag3").read_bool()?;
+        if extension_flag3 {
+            r.violation(Violation::ReservedValue);
+        }
+        Ok(GaExtension {
+            bsac,
+            resilience,
+            extension_flag3,
+        })
+    }
+
+    fn write<W: BitSink>(&self, w: &mut W) {
+        if let Some(ref bsac) = self.bsac {
+            w.write(5, u32::from(bsac.num_of_sub_frame));
+            w.write(11, u32::from(bsac.layer_length));
+        }
+        if let Some(ref resilience) = self.resilience {
+            resilience.write(w);
+        }
+        w.write_bool(self.extension_flag3);
+    }
+}
+
+/// The `GASpecificConfig`, used by the AAC family of object types
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GaSpecificConfig {
+    /// `true` if frames are 960 (rather than 1024) samples long (or 480 rather than 512 for
+    /// `ER_AAC_LD`)
+    pub frame_length_flag: bool,
+    /// The `coreCoderDelay`, present when `dependsOnCoreCoder` is set
+    pub core_coder_delay: Option<u16>,
+    /// The channel layout, present when `channelConfiguration` is `0`
+    pub program_config_element: Option<ProgramConfigElement>,
+    /// Present for the `AAC_SCALABLE` and `ER_AAC_SCALABLE` object types
+    pub layer_nr: Option<u8>,
+    pub extension: Option<GaExtension>,
+}
+
+#[cfg(feature = "alloc")]
+impl GaSpecificConfig {
+    fn read(
+        r: &mut BitReader<'_>,
+        channel_configuration: ChannelConfiguration,
+        audio_object_type: AudioObjectType,
+    ) -> Result<GaSpecificConfig, AscError> {
+        let (frame_length_flag, core_coder_delay, extension_flag) = read_ga_flags(r)?;
+        let program_config_element =
+            if channel_configuration == ChannelConfiguration::AOT_SPECIFIC_CONFIG {
+                Some(ProgramConfigElement::read(r)?)
+            } else {
+                None
+            };
+        let layer_nr = read_layer_nr(r, audio_object_type)?;
+        let extension = if extension_flag {
+            Some(GaExtension::read(r, audio_object_type)?)
+        } else {
+            None
+        };
+        Ok(GaSpecificConfig {
+            frame_length_flag,
+            core_coder_delay,
+            program_config_element,
+            layer_nr,
+            extension,
+        })
+    }
+
+    fn write(&self, w: &mut BitWriter) {
+        w.write_bool(self.frame_length_flag);
+        w.write_bool(self.core_coder_delay.is_some());
+        if let Some(delay) = self.core_coder_delay {
+            w.write(14, u32::from(delay));
+        }
+        w.write_bool(self.extension.is_some());
+        if let Some(ref pce) = self.program_config_element {
+            pce.write(w);
+        }
+        if let Some(layer_nr) = self.layer_nr {
+            w.write(3, u32::from(layer_nr));
+        }
+        if let Some(ref extension) = self.extension {
+            extension.write(w);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromBitstream for GaSpecificConfig {
+    /// The `channelConfiguration` and `audioObjectType` of the enclosing
+    /// `AudioSpecificConfig`
+    type Context = (ChannelConfiguration, AudioObjectType);
+    type Error = AscError;
+
+    fn from_bitstream_with_length(
+        data: &[u8],
+        (channel_configuration, audio_object_type): Self::Context,
+    ) -> Result<(Self, usize), AscError> {
+        read_counted(data, |r| {
+            Self::read(r, channel_configuration, audio_object_type)
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToBitstream for GaSpecificConfig {
+    fn to_bitstream(&self) -> RawBits {
+        write_bits(|w| self.write(w))
+    }
+}
+
+/// The `eldExtType` value terminating the list of ELD extensions
+pub const ELDEXT_TERM: u8 = 0b0000;
+
+/// An extension entry in the `ELDSpecificConfig`, whose content is not interpreted
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EldExtension {
+    pub eld_ext_type: u8,
+    pub data: Vec<u8>,
+}
+
+/// The low-delay SBR fields of the `ELDSpecificConfig`
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LdSbrConfig {
+    pub sampling_rate: bool,
+    pub crc_flag: bool,
+    /// One header per group of channel elements, as determined by the `channelConfiguration`
+    pub headers: Vec<SbrHeader>,
+}
+
+/// The `ELDSpecificConfig`, used by the `ER_AAC_ELD` object type
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EldSpecificConfig {
+    /// `true` if frames are 480 (rather than 512) samples long
+    pub frame_length_flag: bool,
+    pub resilience: ResilienceFlags,
+    /// Present when `ldSbrPresentFlag` is set
+    pub ld_sbr: Option<LdSbrConfig>,
+    pub extensions: Vec<EldExtension>,
+}
+
+#[cfg(feature = "alloc")]
+impl EldSpecificConfig {
+    fn read(
+        r: &mut BitReader<'_>,
+        channel_configuration: ChannelConfiguration,
+    ) -> Result<EldSpecificConfig, AscError> {
+        let frame_length_flag = r.named("frameLengthFlag").read_bool()?;
+        let resilience = ResilienceFlags::read(r)?;
+        let ld_sbr = if r.named("ldSbrPresentFlag").read_bool()? {
+            let sampling_rate = r.named("ldSbrSamplingRate").read_bool()?;
+            let crc_flag = r.named("ldSbrCrcFlag").read_bool()?;
+            let num_sbr_header = eld_sbr_header_count(channel_configuration);
+            let headers = (0..num_sbr_header)
+                .map(|_| SbrHeader::read(r))
+                .collect::<Result<_, _>>()?;
+            Some(LdSbrConfig {
+                sampling_rate,
+                crc_flag,
+                headers,
+            })
+        } else {
+            None
+        };
+        let mut extensions = vec![];
+        loop {
+            let eld_ext_type = r.named("eldExtType").read_u8(4)?;
+            if eld_ext_type == ELDEXT_TERM {
+                break;
+            }
+            let mut len = usize::from(r.named("eldExtLen").read_u8(4)?);
+            if len == 15 {
+                let add = r.named("eldExtLenAdd").read_u8(8)?;
+                len += usize::from(add);
+                if add == 255 {
+                    len += usize::from(r.named("eldExtLenAddAdd").read_u16(16)?);
+                }
+            }
+            if len * 8 > r.limits().max_explicit_length {
+                return Err(AscError::LengthLimitExceeded(r.last_read()));
+            }
+            let data = (0..len)
+                .map(|_| r.named("other_byte").read_u8(8))
+                .collect::<Result<_, _>>()?;
+            extensions.push(EldExtension { eld_ext_type, data });
+        }
+        Ok(EldSpecificConfig {
+            frame_length_flag,
+            resilience,
+            ld_sbr,
+            extensions,
+        })
+    }
+
+    fn write(&self, w: &mut BitWriter) {
+        w.write_bool(self.frame_length_flag);
+        self.resilience.write(w);
+        w.write_bool(self.ld_sbr.is_some());
+        if let Some(ref ld_sbr) = self.ld_sbr {
+            w.write_bool(ld_sbr.sampling_rate);
+            w.write_bool(ld_sbr.crc_flag);
+            for header in &ld_sbr.headers {
+                header.write(w);
+            }
+        }
+        for extension in &self.extensions {
+            w.write(4, u32::from(extension.eld_ext_type));
+            let len = extension.data.len();
+            if len < 15 {
+                w.write(4, len as u32);
+            } else if len < 15 + 255 {
+                w.write(4, 15);
+                w.write(8, (len - 15) as u32);
+            } else {
+                w.write(4, 15);
+                w.write(8, 255);
+                w.write(16, (len - 15 - 255) as u32);
+            }
+            for b in &extension.data {
+                w.write(8, u32::from(*b));
+            }
+        }
+        w.write(4, u32::from(ELDEXT_TERM));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromBitstream for EldSpecificConfig {
+    /// The `channelConfiguration` of the enclosing `AudioSpecificConfig`
+    type Context = ChannelConfiguration;
+    type Error = AscError;
+
+    fn from_bitstream_with_length(
+        data: &[u8],
+        channel_configuration: Self::Context,
+    ) -> Result<(Self, usize), AscError> {
+        read_counted(data, |r| Self::read(r, channel_configuration))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToBitstream for EldSpecificConfig {
+    fn to_bitstream(&self) -> RawBits {
+        write_bits(|w| self.write(w))
+    }
+}
+
+/// The object-type specific part of an `AudioSpecificConfig`
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpecificConfig<D = Vec<u8>> {
+    Ga(GaSpecificConfig),
+    Eld(EldSpecificConfig),
+    /// The specific config for an object type which this crate does not interpret.  Since its
+    /// length is unknown, this holds all remaining bits of the `AudioSpecificConfig`.
+    Unparsed(RawBits<D>),
+}
+
+/// The channel layout of an `AudioSpecificConfig`, as given by
+/// [`AudioSpecificConfig::channel_layout()`](struct.AudioSpecificConfig.html#method.channel_layout),
+/// distinguishing the layouts given by a standard `channelConfiguration` value from those
+/// described by a program config element (signalled by `channelConfiguration` `0`).
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ChannelLayout<'a> {
+    /// One of the layouts defined for a non-zero `channelConfiguration`
+    Standard(ChannelConfiguration),
+    /// The layout described by the program config element within the `GASpecificConfig`
+    FromPce(&'a ProgramConfigElement),
+    /// `channelConfiguration` is `0`, but the object type's specific config either has no
+    /// program config element or is not interpreted by this crate, so the layout is unknown
+    ObjectTypeSpecific,
+    /// `channelConfiguration` has a reserved value
+    Reserved(ChannelConfiguration),
+}
+
+#[cfg(feature = "alloc")]
+impl ChannelLayout<'_> {
+    /// The number of output channels of the layout (including LFE channels), or `None` if
+    /// the layout is unknown.
+    pub fn channel_count(&self) -> Option<u32> {
+        match *self {
+            ChannelLayout::Standard(conf) => conf.channel_count(),
+            ChannelLayout::FromPce(pce) => Some(pce.channel_count()),
+            ChannelLayout::ObjectTypeSpecific | ChannelLayout::Reserved(_) => None,
+        }
+    }
+
+    /// The number of LFE channels in the layout, or `None` if the layout is unknown.
+    pub fn lfe_channel_count(&self) -> Option<u32> {
+        match *self {
+            ChannelLayout::Standard(ChannelConfiguration::TWENTY_TWO_TWO) => Some(2),
+            ChannelLayout::Standard(conf) => match u8::from(conf) {
+                1..=5 => Some(0),
+                _ => Some(1),
+            },
+            ChannelLayout::FromPce(pce) => Some(pce.lfe_channel_elements.len() as u32),
+            ChannelLayout::ObjectTypeSpecific | ChannelLayout::Reserved(_) => None,
+        }
+    }
+}
+
+/// A field that differs between two configurations, as found by
+/// [`AudioSpecificConfig::diff()`](struct.AudioSpecificConfig.html#method.diff)
+///
+/// The `Display` implementation gives e.g. `sampling_frequency: 44100 Hz → 48000 Hz`.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FieldDiff {
+    /// The name of the field (or of a property derived from several fields, such as
+    /// `sbr_present`)
+    pub field: &'static str,
+    /// The value in the configuration on which `diff()` was called
+    pub old: String,
+    /// The value in the other configuration
+    pub new: String,
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} → {}", self.field, self.old, self.new)
+    }
+}
+
+/// Collects the differences found by `AudioSpecificConfig::diff()`.
+#[cfg(feature = "alloc")]
+struct Differ(Vec<FieldDiff>);
+
+#[cfg(feature = "alloc")]
+impl Differ {
+    fn field<T: PartialEq + fmt::Debug>(&mut self, field: &'static str, old: T, new: T) {
+        if old != new {
+            self.0.push(FieldDiff {
+                field,
+                old: format!("{:?}", old),
+                new: format!("{:?}", new),
+            });
+        }
+    }
+
+    /// Like `field()`, but for the value types, formatted with `Display` (e.g. `AAC LC`) where
+    /// the `names` feature provides it.
+    #[cfg(feature = "names")]
+    fn value<T: PartialEq + fmt::Display>(&mut self, field: &'static str, old: T, new: T) {
+        if old != new {
+            self.0.push(FieldDiff {
+                field,
+                old: format!("{}", old),
+                new: format!("{}", new),
+            });
+        }
+    }
+
+    #[cfg(not(feature = "names"))]
+    fn value<T: PartialEq + fmt::Debug>(&mut self, field: &'static str, old: T, new: T) {
+        self.field(field, old, new);
+    }
+
+    /// Like `field()`, but formatting `None` as `absent` and `Some(v)` as just `v`.
+    fn optional<T: PartialEq + fmt::Debug>(
+        &mut self,
+        field: &'static str,
+        old: Option<T>,
+        new: Option<T>,
+    ) {
+        if old != new {
+            let describe = |v: Option<T>| match v {
+                Some(v) => format!("{:?}", v),
+                None => String::from("absent"),
+            };
+            self.0.push(FieldDiff {
+                field,
+                old: describe(old),
+                new: describe(new),
+            });
+        }
+    }
+
+    fn frequency(
+        &mut self,
+        field: &'static str,
+        old: Option<SamplingFrequency>,
+        new: Option<SamplingFrequency>,
+    ) {
+        if old != new {
+            let describe = |v: Option<SamplingFrequency>| match v {
+                Some(SamplingFrequency::Index(i)) => format!("{}", i),
+                Some(SamplingFrequency::Explicit(f)) => format!("{} Hz (explicit)", f),
+                None => String::from("absent"),
+            };
+            self.0.push(FieldDiff {
+                field,
+                old: describe(old),
+                new: describe(new),
+            });
+        }
+    }
+}
+
+/// A parsed `AudioSpecificConfig`, which may be serialized again with
+/// [`to_bytes()`](#method.to_bytes)
+///
+/// Where SBR or PS are signalled hierarchically (i.e. the first `audioObjectType` field is
+/// `SBR` or `PS`), `audio_object_type` gives the _underlying_ object type (e.g. `AAC_LC`), and
+/// the extension is described by `extension`.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::asc::*;
+/// // HE-AAC v2: PS, 24kHz core, mono, 48kHz extension, AAC LC
+/// let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+/// assert_eq!(AudioObjectType::AAC_LC, asc.audio_object_type);
+/// assert!(asc.extension.as_ref().unwrap().ps_present());
+/// assert_eq!("mp4a.40.29", asc.codec_string());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AudioSpecificConfig<D = Vec<u8>> {
+    pub audio_object_type: AudioObjectType,
+    pub sampling_frequency: SamplingFrequency,
+    /// The signalled `channelConfiguration`; see
+    /// [`channel_layout()`](#method.channel_layout) for the layout this describes
+    pub channel_configuration: ChannelConfiguration,
+    pub extension: Option<AudioExtension>,
+    pub specific_config: SpecificConfig<D>,
+    /// The `epConfig` value, present for error resilient object types (when the specific
+    /// config was parsed)
+    pub ep_config: Option<u8>,
+    /// The `ErrorProtectionSpecificConfig` and any following data, present when `ep_config`
+    /// is `2` or `3`; this crate does not interpret it, so it holds all remaining bits
+    pub error_protection_specific_config: Option<RawBits<D>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<D: AsRef<[u8]>> AudioSpecificConfig<D> {
+    fn read_with<'a>(r: &mut BitReader<'a>) -> Result<AudioSpecificConfig<D>, AscError>
+    where
+        D: RawData<'a>,
+    {
+        let (audio_object_type, sampling_frequency, channel_configuration, mut extension) =
+            read_header(r)?;
+        let specific_config = match u8::from(audio_object_type) {
+            1..=4 | 6 | 7 | 17 | 19..=23 => SpecificConfig::Ga(GaSpecificConfig::read(
+                r,
+                channel_configuration,
+                audio_object_type,
+            )?),
+            39 => SpecificConfig::Eld(EldSpecificConfig::read(r, channel_configuration)?),
+            _ => SpecificConfig::Unparsed(RawBits::read_remaining(
+                r.named("AudioObjectTypeSpecificConfig"),
+            )),
+        };
+        let mut ep_config = None;
+        let mut error_protection_specific_config = None;
+        if let SpecificConfig::Unparsed(_) = specific_config {
+            // the remaining fields can't be located
+        } else {
+            if has_ep_config(audio_object_type) {
+                let ep = r.named("epConfig").read_u8(2)?;
+                ep_config = Some(ep);
+                if ep == 2 || ep == 3 {
+                    error_protection_specific_config = Some(RawBits::read_remaining(
+                        r.named("ErrorProtectionSpecificConfig"),
+                    ));
+                }
+            }
+            if extension.is_none() && r.remaining() >= 16 {
+                extension = read_sync_extension(r)?;
+            }
+        }
+        Ok(AudioSpecificConfig {
+            audio_object_type,
+            sampling_frequency,
+            channel_configuration,
+            extension,
+            specific_config,
+            ep_config,
+            error_protection_specific_config,
+        })
+    }
+
+    /// Converts the data of any uninterpreted payloads to another kind of storage.
+    fn map_data<E>(self, f: impl Fn(D) -> E) -> AudioSpecificConfig<E> {
+        AudioSpecificConfig {
+            audio_object_type: self.audio_object_type,
+            sampling_frequency: self.sampling_frequency,
+            channel_configuration: self.channel_configuration,
+            extension: self.extension,
+            specific_config: match self.specific_config {
+                SpecificConfig::Ga(ga) => SpecificConfig::Ga(ga),
+                SpecificConfig::Eld(eld) => SpecificConfig::Eld(eld),
+                SpecificConfig::Unparsed(bits) => SpecificConfig::Unparsed(bits.map_data(&f)),
+            },
+            ep_config: self.ep_config,
+            error_protection_specific_config: self
+                .error_protection_specific_config
+                .map(|bits| bits.map_data(&f)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> AudioSpecificConfig<Cow<'a, [u8]>> {
+    /// Parses an `AudioSpecificConfig` as for
+    /// [`from_bytes()`](struct.AudioSpecificConfig.html#method.from_bytes), but with any
+    /// payloads that this crate does not interpret borrowing from the given buffer where they
+    /// start and end on byte boundaries, rather than being copied.  Payloads not aligned to
+    /// byte boundaries are still copied, since `RawBits` holds its bits left-aligned.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// # use std::borrow::Cow;
+    /// // ER AAC ELD with epConfig 2, followed by an ErrorProtectionSpecificConfig (not
+    /// // interpreted by this crate) starting on a byte boundary
+    /// let buf = [0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x02, 0x12, 0x34];
+    /// let asc = AudioSpecificConfig::from_bytes_borrowed(&buf).unwrap();
+    /// let ep = asc.error_protection_specific_config.as_ref().unwrap();
+    /// assert!(matches!(ep.data, Cow::Borrowed(&[0x12, 0x34])));
+    /// // the owned form, with the full API
+    /// let owned = AudioSpecificConfig::from(asc);
+    /// assert_eq!(buf.to_vec(), owned.to_bytes());
+    /// ```
+    pub fn from_bytes_borrowed(buf: &'a [u8]) -> Result<Self, AscError> {
+        Self::read_with(&mut BitReader::new(buf))
+    }
+
+    /// Copies any borrowed data, so that the config can outlive the buffer it was parsed from
+    /// (e.g. to be cached alongside configs which were never borrowed).
+    pub fn into_owned(self) -> AudioSpecificConfig<Cow<'static, [u8]>> {
+        self.map_data(|data| Cow::Owned(data.into_owned()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<AudioSpecificConfig<Cow<'a, [u8]>>> for AudioSpecificConfig {
+    fn from(asc: AudioSpecificConfig<Cow<'a, [u8]>>) -> Self {
+        asc.map_data(Cow::into_owned)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<AudioSpecificConfig> for AudioSpecificConfig<Cow<'static, [u8]>> {
+    fn from(asc: AudioSpecificConfig) -> Self {
+        asc.map_data(Cow::Owned)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AudioSpecificConfig {
+    /// Parses an `AudioSpecificConfig` from the given buffer.
+    pub fn from_bytes(buf: &[u8]) -> Result<AudioSpecificConfig, AscError> {
+        Self::read(&mut BitReader::new(buf))
+    }
+
+    /// Parses an `AudioSpecificConfig` as for [`from_bytes()`](#method.from_bytes), checking
+    /// for reserved values and other violations of the spec as the given options require.
+    ///
+    /// Data following the config is only checked if
+    /// [`check_trailing_data`](../options/struct.ParseOptions.html#structfield.check_trailing_data)
+    /// is set, and is otherwise ignored, as by `from_bytes()`.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// # use mpeg4_audio_const::options::*;
+    /// let buf = [0x12, 0x10, 0x00, 0x00];
+    /// assert!(AudioSpecificConfig::from_bytes_with_options(&buf, &ParseOptions::STRICT).is_ok());
+    /// let options = ParseOptions {
+    ///     check_trailing_data: true,
+    ///     ..ParseOptions::STRICT
+    /// };
+    /// assert!(AudioSpecificConfig::from_bytes_with_options(&buf, &options).is_err());
+    /// ```
+    pub fn from_bytes_with_options(
+        buf: &[u8],
+        options: &ParseOptions,
+    ) -> Result<Parsed<AudioSpecificConfig>, AscError> {
+        options.parse(buf, |r| {
+            let asc = Self::read(r)?;
+            if options.check_trailing_data {
+                TrailingBits::read(r)?;
+            }
+            Ok(asc)
+        })
+    }
+
+    /// Parses an `AudioSpecificConfig` as for [`from_bytes()`](#method.from_bytes), also
+    /// describing the bits following it in the buffer.
+    ///
+    /// Anything other than zero padding to a byte boundary is a common symptom of the length
+    /// of the config being given wrongly by the enclosing container (e.g. an `esds` box).
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// let (_, trailing) = AudioSpecificConfig::from_bytes_with_trailing(&[0x12, 0x10]).unwrap();
+    /// assert!(trailing.is_padding);
+    /// let (_, trailing) =
+    ///     AudioSpecificConfig::from_bytes_with_trailing(&[0x12, 0x10, 0x00, 0x00]).unwrap();
+    /// assert_eq!(16, trailing.bit_length);
+    /// assert!(!trailing.is_padding);
+    /// ```
+    pub fn from_bytes_with_trailing(
+        buf: &[u8],
+    ) -> Result<(AudioSpecificConfig, TrailingBits), AscError> {
+        let mut r = BitReader::new(buf);
+        let asc = Self::read(&mut r)?;
+        let trailing = TrailingBits::read(&mut r)?;
+        Ok((asc, trailing))
+    }
+
+    /// Parses an `AudioSpecificConfig` as for [`from_bytes()`](#method.from_bytes), also
+    /// returning a trace of the fields read (up to the point of any error).
+    pub fn from_bytes_traced(
+        buf: &[u8],
+    ) -> (Result<AudioSpecificConfig, AscError>, Vec<TraceEntry>) {
+        let mut trace = Vec::new();
+        let result = Self::read(&mut BitReader::visiting(buf, &mut trace));
+        (result, trace)
+    }
+
+    /// Parses an `AudioSpecificConfig`, passing each field to the given visitor rather than
+    /// returning the parsed structure.  If the visitor stops the parse early, `Ok` is
+    /// returned.
+    pub fn parse_with(buf: &[u8], visitor: &mut impl AscVisitor) -> Result<(), AscError> {
+        let mut r = BitReader::visiting(buf, visitor);
+        match Self::read(&mut r) {
+            Err(_) if r.stopped() => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    pub(crate) fn read(r: &mut BitReader<'_>) -> Result<AudioSpecificConfig, AscError> {
+        Self::read_with(r)
+    }
+    /// Serializes this configuration.
+    ///
+    /// Fields are written as given, so for the result to be meaningful the optional fields
+    /// should be present or absent as required by the syntax for the `audio_object_type` and
+    /// `channel_configuration`, as they are in a parsed value.  Numeric values too large for
+    /// their fields are truncated.
+    ///
+    /// Any data following a backward-compatible extension within the parsed input is not
+    /// retained, so is not reproduced.  To reproduce the parsed input exactly, use
+    /// [`Verbatim`](../bitstream/struct.Verbatim.html).
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// let buf = [0xeb, 0x09, 0x88, 0x00];
+    /// let asc = AudioSpecificConfig::from_bytes(&buf).unwrap();
+    /// assert_eq!(buf.to_vec(), asc.to_bytes());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = BitWriter::new();
+        self.write(&mut w);
+        w.into_bytes()
+    }
+
+    pub(crate) fn write(&self, w: &mut BitWriter) {
+        write_header(
+            w,
+            self.audio_object_type,
+            self.sampling_frequency,
+            self.channel_configuration,
+            self.extension.as_ref(),
+        );
+        match self.specific_config {
+            SpecificConfig::Ga(ref ga) => ga.write(w),
+            SpecificConfig::Eld(ref eld) => eld.write(w),
+            SpecificConfig::Unparsed(ref bits) => bits.write(w),
+        }
+        if let Some(ep_config) = self.ep_config {
+            w.write(2, u32::from(ep_config));
+        }
+        if let Some(ref bits) = self.error_protection_specific_config {
+            bits.write(w);
+        }
+        if let Some(ref extension) = self.extension {
+            write_sync_extension(w, extension);
+        }
+    }
+
+    /// A best-effort choice of the _audio profile and level indication_ for this
+    /// configuration, e.g. for use as the `profile-level-id` SDP parameter.
+    ///
+    /// Only the AAC, High Efficiency AAC and High Efficiency AAC v2 profiles are considered,
+    /// with the lowest level of the appropriate profile being chosen whose limits on channel
+    /// count (not counting LFE channels) and sampling rate accommodate the configuration.
+    /// Other limits imposed by the levels (e.g. on the use of coupling channels) are not
+    /// checked.  For other object types, or when the channel count or sampling rate are
+    /// unknown, `NOT_SPECIFIED` is returned.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// # use mpeg4_audio_const::asc::*;
+    /// let asc = AudioSpecificConfig::from_bytes(&[0x11, 0x90]).unwrap();
+    /// assert_eq!(AudioProfileLevelIndication::AAC_L2, asc.audio_profile_level_indication());
+    /// let he_aac_v2 = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+    /// assert_eq!(
+    ///     AudioProfileLevelIndication::HE_AAC_V2_L2,
+    ///     he_aac_v2.audio_profile_level_indication()
+    /// );
+    /// ```
+    pub fn audio_profile_level_indication(&self) -> AudioProfileLevelIndication {
+        self.profile_level()
+            .unwrap_or(AudioProfileLevelIndication::NOT_SPECIFIED)
+    }
+
+    fn profile_level(&self) -> Option<AudioProfileLevelIndication> {
+        if self.audio_object_type != AudioObjectType::AAC_LC {
+            return None;
+        }
+        let channels = self.main_channel_count()?;
+        let core_rate = self.sample_rate()?;
+        let (sbr, ps, rate) = match self.extension {
+            Some(ref ext) if ext.sbr_present() => {
+                let ext_rate = match ext.sampling_frequency() {
+                    Some(f) => f.frequency()?,
+                    None => core_rate * 2,
+                };
+                (true, ext.ps_present(), core_rate.max(ext_rate))
+            }
+            _ => (false, false, core_rate),
+        };
+        // levels in order of increasing capability: (max channels, max sampling rate, value)
+        let levels: &[(u32, u32, u8)] = if !sbr {
+            &[
+                (2, 24000, 0x28),
+                (2, 48000, 0x29),
+                (5, 48000, 0x2a),
+                (5, 96000, 0x2b),
+                (7, 48000, 0x50),
+                (7, 96000, 0x51),
+            ]
+        } else {
+            if channels <= 2 && rate <= 48000 {
+                // Level 2 limits the AAC core to 24kHz, with SBR output at up to 48kHz
+                let level = if core_rate <= 24000 { 0x2c } else { 0x2d };
+                return Some(AudioProfileLevelIndication(level + if ps { 4 } else { 0 }));
+            }
+            if ps {
+                &[
+                    (5, 48000, 0x32),
+                    (5, 96000, 0x33),
+                    (7, 48000, 0x54),
+                    (7, 96000, 0x55),
+                ]
+            } else {
+                &[
+                    (5, 48000, 0x2e),
+                    (5, 96000, 0x2f),
+                    (7, 48000, 0x52),
+                    (7, 96000, 0x53),
+                ]
+            }
+        };
+        levels
+            .iter()
+            .find(|(max_channels, max_rate, _)| channels <= *max_channels && rate <= *max_rate)
+            .map(|(_, _, value)| AudioProfileLevelIndication(*value))
+    }
+
+    /// The number of channels, excluding LFE channels, as counted by the profile levels.
+    fn main_channel_count(&self) -> Option<u32> {
+        let layout = self.channel_layout();
+        Some(layout.channel_count()? - layout.lfe_channel_count()?)
+    }
+
+    /// The core sampling rate in Hz, whether it was signalled by index or explicitly, or `None`
+    /// if signalled using a reserved index value.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// // HE-AAC v2: 24kHz core, 48kHz extension
+    /// let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+    /// assert_eq!(Some(24000), asc.sample_rate());
+    /// assert_eq!(Some(48000), asc.output_sample_rate());
+    /// ```
+    pub fn sample_rate(&self) -> Option<u32> {
+        self.sampling_frequency.frequency()
+    }
+
+    /// The output sampling rate in Hz: the extension sampling rate if one is signalled (i.e.
+    /// for explicitly signalled SBR), and otherwise the core
+    /// [`sample_rate()`](#method.sample_rate).
+    pub fn output_sample_rate(&self) -> Option<u32> {
+        self.extension
+            .as_ref()
+            .and_then(|ext| ext.sampling_frequency())
+            .unwrap_or(self.sampling_frequency)
+            .frequency()
+    }
+
+    /// The number of output channels: that of the [`channel_layout()`](#method.channel_layout),
+    /// except that a mono core with parametric stereo signalled produces stereo output.
+    /// Returns `None` if the layout is unknown (i.e. for a reserved `channelConfiguration`
+    /// value, or a `channelConfiguration` of `0` without a parsed program config element).
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// // HE-AAC v2: PS, mono core
+    /// let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+    /// assert_eq!(Some(2), asc.channel_count());
+    /// ```
+    pub fn channel_count(&self) -> Option<u32> {
+        let count = self.channel_layout().channel_count()?;
+        let ps_present = self.extension.as_ref().is_some_and(|e| e.ps_present());
+        Some(if count == 1 && ps_present { 2 } else { count })
+    }
+
+    /// The channel layout, which for a `channel_configuration` of `0` is described by the
+    /// program config element within the `GASpecificConfig` (if there is one).
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// # use mpeg4_audio_const::asc::*;
+    /// let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+    /// assert_eq!(ChannelLayout::Standard(ChannelConfiguration::STEREO), asc.channel_layout());
+    /// // channelConfiguration 0, with a PCE describing a single CPE
+    /// let asc = AudioSpecificConfig::from_bytes(&[0x11, 0x80, 0x04, 0xc4, 0x00, 0x00, 0x20, 0x00])
+    ///     .unwrap();
+    /// match asc.channel_layout() {
+    ///     ChannelLayout::FromPce(pce) => assert_eq!(2, pce.channel_count()),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// ```
+    pub fn channel_layout(&self) -> ChannelLayout<'_> {
+        if self.channel_configuration != ChannelConfiguration::AOT_SPECIFIC_CONFIG {
+            return if self.channel_configuration.channel_count().is_some() {
+                ChannelLayout::Standard(self.channel_configuration)
+            } else {
+                ChannelLayout::Reserved(self.channel_configuration)
+            };
+        }
+        match self.specific_config {
+            SpecificConfig::Ga(GaSpecificConfig {
+                program_config_element: Some(ref pce),
+                ..
+            }) => ChannelLayout::FromPce(pce),
+            _ => ChannelLayout::ObjectTypeSpecific,
+        }
+    }
+
+    /// The RFC 6381 codec string for this configuration, e.g. `"mp4a.40.2"`.
+    ///
+    /// When the presence of SBR or PS is signalled (whether hierarchically or in the
+    /// backward-compatible form) the codec string names the `SBR` (`"mp4a.40.5"`) or `PS`
+    /// (`"mp4a.40.29"`) object type respectively, as is expected by HLS and DASH clients;
+    /// otherwise it names the `audio_object_type`.
+    pub fn codec_string(&self) -> String {
+        self.signalled_object_type().codec_string()
+    }
+
+    /// A copy of this configuration in a normalized form, so that configurations which a
+    /// decoder treats identically also compare equal and serialize identically.
+    ///
+    /// Currently the only normalization is that sampling frequencies (both core and extension)
+    /// given explicitly, but exactly matching a frequency of the index table, are instead
+    /// signalled by index.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// let mut asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+    /// let indexed = asc.clone();
+    /// asc.sampling_frequency = SamplingFrequency::Explicit(44100);
+    /// assert_ne!(indexed.to_bytes(), asc.to_bytes());
+    /// assert_eq!(indexed, asc.canonicalize());
+    /// ```
+    pub fn canonicalize(&self) -> AudioSpecificConfig {
+        let mut asc = self.clone();
+        asc.sampling_frequency = asc.sampling_frequency.canonical();
+        match asc.extension {
+            Some(AudioExtension::Hierarchical {
+                ref mut sampling_frequency,
+                ..
+            }) => *sampling_frequency = sampling_frequency.canonical(),
+            Some(AudioExtension::BackwardCompatibleSbr {
+                ref mut sampling_frequency,
+                ..
+            })
+            | Some(AudioExtension::BackwardCompatibleBsac {
+                ref mut sampling_frequency,
+                ..
+            }) => *sampling_frequency = sampling_frequency.map(SamplingFrequency::canonical),
+            Some(AudioExtension::BackwardCompatibleOther(_)) | None => {}
+        }
+        asc
+    }
+
+    /// A 64-bit hash of the [canonical form](#method.canonicalize) of this configuration,
+    /// for keying caches or deduplicating initialization segments by audio configuration.
+    ///
+    /// Unlike the `Hash` implementations of the standard library, the result is stable: it is
+    /// the 64-bit FNV-1a hash of the bytes produced by `canonicalize().to_bytes()`, and will
+    /// only change (in a release with a new major version) if the canonical form does.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+    /// assert_eq!(0x086f_a407_b51f_4d9f, asc.stable_hash());
+    /// ```
+    pub fn stable_hash(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+        self.canonicalize()
+            .to_bytes()
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, b| {
+                (hash ^ u64::from(*b)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// The fields that differ between this configuration and the given one, with both values,
+    /// in the order that the fields appear in the syntax; empty if the configurations are
+    /// equal.
+    ///
+    /// The properties of the extension (`sbr_present`, `ps_present` and
+    /// `extension_sampling_frequency`) are compared rather than the form in which it is
+    /// signalled, which is reported as a difference in `extension` only if the properties are
+    /// all equal.  Likewise the fields of a `GASpecificConfig` are compared individually.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// let a = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+    /// let b = AudioSpecificConfig::from_bytes(&[0x11, 0x90]).unwrap();
+    /// let diff = a.diff(&b);
+    /// assert_eq!(1, diff.len());
+    /// assert_eq!("sampling_frequency: 44100 Hz → 48000 Hz", diff[0].to_string());
+    /// ```
+    pub fn diff(&self, other: &AudioSpecificConfig) -> Vec<FieldDiff> {
+        let mut d = Differ(Vec::new());
+        d.value(
+            "audio_object_type",
+            self.audio_object_type,
+            other.audio_object_type,
+        );
+        d.frequency(
+            "sampling_frequency",
+            Some(self.sampling_frequency),
+            Some(other.sampling_frequency),
+        );
+        d.value(
+            "channel_configuration",
+            self.channel_configuration,
+            other.channel_configuration,
+        );
+        let (ext, other_ext) = (self.extension.as_ref(), other.extension.as_ref());
+        let before = d.0.len();
+        d.field(
+            "sbr_present",
+            ext.is_some_and(|e| e.sbr_present()),
+            other_ext.is_some_and(|e| e.sbr_present()),
+        );
+        d.field(
+            "ps_present",
+            ext.is_some_and(|e| e.ps_present()),
+            other_ext.is_some_and(|e| e.ps_present()),
+        );
+        d.frequency(
+            "extension_sampling_frequency",
+            ext.and_then(|e| e.sampling_frequency()),
+            other_ext.and_then(|e| e.sampling_frequency()),
+        );
+        if d.0.len() == before {
+            d.optional("extension", ext, other_ext);
+        }
+        match (&self.specific_config, &other.specific_config) {
+            (SpecificConfig::Ga(ga), SpecificConfig::Ga(other_ga)) => {
+                d.field(
+                    "frame_length_flag",
+                    ga.frame_length_flag,
+                    other_ga.frame_length_flag,
+                );
+                d.optional(
+                    "core_coder_delay",
+                    ga.core_coder_delay,
+                    other_ga.core_coder_delay,
+                );
+                d.optional(
+                    "program_config_element",
+                    ga.program_config_element.as_ref(),
+                    other_ga.program_config_element.as_ref(),
+                );
+                d.optional("layer_nr", ga.layer_nr, other_ga.layer_nr);
+                d.optional(
+                    "ga_extension",
+                    ga.extension.as_ref(),
+                    other_ga.extension.as_ref(),
+                );
+            }
+            (specific, other_specific) => {
+                d.field("specific_config", specific, other_specific);
+            }
+        }
+        d.optional("ep_config", self.ep_config, other.ep_config);
+        d.optional(
+            "error_protection_specific_config",
+            self.error_protection_specific_config.as_ref(),
+            other.error_protection_specific_config.as_ref(),
+        );
+        d.0
+    }
+
+    /// A multi-line, human-readable description of this configuration, similar to that given
+    /// by media inspection tools, for diagnostic output.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+    /// # #[cfg(feature = "names")]
+    /// assert_eq!(
+    ///     "\
+    /// AudioSpecificConfig
+    ///   Object type: 2 (AAC LC)
+    ///   Sampling frequency: 4 (44100 Hz)
+    ///   Channel configuration: 2 (stereo)
+    ///   GASpecificConfig
+    ///     Frame length: 1024 samples
+    ///     Depends on core coder: no
+    /// ",
+    ///     asc.describe().to_string()
+    /// );
+    /// ```
+    pub fn describe(&self) -> impl fmt::Display + '_ {
+        Description(self)
+    }
+
+    pub(crate) fn signalled_object_type(&self) -> AudioObjectType {
+        match self.extension {
+            Some(ref ext) if ext.ps_present() => AudioObjectType::PS,
+            Some(ref ext) if ext.sbr_present() => AudioObjectType::SBR,
+            _ => self.audio_object_type,
+        }
+    }
+}
+
+/// The bits following an `AudioSpecificConfig` in its buffer
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TrailingBits {
+    /// The number of bits following the config
+    pub bit_length: usize,
+    /// Whether these bits are only the zero bits padding the config to a byte boundary (which
+    /// includes there being no bits at all)
+    pub is_padding: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl TrailingBits {
+    /// Consumes the remaining bits of the reader, reporting them as a violation of the spec
+    /// unless they are padding.
+    fn read(r: &mut BitReader<'_>) -> Result<TrailingBits, EndOfData> {
+        let bit_length = r.remaining();
+        let is_padding = if bit_length <= 32 {
+            let bits = r.named("trailing_bits").read(bit_length as u32)?;
+            bit_length < 8 && bits == 0
+        } else {
+            r.named("trailing_bits").skip(bit_length)?;
+            false
+        };
+        if !is_padding {
+            r.violation(Violation::TrailingData);
+        }
+        Ok(TrailingBits {
+            bit_length,
+            is_padding,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromBitstream for AudioSpecificConfig {
+    type Context = ();
+    type Error = AscError;
+
+    fn from_bitstream_with_length(data: &[u8], _: ()) -> Result<(Self, usize), AscError> {
+        read_counted(data, Self::read)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToBitstream for AudioSpecificConfig {
+    fn to_bitstream(&self) -> RawBits {
+        write_bits(|w| self.write(w))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Describe for AudioSpecificConfig {
+    fn describe_to(&self, d: &mut Describer<'_, '_>) -> fmt::Result {
+        d.section("AudioSpecificConfig", |d| {
+            d.field("Object type", Value(self.audio_object_type))?;
+            d.field("Sampling frequency", self.sampling_frequency.describe())?;
+            d.field("Channel configuration", Value(self.channel_configuration))?;
+            if let Some(ref ext) = self.extension {
+                let signalling = match *ext {
+                    AudioExtension::Hierarchical { .. } => "hierarchical",
+                    _ => "backward-compatible",
+                };
+                d.section(format_args!("Extension ({} signalling)", signalling), |d| {
+                    if let AudioExtension::BackwardCompatibleOther(aot) = *ext {
+                        return d.field("Object type", Value(aot));
+                    }
+                    d.field("SBR", yes_no(ext.sbr_present()))?;
+                    d.field("PS", yes_no(ext.ps_present()))?;
+                    if let Some(f) = ext.sampling_frequency() {
+                        d.field("Sampling frequency", f.describe())?;
+                    }
+                    Ok(())
+                })?;
+            }
+            let (long, short) = if self.audio_object_type == AudioObjectType::ER_AAC_LD
+                || self.audio_object_type == AudioObjectType::ER_AAC_ELD
+            {
+                (512, 480)
+            } else {
+                (1024, 960)
+            };
+            let frame_length = |flag| format!("{} samples", if flag { short } else { long });
+            match self.specific_config {
+                SpecificConfig::Ga(ref ga) => d.section("GASpecificConfig", |d| {
+                    d.field("Frame length", frame_length(ga.frame_length_flag))?;
+                    match ga.core_coder_delay {
+                        Some(delay) => d.field("Core coder delay", delay)?,
+                        None => d.field("Depends on core coder", "no")?,
+                    }
+                    if let Some(ref pce) = ga.program_config_element {
+                        d.field(
+                            "Program config element",
+                            format_args!("{} channels", pce.channel_count()),
+                        )?;
+                    }
+                    if let Some(layer_nr) = ga.layer_nr {
+                        d.field("Layer", layer_nr)?;
+                    }
+                    if let Some(ref ext) = ga.extension {
+                        if let Some(ref bsac) = ext.bsac {
+                            d.field("BSAC sub-frames", bsac.num_of_sub_frame)?;
+                            d.field("BSAC layer length", bsac.layer_length)?;
+                        }
+                        if let Some(ref resilience) = ext.resilience {
+                            resilience.describe_to(d)?;
+                        }
+                    }
+                    Ok(())
+                })?,
+                SpecificConfig::Eld(ref eld) => d.section("ELDSpecificConfig", |d| {
+                    d.field("Frame length", frame_length(eld.frame_length_flag))?;
+                    eld.resilience.describe_to(d)?;
+                    match eld.ld_sbr {
+                        Some(ref ld_sbr) => d.field(
+                            "LD SBR",
+                            format_args!("yes ({} headers)", ld_sbr.headers.len()),
+                        )?,
+                        None => d.field("LD SBR", "no")?,
+                    }
+                    if !eld.extensions.is_empty() {
+                        d.field("Extensions", eld.extensions.len())?;
+                    }
+                    Ok(())
+                })?,
+                SpecificConfig::Unparsed(ref bits) => d.field(
+                    "Specific config",
+                    format_args!("{} bits, not interpreted", bits.bit_length),
+                )?,
+            }
+            if let Some(ep_config) = self.ep_config {
+                d.field("Error protection config", ep_config)?;
+            }
+            if let Some(ref bits) = self.error_protection_specific_config {
+                d.field(
+                    "ErrorProtectionSpecificConfig",
+                    format_args!("{} bits, not interpreted", bits.bit_length),
+                )?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Describe for ResilienceFlags {
+    fn describe_to(&self, d: &mut Describer<'_, '_>) -> fmt::Result {
+        d.field(
+            "Section data resilience",
+            yes_no(self.aac_section_data_resilience),
+        )?;
+        d.field(
+            "Scalefactor data resilience",
+            yes_no(self.aac_scalefactor_data_resilience),
+        )?;
+        d.field(
+            "Spectral data resilience",
+            yes_no(self.aac_spectral_data_resilience),
+        )
+    }
+}
+
+/// The worst-case serialized length of a
+/// [`FixedAudioSpecificConfig`](struct.FixedAudioSpecificConfig.html), in bytes
+pub const FIXED_ASC_MAX_LENGTH: usize = 17;
+
+/// Errors that may be produced while parsing a `FixedAudioSpecificConfig`
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum FixedAscError {
+    /// The data ended before the end of the structure, while reading the given element.
+    NotEnoughData(TraceEntry),
+    /// The configuration holds variable-length data that a `FixedAudioSpecificConfig` can't
+    /// represent: an object type other than those using the `GASpecificConfig`, a program
+    /// config element (i.e. `channelConfiguration` `0`), or an `ErrorProtectionSpecificConfig`.
+    Unsupported,
+}
+
+impl fmt::Display for FixedAscError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixedAscError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            FixedAscError::Unsupported => {
+                f.write_str("configuration can't be held by a FixedAudioSpecificConfig")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FixedAscError {}
+
+impl From<EndOfData> for FixedAscError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        FixedAscError::NotEnoughData(at)
+    }
+}
+
+/// An `AudioSpecificConfig` for the AAC family of object types, held without any heap
+/// allocation, for use where the `alloc` feature is unavailable.
+///
+/// The fields of the `GASpecificConfig` are held directly.  Configurations which can't be
+/// represented (see [`FixedAscError::Unsupported`](enum.FixedAscError.html)) are rejected
+/// rather than partially parsed.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::asc::*;
+/// let buf = [0xeb, 0x09, 0x88, 0x00];
+/// let asc = FixedAudioSpecificConfig::from_bytes(&buf).unwrap();
+/// assert_eq!(AudioObjectType::AAC_LC, asc.audio_object_type);
+/// assert!(asc.extension.as_ref().unwrap().ps_present());
+/// let mut out = [0; FIXED_ASC_MAX_LENGTH];
+/// let len = asc.write_to(&mut out).unwrap();
+/// assert_eq!(&buf[..], &out[..len]);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FixedAudioSpecificConfig {
+    pub audio_object_type: AudioObjectType,
+    pub sampling_frequency: SamplingFrequency,
+    pub channel_configuration: ChannelConfiguration,
+    pub extension: Option<AudioExtension>,
+    /// As for [`GaSpecificConfig`](struct.GaSpecificConfig.html)
+    pub frame_length_flag: bool,
+    /// As for [`GaSpecificConfig`](struct.GaSpecificConfig.html)
+    pub core_coder_delay: Option<u16>,
+    /// As for [`GaSpecificConfig`](struct.GaSpecificConfig.html)
+    pub layer_nr: Option<u8>,
+    /// As for [`GaSpecificConfig`](struct.GaSpecificConfig.html)
+    pub ga_extension: Option<GaExtension>,
+    /// The `epConfig` value, present for error resilient object types (and never `2` or `3`)
+    pub ep_config: Option<u8>,
+}
+
+impl FixedAudioSpecificConfig {
+    /// Parses an `AudioSpecificConfig` from the given buffer, without allocating.
+    pub fn from_bytes(buf: &[u8]) -> Result<FixedAudioSpecificConfig, FixedAscError> {
+        let mut r = BitReader::new(buf);
+        let (audio_object_type, sampling_frequency, channel_configuration, mut extension) =
+            read_header(&mut r)?;
+        if !is_ga(audio_object_type)
+            || channel_configuration == ChannelConfiguration::AOT_SPECIFIC_CONFIG
+        {
+            return Err(FixedAscError::Unsupported);
+        }
+        let (frame_length_flag, core_coder_delay, extension_flag) = read_ga_flags(&mut r)?;
+        let layer_nr = read_layer_nr(&mut r, audio_object_type)?;
+        let ga_extension = if extension_flag {
+            Some(GaExtension::read(&mut r, audio_object_type)?)
+        } else {
+            None
+        };
+        let ep_config = if has_ep_config(audio_object_type) {
+            let ep = r.named("epConfig").read_u8(2)?;
+            if ep == 2 || ep == 3 {
+                return Err(FixedAscError::Unsupported);
+            }
+            Some(ep)
+        } else {
+            None
+        };
+        if extension.is_none() && r.remaining() >= 16 {
+            extension = read_sync_extension(&mut r)?;
+        }
+        Ok(FixedAudioSpecificConfig {
+            audio_object_type,
+            sampling_frequency,
+            channel_configuration,
+            extension,
+            frame_length_flag,
+            core_coder_delay,
+            layer_nr,
+            ga_extension,
+            ep_config,
+        })
+    }
+
+    /// Serializes this configuration into the given buffer, returning the number of bytes
+    /// written, or `None` if the buffer is too small (a buffer of
+    /// [`FIXED_ASC_MAX_LENGTH`](constant.FIXED_ASC_MAX_LENGTH.html) bytes is always large
+    /// enough).
+    ///
+    /// As for [`AudioSpecificConfig::to_bytes()`](struct.AudioSpecificConfig.html#method.to_bytes),
+    /// fields are written as given.
+    pub fn write_to(&self, buf: &mut [u8]) -> Option<usize> {
+        let mut w = SliceWriter::new(buf);
+        write_header(
+            &mut w,
+            self.audio_object_type,
+            self.sampling_frequency,
+            self.channel_configuration,
+            self.extension.as_ref(),
+        );
+        w.write_bool(self.frame_length_flag);
+        w.write_bool(self.core_coder_delay.is_some());
+        if let Some(delay) = self.core_coder_delay {
+            w.write(14, u32::from(delay));
+        }
+        w.write_bool(self.ga_extension.is_some());
+        if let Some(layer_nr) = self.layer_nr {
+            w.write(3, u32::from(layer_nr));
+        }
+        if let Some(ref extension) = self.ga_extension {
+            extension.write(&mut w);
+        }
+        if let Some(ep_config) = self.ep_config {
+            w.write(2, u32::from(ep_config));
+        }
+        if let Some(ref extension) = self.extension {
+            write_sync_extension(&mut w, extension);
+        }
+        w.finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<FixedAudioSpecificConfig> for AudioSpecificConfig {
+    fn from(asc: FixedAudioSpecificConfig) -> Self {
+        AudioSpecificConfig {
+            audio_object_type: asc.audio_object_type,
+            sampling_frequency: asc.sampling_frequency,
+            channel_configuration: asc.channel_configuration,
+            extension: asc.extension,
+            specific_config: SpecificConfig::Ga(GaSpecificConfig {
+                frame_length_flag: asc.frame_length_flag,
+                core_coder_delay: asc.core_coder_delay,
+                program_config_element: None,
+                layer_nr: asc.layer_nr,
+                extension: asc.ga_extension,
+            }),
+            ep_config: asc.ep_config,
+            error_protection_specific_config: None,
+        }
+    }
+}
+
+/// A view of an `AudioSpecificConfig` borrowing the data holding it, decoding the fields of its
+/// header only when asked for, for uses needing just these (e.g. the object type and sample
+/// rate) without building the whole structure.
+///
+/// Creating the view checks that the data holds the complete header (the object type, sampling
+/// frequency and channel configuration, along with any explicitly signalled SBR or PS
+/// extension) and notes where each field lies; the rest of the config is not examined, so a
+/// backward-compatible extension is not detected.  No allocation is needed.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::asc::*;
+/// // HE-AAC, signalling SBR explicitly
+/// let view = AscView::new(&[0x2b, 0x11, 0x88, 0x00]).unwrap();
+/// assert_eq!(AudioObjectType::AAC_LC, view.audio_object_type());
+/// assert_eq!(Some(24000), view.sampling_frequency().frequency());
+/// assert_eq!(
+///     Some(48000),
+///     view.extension_sampling_frequency().and_then(|f| f.frequency())
+/// );
+/// ```
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct AscView<'a> {
+    data: &'a [u8],
+    /// The positions of the `audioObjectType` giving the underlying object type (after any
+    /// explicit extension signalling), the core sampling frequency, the `channelConfiguration`
+    /// and any extension sampling frequency
+    audio_object_type_at: usize,
+    sampling_frequency_at: usize,
+    channel_configuration_at: usize,
+    extension_sampling_frequency_at: Option<usize>,
+}
+
+impl<'a> AscView<'a> {
+    /// Checks that the given data starts with a complete `AudioSpecificConfig` header,
+    /// giving a view of it.
+    pub fn new(data: &'a [u8]) -> Result<AscView<'a>, AscError> {
+        let mut r = BitReader::new(data);
+        let audio_object_type = read_audio_object_type(&mut r)?;
+        let sampling_frequency_at = r.position();
+        SamplingFrequency::read(&mut r, false)?;
+        let channel_configuration_at = r.position();
+        read_channel_configuration(&mut r, "channelConfiguration")?;
+        let mut view = AscView {
+            data,
+            audio_object_type_at: 0,
+            sampling_frequency_at,
+            channel_configuration_at,
+            extension_sampling_frequency_at: None,
+        };
+        if audio_object_type == AudioObjectType::SBR || audio_object_type == AudioObjectType::PS {
+            view.extension_sampling_frequency_at = Some(r.position());
+            SamplingFrequency::read(&mut r, true)?;
+            view.audio_object_type_at = r.position();
+            read_audio_object_type(&mut r)?;
+        }
+        Ok(view)
+    }
+
+    /// The data that the view was created from, including any data following the config
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// The underlying object type, as for `AudioSpecificConfig::audio_object_type` (so `SBR`
+    /// or `PS` signalled explicitly is instead reported by
+    /// [`extension_sampling_frequency()`](#method.extension_sampling_frequency))
+    pub fn audio_object_type(&self) -> AudioObjectType {
+        read_audio_object_type(&mut self.reader_at(self.audio_object_type_at)).unwrap()
+    }
+
+    pub fn sampling_frequency(&self) -> SamplingFrequency {
+        SamplingFrequency::read(&mut self.reader_at(self.sampling_frequency_at), false).unwrap()
+    }
+
+    pub fn channel_configuration(&self) -> ChannelConfiguration {
+        read_channel_configuration(
+            &mut self.reader_at(self.channel_configuration_at),
+            "channelConfiguration",
+        )
+        .unwrap()
+    }
+
+    /// The sampling frequency of an SBR or PS extension, if signalled explicitly
+    pub fn extension_sampling_frequency(&self) -> Option<SamplingFrequency> {
+        self.extension_sampling_frequency_at
+            .map(|at| SamplingFrequency::read(&mut self.reader_at(at), true).unwrap())
+    }
+
+    /// Parses the whole config.
+    #[cfg(feature = "alloc")]
+    pub fn to_config(&self) -> Result<AudioSpecificConfig, AscError> {
+        AudioSpecificConfig::from_bytes(self.data)
+    }
+
+    /// A reader positioned at the given field, which `new()` has checked can be read in full.
+    fn reader_at(&self, at: usize) -> BitReader<'a> {
+        let mut r = BitReader::new(self.data);
+        r.skip(at).unwrap();
+        r
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&AudioSpecificConfig> for FixedAudioSpecificConfig {
+    type Error = FixedAscError;
+
+    /// Fails with `FixedAscError::Unsupported` if the configuration has a program config
+    /// element, an `ErrorProtectionSpecificConfig`, or a specific config other than a
+    /// `GASpecificConfig`.
+    fn try_from(asc: &AudioSpecificConfig) -> Result<Self, Self::Error> {
+        match asc.specific_config {
+            SpecificConfig::Ga(ref ga)
+                if ga.program_config_element.is_none()
+                    && asc.error_protection_specific_config.is_none() =>
+            {
+                Ok(FixedAudioSpecificConfig {
+                    audio_object_type: asc.audio_object_type,
+                    sampling_frequency: asc.sampling_frequency,
+                    channel_configuration: asc.channel_configuration,
+                    extension: asc.extension.clone(),
+                    frame_length_flag: ga.frame_length_flag,
+                    core_coder_delay: ga.core_coder_delay,
+                    layer_nr: ga.layer_nr,
+                    ga_extension: ga.extension.clone(),
+                    ep_config: asc.ep_config,
+                })
+            }
+            _ => Err(FixedAscError::Unsupported),
+        }
+    }
+}
+
+/// Errors that may be produced while patching a field of an encoded `AudioSpecificConfig`
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum PatchError {
+    /// The data ended before the end of the field being patched, while reading the given
+    /// element.
+    NotEnoughData(TraceEntry),
+    /// The new value can't be encoded using the same number of bits as the existing value
+    /// (e.g. a frequency with no index, where the existing frequency was given by index), so
+    /// the rest of the data can't be preserved.
+    LengthChange,
+    /// The new value would change the syntax of the data following the field (i.e. a change to
+    /// or from a `channelConfiguration` of `0`, which determines whether a program config
+    /// element is present).
+    SyntaxChange,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            PatchError::LengthChange => {
+                f.write_str("new value can't be encoded in the same number of bits")
+            }
+            PatchError::SyntaxChange => {
+                f.write_str("new value would change the syntax of the following data")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PatchError {}
+
+impl From<EndOfData> for PatchError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        PatchError::NotEnoughData(at)
+    }
+}
+
+/// Changes the (core) sampling frequency of the encoded `AudioSpecificConfig` in the given
+/// buffer, rewriting only the bits of the `samplingFrequencyIndex` field (and any explicit
+/// `samplingFrequency` following it), so that all other data is preserved exactly, even if it
+/// is not understood by this crate.
+///
+/// The existing form of the field is kept: if the frequency is given by index, the new
+/// frequency must be exactly that of an index (see
+/// [`SamplingFrequencyIndex::exact()`](../struct.SamplingFrequencyIndex.html#method.exact)),
+/// and if given explicitly, the new frequency must fit in 24 bits.  Otherwise
+/// `PatchError::LengthChange` is returned and the buffer is unchanged.
+///
+/// ```rust
+/// # use mpeg4_audio_const::asc::*;
+/// // AAC LC, 44.1kHz, stereo
+/// let mut buf = [0x12, 0x10];
+/// set_sample_rate(&mut buf, 48000).unwrap();
+/// assert_eq!([0x11, 0x90], buf);
+/// assert_eq!(Err(PatchError::LengthChange), set_sample_rate(&mut buf, 44000));
+/// ```
+pub fn set_sample_rate(buf: &mut [u8], hz: u32) -> Result<(), PatchError> {
+    let mut r = BitReader::new(buf);
+    read_audio_object_type(&mut r)?;
+    let pos = r.position();
+    let sampling_frequency = match SamplingFrequency::read(&mut r, false)? {
+        SamplingFrequency::Index(_) => SamplingFrequency::Index(
+            SamplingFrequencyIndex::exact(hz).ok_or(PatchError::LengthChange)?,
+        ),
+        SamplingFrequency::Explicit(_) if hz < 1 << 24 => SamplingFrequency::Explicit(hz),
+        SamplingFrequency::Explicit(_) => return Err(PatchError::LengthChange),
+    };
+    sampling_frequency.write(&mut PatchWriter::new(buf, pos));
+    Ok(())
+}
+
+/// Changes the `channelConfiguration` of the encoded `AudioSpecificConfig` in the given buffer,
+/// rewriting only the bits of that field, so that all other data is preserved exactly, even if
+/// it is not understood by this crate.
+///
+/// Since a `channelConfiguration` of `0` is followed by a program config element for some
+/// object types, changing to or from `0` is refused with `PatchError::SyntaxChange`, as is a
+/// change for `ER_AAC_ELD` altering the number of SBR headers that the `ELDSpecificConfig`
+/// carries.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::asc::*;
+/// // AAC LC, 44.1kHz, stereo
+/// let mut buf = [0x12, 0x10];
+/// set_channel_configuration(&mut buf, ChannelConfiguration::MONO).unwrap();
+/// assert_eq!([0x12, 0x08], buf);
+/// ```
+pub fn set_channel_configuration(
+    buf: &mut [u8],
+    channel_configuration: ChannelConfiguration,
+) -> Result<(), PatchError> {
+    let mut r = BitReader::new(buf);
+    let audio_object_type = read_audio_object_type(&mut r)?;
+    SamplingFrequency::read(&mut r, false)?;
+    let pos = r.position();
+    // a 4-bit value can't be rejected
+    let existing =
+        ChannelConfiguration::try_from(r.named("channelConfiguration").read_u8(4)?).unwrap();
+    let zero = ChannelConfiguration::AOT_SPECIFIC_CONFIG;
+    if (existing == zero) != (channel_configuration == zero)
+        || (audio_object_type == AudioObjectType::ER_AAC_ELD
+            && eld_sbr_header_count(existing) != eld_sbr_header_count(channel_configuration))
+    {
+        return Err(PatchError::SyntaxChange);
+    }
+    PatchWriter::new(buf, pos).write(4, u32::from(u8::from(channel_configuration)));
+    Ok(())
+}
+
+/// Reads the fields common to all object types, up to the start of the specific config (with
+/// any hierarchically signalled extension giving the underlying object type).
+fn read_header(
+    r: &mut BitReader<'_>,
+) -> Result<
+    (
+        AudioObjectType,
+        SamplingFrequency,
+        ChannelConfiguration,
+        Option<AudioExtension>,
+    ),
+    EndOfData,
+> {
+    let mut audio_object_type = read_audio_object_type(r)?;
+    let sampling_frequency = SamplingFrequency::read(r, false)?;
+    let channel_configuration = read_channel_configuration(r, "channelConfiguration")?;
+    let mut extension = None;
+    if audio_object_type == AudioObjectType::SBR || audio_object_type == AudioObjectType::PS {
+        let ps_present = audio_object_type == AudioObjectType::PS;
+        let ext_sampling_frequency = SamplingFrequency::read(r, true)?;
+        audio_object_type = read_audio_object_type(r)?;
+        let ext_channel_configuration = if audio_object_type == AudioObjectType::ER_BSAC {
+            Some(read_channel_configuration(
+                r,
+                "extensionChannelConfiguration",
+            )?)
+        } else {
+            None
+        };
+        extension = Some(AudioExtension::Hierarchical {
+            ps_present,
+            sampling_frequency: ext_sampling_frequency,
+            channel_configuration: ext_channel_configuration,
+        });
+    }
+    Ok((
+        audio_object_type,
+        sampling_frequency,
+        channel_configuration,
+        extension,
+    ))
+}
+
+fn write_header<W: BitSink>(
+    w: &mut W,
+    audio_object_type: AudioObjectType,
+    sampling_frequency: SamplingFrequency,
+    channel_configuration: ChannelConfiguration,
+    extension: Option<&AudioExtension>,
+) {
+    match extension {
+        Some(&AudioExtension::Hierarchical {
+            ps_present,
+            sampling_frequency: ext_sampling_frequency,
+            channel_configuration: ext_channel_configuration,
+        }) => {
+            write_audio_object_type(
+                w,
+                if ps_present {
+                    AudioObjectType::PS
+                } else {
+                    AudioObjectType::SBR
+                },
+            );
+            sampling_frequency.write(w);
+            w.write(4, u32::from(u8::from(channel_configuration)));
+            ext_sampling_frequency.write(w);
+            write_audio_object_type(w, audio_object_type);
+            if let Some(ext_channel_configuration) = ext_channel_configuration {
+                w.write(4, u32::from(u8::from(ext_channel_configuration)));
+            }
+        }
+        _ => {
+            write_audio_object_type(w, audio_object_type);
+            sampling_frequency.write(w);
+            w.write(4, u32::from(u8::from(channel_configuration)));
+        }
+    }
+}
+
+/// The number of SBR headers in the `ld_sbr_header()` of an `ELDSpecificConfig`, which depends
+/// on the channel configuration.
+fn eld_sbr_header_count(channel_configuration: ChannelConfiguration) -> usize {
+    match u8::from(channel_configuration) {
+        1 | 2 => 1,
+        3 => 2,
+        4..=6 => 3,
+        7 => 4,
+        _ => 0,
+    }
+}
+
+/// `true` for the object types using the `GASpecificConfig`
+pub(crate) fn is_ga(audio_object_type: AudioObjectType) -> bool {
+    matches!(u8::from(audio_object_type), 1..=4 | 6 | 7 | 17 | 19..=23)
+}
+
+/// `true` for the error resilient object types, whose specific config is followed by the
+/// `epConfig` field.
+pub(crate) fn has_ep_config(audio_object_type: AudioObjectType) -> bool {
+    matches!(u8::from(audio_object_type), 17 | 19..=27 | 39)
+}
+
+fn read_audio_object_type(r: &mut BitReader<'_>) -> Result<AudioObjectType, EndOfData> {
+    let mut aot = r.named("audioObjectType").read_u8(AOT_FIELD_BITS)?;
+    if aot == AOT_ESCAPE_VALUE {
+        aot = 32 + r.named("audioObjectTypeExt").read_u8(AOT_EXT_FIELD_BITS)?;
+    }
+    // the largest value that can be encoded is 95, and the escape value is never produced
+    let aot = AudioObjectType::try_from(aot).unwrap();
+    if aot.is_reserved() {
+        r.violation(Violation::ReservedValue);
+    }
+    Ok(aot)
+}
+
+fn read_channel_configuration(
+    r: &mut BitReader<'_>,
+    name: &'static str,
+) -> Result<ChannelConfiguration, EndOfData> {
+    // a 4-bit value can't be rejected
+    let channel_configuration = ChannelConfiguration::try_from(r.named(name).read_u8(4)?).unwrap();
+    if channel_configuration != ChannelConfiguration::AOT_SPECIFIC_CONFIG
+        && channel_configuration.channel_count().is_none()
+    {
+        r.violation(Violation::ReservedValue);
+    }
+    Ok(channel_configuration)
+}
+
+fn write_audio_object_type<W: BitSink>(w: &mut W, audio_object_type: AudioObjectType) {
+    let aot = u8::from(audio_object_type);
+    if audio_object_type.is_escape_coded() {
+        w.write(AOT_FIELD_BITS, u32::from(AOT_ESCAPE_VALUE));
+        w.write(AOT_EXT_FIELD_BITS, u32::from(aot - 32));
+    } else {
+        w.write(AOT_FIELD_BITS, u32::from(aot));
+    }
+}
+
+fn write_sync_extension<W: BitSink>(w: &mut W, extension: &AudioExtension) {
+    match *extension {
+        AudioExtension::Hierarchical { .. } => (),
+        AudioExtension::BackwardCompatibleSbr {
+            sbr_present,
+            sampling_frequency,
+            ps_present,
+        } => {
+            w.write(11, u32::from(SYNC_EXTENSION_TYPE_AOT));
+            write_audio_object_type(w, AudioObjectType::SBR);
+            w.write_bool(sbr_present);
+            if let Some(sampling_frequency) = sampling_frequency {
+                sampling_frequency.write(w);
+            }
+            if let Some(ps_present) = ps_present {
+                w.write(11, u32::from(SYNC_EXTENSION_TYPE_PS));
+                w.write_bool(ps_present);
+            }
+        }
+        AudioExtension::BackwardCompatibleBsac {
+            sbr_present,
+            sampling_frequency,
+            channel_configuration,
+        } => {
+            w.write(11, u32::from(SYNC_EXTENSION_TYPE_AOT));
+            write_audio_object_type(w, AudioObjectType::ER_BSAC);
+            w.write_bool(sbr_present);
+            if let Some(sampling_frequency) = sampling_frequency {
+                sampling_frequency.write(w);
+            }
+            w.write(4, u32::from(u8::from(channel_configuration)));
+        }
+        AudioExtension::BackwardCompatibleOther(audio_object_type) => {
+            w.write(11, u32::from(SYNC_EXTENSION_TYPE_AOT));
+            write_audio_object_type(w, audio_object_type);
+        }
+    }
+}
+
+/// Reads the fields of a `GASpecificConfig()` preceding any `program_config_element()`: the
+/// `frameLengthFlag`, the `coreCoderDelay` (if `dependsOnCoreCoder` is set) and the
+/// `extensionFlag`.
+fn read_ga_flags(r: &mut BitReader<'_>) -> Result<(bool, Option<u16>, bool), EndOfData> {
+    let frame_length_flag = r.named("frameLengthFlag").read_bool()?;
+    let core_coder_delay = if r.named("dependsOnCoreCoder").read_bool()? {
+        Some(r.named("coreCoderDelay").read_u16(14)?)
+    } else {
+        None
+    };
+    let extension_flag = r.named("extensionFlag").read_bool()?;
+    Ok((frame_length_flag, core_coder_delay, extension_flag))
+}
+
+/// Reads the `layerNr` field of a `GASpecificConfig()`, present for the scalable object types.
+fn read_layer_nr(
+    r: &mut BitReader<'_>,
+    audio_object_type: AudioObjectType,
+) -> Result<Option<u8>, EndOfData> {
+    match audio_object_type {
+        AudioObjectType::AAC_SCALABLE | AudioObjectType::ER_AAC_SCALABLE => {
+            Ok(Some(r.named("layerNr").read_u8(3)?))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn read_sync_extension(r: &mut BitReader<'_>) -> Result<Option<AudioExtension>, EndOfData> {
+    // leave the data unconsumed if it does not hold an extension, since the
+    // AudioSpecificConfig may be followed by other fields (e.g. within a StreamMuxConfig)
+    if r.peek(11)? != u32::from(SYNC_EXTENSION_TYPE_AOT) {
+        return Ok(None);
+    }
+    r.named("syncExtensionType").skip(11)?;
+    let extension_audio_object_type = read_audio_object_type(r)?;
+    let extension = match extension_audio_object_type {
+        AudioObjectType::SBR => {
+            let sbr_present = r.named("sbrPresentFlag").read_bool()?;
+            let mut sampling_frequency = None;
+            let mut ps_present = None;
+            if sbr_present {
+                sampling_frequency = Some(SamplingFrequency::read(r, true)?);
+                if r.remaining() >= 12 && r.peek(11)? == u32::from(SYNC_EXTENSION_TYPE_PS) {
+                    r.named("syncExtensionType").skip(11)?;
+                    ps_present = Some(r.named("psPresentFlag").read_bool()?);
+                }
+            }
+            AudioExtension::BackwardCompatibleSbr {
+                sbr_present,
+                sampling_frequency,
+                ps_present,
+            }
+        }
+        AudioObjectType::ER_BSAC => {
+            let sbr_present = r.named("sbrPresentFlag").read_bool()?;
+            let sampling_frequency = if sbr_present {
+                Some(SamplingFrequency::read(r, true)?)
+            } else {
+                None
+            };
+            AudioExtension::BackwardCompatibleBsac {
+                sbr_present,
+                sampling_frequency,
+                channel_configuration: read_channel_configuration(
+                    r,
+                    "extensionChannelConfiguration",
+                )?,
+            }
+        }
+        other => AudioExtension::BackwardCompatibleOther(other),
+    };
+    Ok(Some(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn backward_compatible_sbr_ps() {
+        // AAC LC, 24kHz, stereo, then sync extension: SBR present at 48kHz, PS present
+        let asc =
+            AudioSpecificConfig::from_bytes(&[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80]).unwrap();
+        assert_eq!(AudioObjectType::AAC_LC, asc.audio_object_type);
+        assert_eq!(
+            Some(AudioExtension::BackwardCompatibleSbr {
+                sbr_present: true,
+                sampling_frequency: Some(SamplingFrequency::Index(
+                    SamplingFrequencyIndex::FREQ_48000
+                )),
+                ps_present: Some(true),
+            }),
+            asc.extension
+        );
+        assert_eq!("mp4a.40.29", asc.codec_string());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn explicit_frequency_and_escaped_aot() {
+        // AOT 36 (ALS), explicit 44100Hz, mono, followed by unparsed ALS config
+        let asc =
+            AudioSpecificConfig::from_bytes(&[0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68])
+                .unwrap();
+        assert_eq!(AudioObjectType::ALS, asc.audio_object_type);
+        assert_eq!(SamplingFrequency::Explicit(44100), asc.sampling_frequency);
+        assert_eq!(Some(44100), asc.sample_rate());
+        assert_eq!(ChannelConfiguration::MONO, asc.channel_configuration);
+        assert_eq!(
+            SpecificConfig::Unparsed(RawBits {
+                data: vec![0x01, 0x23, 0x40],
+                bit_length: 21
+            }),
+            asc.specific_config
+        );
+        assert_eq!("mp4a.40.36", asc.codec_string());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn eld_with_ld_sbr() {
+        // ER AAC ELD, 48kHz, mono, ld_sbr with one header, no extensions, epConfig 0
+        let asc = AudioSpecificConfig::from_bytes(&[0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x00]).unwrap();
+        assert_eq!(AudioObjectType::ER_AAC_ELD, asc.audio_object_type);
+        match asc.specific_config {
+            SpecificConfig::Eld(ref eld) => {
+                let ld_sbr = eld.ld_sbr.as_ref().unwrap();
+                assert_eq!(1, ld_sbr.headers.len());
+                assert_eq!(5, ld_sbr.headers[0].start_freq);
+            }
+            ref other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(Some(0), asc.ep_config);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn roundtrip() {
+        let configs: &[&[u8]] = &[
+            &[0x12, 0x10],
+            &[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80],
+            &[0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68],
+            &[0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x00],
+            &[0x11, 0x80, 0x04, 0xc4, 0x00, 0x00, 0x20, 0x00],
+            &[0xf9, 0x46, 0x40, 0x00],
+        ];
+        for buf in configs {
+            let asc = AudioSpecificConfig::from_bytes(buf).unwrap();
+            assert_eq!(buf.to_vec(), asc.to_bytes(), "{:?}", asc);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn profile_level() {
+        // AAC LC, 48kHz, 5.1
+        let asc = AudioSpecificConfig::from_bytes(&[0x11, 0xb0]).unwrap();
+        assert_eq!(
+            AudioProfileLevelIndication::AAC_L4,
+            asc.audio_profile_level_indication()
+        );
+        // backward-compatible SBR + PS from a 24kHz stereo core
+        let asc =
+            AudioSpecificConfig::from_bytes(&[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80]).unwrap();
+        assert_eq!(
+            AudioProfileLevelIndication::HE_AAC_V2_L2,
+            asc.audio_profile_level_indication()
+        );
+        let als =
+            AudioSpecificConfig::from_bytes(&[0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68])
+                .unwrap();
+        assert_eq!(
+            AudioProfileLevelIndication::NOT_SPECIFIED,
+            als.audio_profile_level_indication()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn channel_layout() {
+        let asc = AudioSpecificConfig::from_bytes(&[0x11, 0xb0]).unwrap();
+        assert_eq!(
+            ChannelLayout::Standard(ChannelConfiguration::FIVE_ONE),
+            asc.channel_layout()
+        );
+        assert_eq!(Some(1), asc.channel_layout().lfe_channel_count());
+        assert_eq!(Some(6), asc.channel_count());
+        // channelConfiguration 0 with an uninterpreted specific config
+        let mut als =
+            AudioSpecificConfig::from_bytes(&[0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68])
+                .unwrap();
+        als.channel_configuration = ChannelConfiguration::AOT_SPECIFIC_CONFIG;
+        assert_eq!(ChannelLayout::ObjectTypeSpecific, als.channel_layout());
+        assert_eq!(None, als.channel_layout().channel_count());
+        assert_eq!(None, als.channel_count());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn patch() {
+        // ER AAC ELD, 48kHz, mono, ld_sbr with one header (the fields following the escaped
+        // object type)
+        let mut buf = [0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x00];
+        set_sample_rate(&mut buf, 24000).unwrap();
+        set_channel_configuration(&mut buf, ChannelConfiguration::STEREO).unwrap();
+        let asc = AudioSpecificConfig::from_bytes(&buf).unwrap();
+        assert_eq!(AudioObjectType::ER_AAC_ELD, asc.audio_object_type);
+        assert_eq!(Some(24000), asc.sample_rate());
+        assert_eq!(ChannelConfiguration::STEREO, asc.channel_configuration);
+        assert_eq!(
+            Err(PatchError::SyntaxChange),
+            set_channel_configuration(&mut buf, ChannelConfiguration::AOT_SPECIFIC_CONFIG)
+        );
+        assert_eq!(
+            Err(PatchError::SyntaxChange),
+            set_channel_configuration(&mut buf, ChannelConfiguration::FIVE_ONE)
+        );
+        // ALS, explicit 44100Hz
+        let mut buf = [0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68];
+        set_sample_rate(&mut buf, 44000).unwrap();
+        assert_eq!(
+            SamplingFrequency::Explicit(44000),
+            AudioSpecificConfig::from_bytes(&buf)
+                .unwrap()
+                .sampling_frequency
+        );
+        assert!(matches!(
+            set_sample_rate(&mut [0x12], 48000),
+            Err(PatchError::NotEnoughData(_))
+        ));
+        // an escaped object type, ending within the channelConfiguration
+        assert_eq!(
+            Err(PatchError::NotEnoughData(TraceEntry {
+                bit_offset: 15,
+                bit_length: 4,
+                field: "channelConfiguration",
+                value: None,
+            })),
+            set_channel_configuration(&mut [0xf9, 0x48], ChannelConfiguration::MONO)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn canonical() {
+        // ALS, explicit 44100Hz, which has an index
+        let als =
+            AudioSpecificConfig::from_bytes(&[0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68])
+                .unwrap();
+        let canonical = als.canonicalize();
+        assert_eq!(
+            SamplingFrequency::Index(SamplingFrequencyIndex::FREQ_44100),
+            canonical.sampling_frequency
+        );
+        assert_eq!(als.stable_hash(), canonical.stable_hash());
+        assert_eq!(canonical, canonical.canonicalize());
+        let mut other = als.clone();
+        other.sampling_frequency = SamplingFrequency::Explicit(44000);
+        assert_ne!(als.stable_hash(), other.stable_hash());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn diff() {
+        let lc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+        assert!(lc.diff(&lc).is_empty());
+        // backward-compatible SBR + PS from a 24kHz stereo core
+        let he =
+            AudioSpecificConfig::from_bytes(&[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80]).unwrap();
+        let fields: Vec<_> = lc.diff(&he).iter().map(|d| d.field).collect();
+        assert_eq!(
+            vec![
+                "sampling_frequency",
+                "sbr_present",
+                "ps_present",
+                "extension_sampling_frequency"
+            ],
+            fields
+        );
+        let als =
+            AudioSpecificConfig::from_bytes(&[0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68])
+                .unwrap();
+        let diff = als.diff(&als.canonicalize());
+        assert_eq!(
+            "sampling_frequency: 44100 Hz (explicit) → 44100 Hz",
+            diff[0].to_string()
+        );
+        #[cfg(feature = "names")]
+        {
+            // AAC Main, 48kHz, mono
+            let main = AudioSpecificConfig::from_bytes(&[0x09, 0x88]).unwrap();
+            let diff: Vec<_> = lc.diff(&main).iter().map(|d| d.to_string()).collect();
+            assert_eq!(
+                vec![
+                    "audio_object_type: AAC LC → AAC main",
+                    "sampling_frequency: 44100 Hz → 48000 Hz",
+                    "channel_configuration: stereo → mono",
+                ],
+                diff
+            );
+        }
+    }
+
+    #[cfg(all(feature = "alloc", feature = "names"))]
+    #[test]
+    fn describe() {
+        // backward-compatible SBR + PS from a 24kHz stereo core
+        let asc =
+            AudioSpecificConfig::from_bytes(&[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80]).unwrap();
+        let description = asc.describe().to_string();
+        assert!(
+            description.contains(
+                "  Extension (backward-compatible signalling)\n    SBR: yes\n    PS: yes\n    \
+                 Sampling frequency: 3 (48000 Hz)\n"
+            ),
+            "{}",
+            description
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn traced() {
+        let (asc, trace) =
+            AudioSpecificConfig::from_bytes_traced(&[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80]);
+        assert!(asc.is_ok());
+        let trace: Vec<_> = trace.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            vec![
+                "bits 0..5 audioObjectType=2",
+                "bits 5..9 samplingFrequencyIndex=6",
+                "bits 9..13 channelConfiguration=2",
+                "bits 13..14 frameLengthFlag=0",
+                "bits 14..15 dependsOnCoreCoder=0",
+                "bits 15..16 extensionFlag=0",
+                "bits 16..27 syncExtensionType",
+                "bits 27..32 audioObjectType=5",
+                "bits 32..33 sbrPresentFlag=1",
+                "bits 33..37 extensionSamplingFrequencyIndex=3",
+                "bits 37..48 syncExtensionType",
+                "bits 48..49 psPresentFlag=1",
+            ],
+            trace
+        );
+        // the fields read before an error are kept
+        let (asc, trace) = AudioSpecificConfig::from_bytes_traced(&[0x12]);
+        assert!(matches!(asc, Err(AscError::NotEnoughData(_))));
+        assert_eq!(1, trace.len());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn strictness() {
+        let parsed =
+            AudioSpecificConfig::from_bytes_with_options(&[0x12, 0x10], &ParseOptions::STRICT)
+                .unwrap();
+        assert!(parsed.warnings.is_empty());
+        // reserved object type 18
+        assert_eq!(
+            Err(AscError::Violation(Violation::ReservedValue(TraceEntry {
+                bit_offset: 0,
+                bit_length: 5,
+                field: "audioObjectType",
+                value: Some(18),
+            }))),
+            AudioSpecificConfig::from_bytes_with_options(&[0x92, 0x10], &ParseOptions::STRICT)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn trailing() {
+        // 30 bits of config, followed by padding bits set to 1
+        let buf = [0x12, 0x12, 0x00, 0x03];
+        let (_, trailing) = AudioSpecificConfig::from_bytes_with_trailing(&buf).unwrap();
+        assert_eq!(
+            TrailingBits {
+                bit_length: 2,
+                is_padding: false,
+            },
+            trailing
+        );
+        let parsed =
+            AudioSpecificConfig::from_bytes_with_options(&buf, &ParseOptions::PERMISSIVE).unwrap();
+        assert!(parsed.warnings.is_empty());
+        let options = ParseOptions {
+            check_trailing_data: true,
+            ..ParseOptions::PERMISSIVE
+        };
+        let parsed = AudioSpecificConfig::from_bytes_with_options(&buf, &options).unwrap();
+        assert_eq!(
+            vec![Violation::TrailingData(TraceEntry {
+                bit_offset: 30,
+                bit_length: 2,
+                field: "trailing_bits",
+                value: Some(3),
+            })],
+            parsed.warnings
+        );
+        let options = ParseOptions {
+            check_trailing_data: true,
+            ..ParseOptions::STRICT
+        };
+        assert!(
+            AudioSpecificConfig::from_bytes_with_options(&[0x12, 0x12, 0x00, 0x00], &options)
+                .is_ok()
+        );
+        // the unparsed config of ALS runs to the end of the buffer, so appended data is taken
+        // to be part of it rather than reported
+        let buf = [0xf8, 0x9e, 0x01, 0x58, 0x88, 0x20, 0x24, 0x68, 0xff, 0xff];
+        let parsed = AudioSpecificConfig::from_bytes_with_options(&buf, &options).unwrap();
+        assert!(parsed.warnings.is_empty());
+        assert!(matches!(
+            parsed.value.specific_config,
+            SpecificConfig::Unparsed(RawBits { bit_length: 37, .. })
+        ));
+    }
+
+    #[test]
+    fn view() {
+        let buf = [0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80];
+        let view = AscView::new(&buf).unwrap();
+        let asc = FixedAudioSpecificConfig::from_bytes(&buf).unwrap();
+        assert_eq!(asc.audio_object_type, view.audio_object_type());
+        assert_eq!(asc.sampling_frequency, view.sampling_frequency());
+        assert_eq!(asc.channel_configuration, view.channel_configuration());
+        assert_eq!(None, view.extension_sampling_frequency());
+        assert_eq!(
+            Err(AscError::NotEnoughData(TraceEntry {
+                bit_offset: 9,
+                bit_length: 24,
+                field: "samplingFrequency",
+                value: None,
+            })),
+            AscView::new(&[0x17, 0x80, 0x00])
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn borrowed() {
+        // object type 36 (ALS), whose unparsed specific config starts mid-byte, so is copied
+        let buf = [0xf8, 0x88, 0x20, 0x12, 0x34];
+        let asc = AudioSpecificConfig::from_bytes_borrowed(&buf).unwrap();
+        match asc.specific_config {
+            SpecificConfig::Unparsed(ref bits) => {
+                assert!(matches!(bits.data, Cow::Owned(_)));
+                assert_eq!(21, bits.bit_length);
+            }
+            ref other => panic!("unexpected {:?}", other),
+        }
+        let cached: AudioSpecificConfig<Cow<'static, [u8]>> = asc.clone().into_owned();
+        assert_eq!(asc, cached);
+        assert_eq!(
+            AudioSpecificConfig::from_bytes(&buf).unwrap(),
+            AudioSpecificConfig::from(cached)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn truncated() {
+        assert_eq!(
+            Err(AscError::NotEnoughData(TraceEntry {
+                bit_offset: 5,
+                bit_length: 4,
+                field: "samplingFrequencyIndex",
+                value: None,
+            })),
+            AudioSpecificConfig::from_bytes(&[0x12])
+        );
+        assert_eq!(
+            "not enough data, reading bits 5..9 samplingFrequencyIndex",
+            AudioSpecificConfig::from_bytes(&[0x12])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fixed() {
+        let samples: &[&[u8]] = &[
+            &[0x12, 0x10],
+            &[0x2b, 0x11, 0x88, 0x00],
+            &[0xeb, 0x09, 0x88, 0x00],
+            &[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80],
+        ];
+        for buf in samples {
+            let fixed = FixedAudioSpecificConfig::from_bytes(buf).unwrap();
+            let asc = AudioSpecificConfig::from_bytes(buf).unwrap();
+            assert_eq!(Ok(fixed.clone()), FixedAudioSpecificConfig::try_from(&asc));
+            assert_eq!(asc, AudioSpecificConfig::from(fixed.clone()));
+            let mut out = [0; FIXED_ASC_MAX_LENGTH];
+            let len = fixed.write_to(&mut out).unwrap();
+            assert_eq!(asc.to_bytes(), &out[..len]);
+            assert_eq!(None, fixed.write_to(&mut out[..len - 1]));
+        }
+        // ELD
+        assert_eq!(
+            Err(FixedAscError::Unsupported),
+            FixedAudioSpecificConfig::from_bytes(&[0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x00])
+        );
+    }
+
+    #[test]
+    fn fixed_truncated() {
+        // AAC Scalable, 44.1kHz, stereo, dependsOnCoreCoder set, ending within coreCoderDelay
+        assert_eq!(
+            Err(FixedAscError::NotEnoughData(TraceEntry {
+                bit_offset: 15,
+                bit_length: 14,
+                field: "coreCoderDelay",
+                value: None,
+            })),
+            FixedAudioSpecificConfig::from_bytes(&[0x32, 0x12])
+        );
+        let buf = [0x2b, 0x11, 0x88, 0x00];
+        for len in 0..buf.len() {
+            match FixedAudioSpecificConfig::from_bytes(&buf[..len]) {
+                Err(FixedAscError::NotEnoughData(at)) => assert_ne!("", at.field),
+                other => panic!("unexpected {:?} for {} bytes", other, len),
+            }
+        }
+    }
+}
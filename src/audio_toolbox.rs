@@ -0,0 +1,174 @@
+//! Mappings to the `AudioFormatID` values used by Apple's AudioToolbox and AVFoundation
+//! frameworks (the `mFormatID` of an `AudioStreamBasicDescription`), and to the CoreAudio
+//! `AudioChannelLayoutTag` values describing channel layouts.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::audio_toolbox::*;
+//! assert_eq!(Some(K_AUDIO_FORMAT_MPEG4_AAC_HE_V2), to_format_id(AudioObjectType::PS));
+//! assert_eq!(Some(AudioObjectType::ER_AAC_LD), from_format_id(K_AUDIO_FORMAT_MPEG4_AAC_LD));
+//! assert_eq!(
+//!     Some(K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_5_1),
+//!     channel_layout_tag(ChannelConfiguration::FIVE_ONE)
+//! );
+//! ```
+
+use crate::asc::{AudioSpecificConfig, SpecificConfig};
+use crate::{AudioObjectType, ChannelConfiguration};
+
+/// `kAudioFormatMPEG4AAC` (`'aac '`), for AAC LC
+pub const K_AUDIO_FORMAT_MPEG4_AAC: u32 = u32::from_be_bytes(*b"aac ");
+/// `kAudioFormatMPEG4AAC_HE` (`'aach'`), for HE-AAC
+pub const K_AUDIO_FORMAT_MPEG4_AAC_HE: u32 = u32::from_be_bytes(*b"aach");
+/// `kAudioFormatMPEG4AAC_HE_V2` (`'aacp'`), for HE-AAC v2
+pub const K_AUDIO_FORMAT_MPEG4_AAC_HE_V2: u32 = u32::from_be_bytes(*b"aacp");
+/// `kAudioFormatMPEG4AAC_LD` (`'aacl'`)
+pub const K_AUDIO_FORMAT_MPEG4_AAC_LD: u32 = u32::from_be_bytes(*b"aacl");
+/// `kAudioFormatMPEG4AAC_ELD` (`'aace'`)
+pub const K_AUDIO_FORMAT_MPEG4_AAC_ELD: u32 = u32::from_be_bytes(*b"aace");
+/// `kAudioFormatMPEG4AAC_ELD_SBR` (`'aacf'`), for AAC ELD using low-delay SBR
+pub const K_AUDIO_FORMAT_MPEG4_AAC_ELD_SBR: u32 = u32::from_be_bytes(*b"aacf");
+
+/// `kAudioChannelLayoutTag_Mono`
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_MONO: u32 = 100 << 16 | 1;
+/// `kAudioChannelLayoutTag_Stereo`
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_STEREO: u32 = 101 << 16 | 2;
+/// `kAudioChannelLayoutTag_AAC_3_0`: C L R
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_3_0: u32 = 114 << 16 | 3;
+/// `kAudioChannelLayoutTag_AAC_4_0`: C L R Cs
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_4_0: u32 = 116 << 16 | 4;
+/// `kAudioChannelLayoutTag_AAC_5_0`: C L R Ls Rs
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_5_0: u32 = 120 << 16 | 5;
+/// `kAudioChannelLayoutTag_AAC_5_1`: C L R Ls Rs Lfe
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_5_1: u32 = 124 << 16 | 6;
+/// `kAudioChannelLayoutTag_AAC_6_1`: C L R Ls Rs Cs Lfe
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_6_1: u32 = 142 << 16 | 7;
+/// `kAudioChannelLayoutTag_AAC_7_1`: C Lc Rc L R Ls Rs Lfe
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1: u32 = 127 << 16 | 8;
+/// `kAudioChannelLayoutTag_AAC_7_1_B`: C L R Ls Rs Rls Rrs Lfe
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1_B: u32 = 183 << 16 | 8;
+/// `kAudioChannelLayoutTag_AAC_7_1_C`: C L R Ls Rs Lfe Vhl Vhr
+pub const K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1_C: u32 = 184 << 16 | 8;
+
+/// The `AudioFormatID` for the given audio object type, or `None` if AudioToolbox defines no
+/// format for it.  `ER_AAC_ELD` maps to `kAudioFormatMPEG4AAC_ELD`; use
+/// [`format_id()`](fn.format_id.html) to distinguish ELD with low-delay SBR.
+pub fn to_format_id(audio_object_type: AudioObjectType) -> Option<u32> {
+    Some(match audio_object_type {
+        AudioObjectType::AAC_LC => K_AUDIO_FORMAT_MPEG4_AAC,
+        AudioObjectType::SBR => K_AUDIO_FORMAT_MPEG4_AAC_HE,
+        AudioObjectType::PS => K_AUDIO_FORMAT_MPEG4_AAC_HE_V2,
+        AudioObjectType::ER_AAC_LD => K_AUDIO_FORMAT_MPEG4_AAC_LD,
+        AudioObjectType::ER_AAC_ELD => K_AUDIO_FORMAT_MPEG4_AAC_ELD,
+        _ => return None,
+    })
+}
+
+/// The audio object type for the given `AudioFormatID`, or `None` if the value is not one of
+/// the AAC formats.  `kAudioFormatMPEG4AAC_ELD_SBR` maps to `ER_AAC_ELD`.
+pub fn from_format_id(format_id: u32) -> Option<AudioObjectType> {
+    Some(match format_id {
+        K_AUDIO_FORMAT_MPEG4_AAC => AudioObjectType::AAC_LC,
+        K_AUDIO_FORMAT_MPEG4_AAC_HE => AudioObjectType::SBR,
+        K_AUDIO_FORMAT_MPEG4_AAC_HE_V2 => AudioObjectType::PS,
+        K_AUDIO_FORMAT_MPEG4_AAC_LD => AudioObjectType::ER_AAC_LD,
+        K_AUDIO_FORMAT_MPEG4_AAC_ELD | K_AUDIO_FORMAT_MPEG4_AAC_ELD_SBR => {
+            AudioObjectType::ER_AAC_ELD
+        }
+        _ => return None,
+    })
+}
+
+/// The `AudioFormatID` for the given configuration, which is HE-AAC or HE-AAC v2 when the
+/// presence of SBR or PS is signalled, and `kAudioFormatMPEG4AAC_ELD_SBR` for ELD using
+/// low-delay SBR.
+///
+/// ```rust
+/// # use mpeg4_audio_const::asc::*;
+/// # use mpeg4_audio_const::audio_toolbox::*;
+/// let asc = AudioSpecificConfig::from_bytes(&[0x2b, 0x11, 0x88, 0x00]).unwrap();
+/// assert_eq!(Some(K_AUDIO_FORMAT_MPEG4_AAC_HE), format_id(&asc));
+/// ```
+pub fn format_id(asc: &AudioSpecificConfig) -> Option<u32> {
+    match asc.specific_config {
+        SpecificConfig::Eld(ref eld) if eld.ld_sbr.is_some() => {
+            Some(K_AUDIO_FORMAT_MPEG4_AAC_ELD_SBR)
+        }
+        _ => to_format_id(asc.signalled_object_type()),
+    }
+}
+
+/// The `AudioChannelLayoutTag` for the given channel configuration, or `None` for `0`, for
+/// reserved values, and for the 22.2 layout.  The `AAC_*` tags give the channels in the order
+/// that an AAC decoder outputs them.
+pub fn channel_layout_tag(channel_configuration: ChannelConfiguration) -> Option<u32> {
+    Some(match channel_configuration {
+        ChannelConfiguration::MONO => K_AUDIO_CHANNEL_LAYOUT_TAG_MONO,
+        ChannelConfiguration::STEREO => K_AUDIO_CHANNEL_LAYOUT_TAG_STEREO,
+        ChannelConfiguration::THREE => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_3_0,
+        ChannelConfiguration::FOUR => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_4_0,
+        ChannelConfiguration::FIVE => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_5_0,
+        ChannelConfiguration::FIVE_ONE => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_5_1,
+        ChannelConfiguration::SEVEN_ONE => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1,
+        ChannelConfiguration::SIX_ONE => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_6_1,
+        ChannelConfiguration::SEVEN_ONE_BACK => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1_B,
+        ChannelConfiguration::SEVEN_ONE_TOP_FRONT => K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1_C,
+        _ => return None,
+    })
+}
+
+/// The channel configuration for the given `AudioChannelLayoutTag`, or `None` if the tag is
+/// not one of those given by [`channel_layout_tag()`](fn.channel_layout_tag.html).
+pub fn from_channel_layout_tag(tag: u32) -> Option<ChannelConfiguration> {
+    Some(match tag {
+        K_AUDIO_CHANNEL_LAYOUT_TAG_MONO => ChannelConfiguration::MONO,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_STEREO => ChannelConfiguration::STEREO,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_3_0 => ChannelConfiguration::THREE,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_4_0 => ChannelConfiguration::FOUR,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_5_0 => ChannelConfiguration::FIVE,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_5_1 => ChannelConfiguration::FIVE_ONE,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1 => ChannelConfiguration::SEVEN_ONE,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_6_1 => ChannelConfiguration::SIX_ONE,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1_B => ChannelConfiguration::SEVEN_ONE_BACK,
+        K_AUDIO_CHANNEL_LAYOUT_TAG_AAC_7_1_C => ChannelConfiguration::SEVEN_ONE_TOP_FRONT,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn roundtrip() {
+        for aot in 0..=95 {
+            if let Ok(aot) = AudioObjectType::try_from(aot) {
+                if let Some(id) = to_format_id(aot) {
+                    assert_eq!(Some(aot), from_format_id(id));
+                }
+            }
+        }
+        assert_eq!(0x6161_6320, K_AUDIO_FORMAT_MPEG4_AAC);
+        assert_eq!(None, from_format_id(u32::from_be_bytes(*b"lpcm")));
+        let eld_sbr =
+            AudioSpecificConfig::from_bytes(&[0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x00]).unwrap();
+        assert_eq!(Some(K_AUDIO_FORMAT_MPEG4_AAC_ELD_SBR), format_id(&eld_sbr));
+    }
+
+    #[test]
+    fn channel_layouts() {
+        for c in 0..=15 {
+            let conf = ChannelConfiguration::try_from(c).unwrap();
+            if let Some(tag) = channel_layout_tag(conf) {
+                assert_eq!(Some(conf), from_channel_layout_tag(tag));
+                // the low 16 bits of a tag give the channel count
+                assert_eq!(conf.channel_count(), Some(tag & 0xffff));
+            }
+        }
+        assert_eq!(
+            None,
+            channel_layout_tag(ChannelConfiguration::TWENTY_TWO_TWO)
+        );
+    }
+}
@@ -0,0 +1,489 @@
+//! Minimal MSB-first bit reader and writer used by the parsers and serializers in this crate.
+
+use crate::options::{Limits, Violation};
+use crate::trace::{AscVisitor, TraceEntry};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Indicates that a read ran past the end of the available data, giving the element that
+/// could not be read.
+#[derive(PartialEq, Debug)]
+pub(crate) struct EndOfData(pub TraceEntry);
+
+pub(crate) struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    end: usize,
+    /// The position of this reader's data within the data being parsed, for readers created
+    /// by `nested()`, to be added to the positions reported
+    base: usize,
+    /// The name given by `named()` for the next read
+    name: Option<&'static str>,
+    /// The most recent read
+    last: TraceEntry,
+    /// Receives the named reads, when visiting
+    visitor: Option<&'a mut dyn AscVisitor>,
+    /// Set when the visitor asked for parsing to stop
+    stopped: bool,
+    /// The bounds on the structures read, checked by the parsers
+    limits: Limits,
+    /// The violations of the spec found so far, when checking is enabled
+    #[cfg(feature = "alloc")]
+    violations: Option<Vec<Violation>>,
+}
+
+// some methods are used only by the parsers needing `alloc`
+#[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> BitReader<'a> {
+        Self::with_bit_length(data, data.len() * 8)
+    }
+
+    /// A reader limited to the first `bit_length` bits of the given data.
+    pub fn with_bit_length(data: &'a [u8], bit_length: usize) -> BitReader<'a> {
+        BitReader {
+            data,
+            pos: 0,
+            end: bit_length.min(data.len() * 8),
+            base: 0,
+            name: None,
+            last: TraceEntry {
+                bit_offset: 0,
+                bit_length: 0,
+                field: "",
+                value: None,
+            },
+            visitor: None,
+            stopped: false,
+            limits: Limits::DEFAULT,
+            #[cfg(feature = "alloc")]
+            violations: None,
+        }
+    }
+
+    /// A reader passing each named read to the given visitor.
+    pub fn visiting(data: &'a [u8], visitor: &'a mut dyn AscVisitor) -> BitReader<'a> {
+        let mut r = Self::new(data);
+        r.visitor = Some(visitor);
+        r
+    }
+
+    /// `true` if the visitor asked for parsing to stop, in which case the reader behaves as if
+    /// the data ended at that point.
+    pub fn stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// The bounds on the structures read
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    /// Starts recording the violations of the spec noted by the parser.
+    #[cfg(feature = "alloc")]
+    pub fn enable_checks(&mut self) {
+        self.violations = Some(Vec::new());
+    }
+
+    /// The violations recorded since checking was enabled.
+    #[cfg(feature = "alloc")]
+    pub fn take_violations(&mut self) -> Vec<Violation> {
+        self.violations.take().unwrap_or_default()
+    }
+
+    /// Notes that the most recent read gave a value violating the spec in the given way, if
+    /// checking is enabled.  Parsing continues regardless, since the caller decides whether
+    /// violations are acceptable.
+    #[cfg_attr(not(feature = "alloc"), allow(unused_variables))]
+    pub fn violation(&mut self, kind: fn(TraceEntry) -> Violation) {
+        #[cfg(feature = "alloc")]
+        if let Some(ref mut violations) = self.violations {
+            violations.push(kind(self.last));
+        }
+    }
+
+    /// Reads a structure held in a separate buffer extracted from this reader's data (e.g. by
+    /// `read_bits()`), which began at the given bit position, reporting its reads and any
+    /// violations of the spec as if made by this reader.
+    #[cfg(feature = "alloc")]
+    pub fn nested<T, E>(
+        &mut self,
+        data: &[u8],
+        bit_length: usize,
+        start: usize,
+        read: impl FnOnce(&mut BitReader<'_>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let mut r = BitReader::with_bit_length(data, bit_length);
+        r.base = self.base + start;
+        r.limits = self.limits;
+        r.violations = self.violations.take();
+        let result = match self.visitor {
+            Some(ref mut visitor) => {
+                r.visitor = Some(&mut **visitor);
+                read(&mut r)
+            }
+            None => read(&mut r),
+        };
+        self.violations = r.violations.take();
+        if r.stopped {
+            self.stop();
+        }
+        result
+    }
+
+    /// Gives the name of the syntax element that the next read (or skip) consumes, for the
+    /// trace.
+    pub fn named(&mut self, name: &'static str) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Records the bits from `start` up to the current position as the most recent read, and
+    /// passes them to any visitor if the read was named.
+    fn record(&mut self, start: usize, value: Option<u32>) {
+        let name = self.name.take();
+        self.last = TraceEntry {
+            bit_offset: self.base + start,
+            bit_length: self.pos - start,
+            field: name.unwrap_or(""),
+            value,
+        };
+        if let (Some(_), Some(visitor)) = (name, &mut self.visitor) {
+            if visitor.field(self.last).is_break() {
+                self.stop();
+            }
+        }
+    }
+
+    /// Ends the data at the current position, so that parsing stops.
+    fn stop(&mut self) {
+        self.stopped = true;
+        self.end = self.pos;
+    }
+
+    /// The element consumed by the most recent read (or skip), for reporting an invalid value.
+    pub fn last_read(&self) -> TraceEntry {
+        self.last
+    }
+
+    /// The error for a read of `count` bits from the current position, which would run past
+    /// the end of the data.
+    fn end_of_data(&mut self, count: usize) -> EndOfData {
+        EndOfData(TraceEntry {
+            bit_offset: self.base + self.pos,
+            bit_length: count,
+            field: self.name.take().unwrap_or(""),
+            value: None,
+        })
+    }
+
+    /// The data being read, including any already consumed.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// The number of bits consumed so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The number of bits not yet consumed.
+    pub fn remaining(&self) -> usize {
+        self.end - self.pos
+    }
+
+    /// Reads up to 32 bits, returning them in the least-significant bits of the result.
+    pub fn read(&mut self, count: u32) -> Result<u32, EndOfData> {
+        debug_assert!(count <= 32);
+        if self.remaining() < count as usize {
+            return Err(self.end_of_data(count as usize));
+        }
+        let start = self.pos;
+        let mut result = 0u32;
+        let mut left = count;
+        while left > 0 {
+            let byte = self.data[self.pos / 8];
+            let bit_offset = (self.pos % 8) as u32;
+            let avail = 8 - bit_offset;
+            let take = avail.min(left);
+            let bits = (byte >> (avail - take)) & (0xff >> (8 - take));
+            result = (result << take) | u32::from(bits);
+            left -= take;
+            self.pos += take as usize;
+        }
+        self.record(start, Some(result));
+        Ok(result)
+    }
+
+    pub fn read_u8(&mut self, count: u32) -> Result<u8, EndOfData> {
+        debug_assert!(count <= 8);
+        self.read(count).map(|v| v as u8)
+    }
+
+    pub fn read_u16(&mut self, count: u32) -> Result<u16, EndOfData> {
+        debug_assert!(count <= 16);
+        self.read(count).map(|v| v as u16)
+    }
+
+    /// Reads up to 32 bits without consuming them.
+    pub fn peek(&self, count: u32) -> Result<u32, EndOfData> {
+        let mut r = BitReader::with_bit_length(self.data, self.end);
+        r.base = self.base;
+        r.pos = self.pos;
+        r.read(count)
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, EndOfData> {
+        self.read(1).map(|v| v == 1)
+    }
+
+    /// Skips any bits remaining before the next byte boundary (relative to the start of the
+    /// data this reader was created with).
+    pub fn byte_align(&mut self) {
+        self.pos = (self.pos + 7) & !7;
+    }
+
+    /// Reads the given number of bits, returning them left-aligned in a byte buffer (with any
+    /// unused bits of the final byte set to zero).
+    #[cfg(feature = "alloc")]
+    pub fn read_bits(&mut self, count: usize) -> Result<Vec<u8>, EndOfData> {
+        if self.remaining() < count {
+            return Err(self.end_of_data(count));
+        }
+        let start = self.pos;
+        let name = self.name.take();
+        let mut data = Vec::with_capacity(count.div_ceil(8));
+        for _ in 0..count / 8 {
+            data.push(self.read_u8(8)?);
+        }
+        let rest = (count % 8) as u32;
+        if rest > 0 {
+            data.push(self.read_u8(rest)? << (8 - rest));
+        }
+        self.name = name;
+        self.record(start, None);
+        Ok(data)
+    }
+
+    /// Consumes all remaining bits, returning them left-aligned in a byte buffer (with any
+    /// unused bits of the final byte set to zero) along with their count.
+    #[cfg(feature = "alloc")]
+    pub fn read_remaining(&mut self) -> (Vec<u8>, usize) {
+        let len = self.remaining();
+        (self.read_bits(len).unwrap(), len)
+    }
+
+    pub fn skip(&mut self, count: usize) -> Result<(), EndOfData> {
+        if self.remaining() < count {
+            return Err(self.end_of_data(count));
+        }
+        let start = self.pos;
+        self.pos += count;
+        self.record(start, None);
+        Ok(())
+    }
+}
+
+/// A destination for MSB-first bits, implemented both by the growable `BitWriter` and by the
+/// fixed-capacity `SliceWriter`, for serializers usable without `alloc`.
+pub(crate) trait BitSink {
+    fn write_bool(&mut self, value: bool);
+
+    /// Writes the least-significant `count` bits of the given value (`count` may be up to 32);
+    /// any higher bits of the value are ignored.
+    fn write(&mut self, count: u32, value: u32) {
+        debug_assert!(count <= 32);
+        for i in (0..count).rev() {
+            self.write_bool((value >> i) & 1 == 1);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub(crate) struct BitWriter {
+    data: Vec<u8>,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl BitWriter {
+    pub fn new() -> BitWriter {
+        BitWriter::default()
+    }
+
+    /// Writes the least-significant `count` bits of the given value (`count` may be up to 32);
+    /// any higher bits of the value are ignored.
+    pub fn write(&mut self, count: u32, value: u32) {
+        debug_assert!(count <= 32);
+        for i in (0..count).rev() {
+            self.write_bool((value >> i) & 1 == 1);
+        }
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        if self.len % 8 == 0 {
+            self.data.push(0);
+        }
+        if value {
+            let last = self.data.last_mut().unwrap();
+            *last |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    /// Writes the first `bit_length` bits of the given left-aligned data.
+    pub fn write_bits(&mut self, data: &[u8], bit_length: usize) {
+        for i in 0..bit_length {
+            self.write_bool(data[i / 8] & (0x80 >> (i % 8)) != 0);
+        }
+    }
+
+    /// Writes zero bits up to the next byte boundary.
+    pub fn byte_align(&mut self) {
+        self.len = (self.len + 7) & !7;
+    }
+
+    /// The number of bits written so far.
+    pub fn bit_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the written data, with any unused bits of the final byte set to zero.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitSink for BitWriter {
+    fn write_bool(&mut self, value: bool) {
+        BitWriter::write_bool(self, value)
+    }
+}
+
+/// Writes into a caller-provided buffer.  Bits beyond the end of the buffer are discarded,
+/// with the overflow reported by `finish()`.
+pub(crate) struct SliceWriter<'a> {
+    data: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub fn new(data: &'a mut [u8]) -> SliceWriter<'a> {
+        SliceWriter { data, len: 0 }
+    }
+
+    /// The number of bytes written (with any unused bits of the final byte set to zero), or
+    /// `None` if the buffer was too small.
+    pub fn finish(self) -> Option<usize> {
+        let bytes = self.len.div_ceil(8);
+        if bytes <= self.data.len() {
+            Some(bytes)
+        } else {
+            None
+        }
+    }
+}
+
+impl BitSink for SliceWriter<'_> {
+    fn write_bool(&mut self, value: bool) {
+        if let Some(byte) = self.data.get_mut(self.len / 8) {
+            if self.len % 8 == 0 {
+                *byte = 0;
+            }
+            if value {
+                *byte |= 0x80 >> (self.len % 8);
+            }
+        }
+        self.len += 1;
+    }
+}
+
+/// Overwrites bits within existing data from the given bit position, leaving the other bits
+/// unchanged.  Bits beyond the end of the data are discarded.
+pub(crate) struct PatchWriter<'a> {
+    data: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> PatchWriter<'a> {
+    pub fn new(data: &'a mut [u8], pos: usize) -> PatchWriter<'a> {
+        PatchWriter { data, pos }
+    }
+}
+
+impl BitSink for PatchWriter<'_> {
+    fn write_bool(&mut self, value: bool) {
+        if let Some(byte) = self.data.get_mut(self.pos / 8) {
+            let mask = 0x80 >> (self.pos % 8);
+            if value {
+                *byte |= mask;
+            } else {
+                *byte &= !mask;
+            }
+        }
+        self.pos += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_across_bytes() {
+        let mut r = BitReader::new(&[0b1010_1100, 0b0101_0011]);
+        assert_eq!(Ok(0b101), r.read(3));
+        assert_eq!(Ok(0b0110_0010), r.read(8));
+        assert_eq!(5, r.remaining());
+        assert_eq!(Ok(true), r.read_bool());
+        assert_eq!(
+            Err(EndOfData(TraceEntry {
+                bit_offset: 12,
+                bit_length: 5,
+                field: "",
+                value: None,
+            })),
+            r.read(5)
+        );
+        assert_eq!(Ok(0b0011), r.read(4));
+        assert_eq!(0, r.remaining());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn write_roundtrip() {
+        let mut w = BitWriter::new();
+        w.write(3, 0b101);
+        w.write(8, 0b0110_0010);
+        w.write_bits(&[0b1000_0000], 1);
+        w.byte_align();
+        w.write(16, 0xabcd);
+        assert_eq!(vec![0b1010_1100, 0b0101_0000, 0xab, 0xcd], w.into_bytes());
+    }
+
+    #[test]
+    fn slice_writer() {
+        let mut buf = [0xff; 2];
+        let mut w = SliceWriter::new(&mut buf);
+        w.write(3, 0b101);
+        w.write(8, 0b0110_0010);
+        assert_eq!(Some(2), w.finish());
+        assert_eq!([0b1010_1100, 0b0100_0000], buf);
+        let mut w = SliceWriter::new(&mut buf);
+        w.write(17, 0);
+        assert_eq!(None, w.finish());
+    }
+
+    #[test]
+    fn patch_writer() {
+        let mut buf = [0b1010_1010, 0b1111_0000];
+        PatchWriter::new(&mut buf, 6).write(4, 0b0110);
+        assert_eq!([0b1010_1001, 0b1011_0000], buf);
+    }
+}
@@ -0,0 +1,265 @@
+//! Traits implemented by each of the configuration structures that this crate can parse and
+//! serialize, for generic code handling any of them (e.g. checking that each structure found
+//! in a stream survives a round trip).
+//!
+//! Some structures can only be parsed given values from the structure enclosing them (e.g. a
+//! `GASpecificConfig` depends on the `audioObjectType` and `channelConfiguration` of its
+//! `AudioSpecificConfig`), which are supplied as the `Context`.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::bitstream::*;
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::latm::*;
+//! fn round_trips<T>(data: &[u8], context: T::Context) -> bool
+//! where
+//!     T: FromBitstream + ToBitstream + PartialEq,
+//! {
+//!     match T::from_bitstream(data, context) {
+//!         Ok(value) => T::from_bitstream(&value.to_bitstream().data, context).ok() == Some(value),
+//!         Err(_) => false,
+//!     }
+//! }
+//! assert!(round_trips::<AudioSpecificConfig>(&[0x12, 0x10], ()));
+//! assert!(round_trips::<StreamMuxConfig>(&[0x40, 0x00, 0x24, 0x20, 0x3f, 0xc0], ()));
+//! ```
+//!
+//! # Exact and canonical serialization
+//!
+//! Some values can be encoded in more than one way; for example a `StreamMuxConfig` may
+//! signal a length using more bytes than it needs, and the bits padding a structure to a byte
+//! boundary need not be zero.  The parsed structures do not record these choices, so there are
+//! two kinds of round trip:
+//!
+//!  - [`Verbatim`](struct.Verbatim.html) keeps the bits that a structure was parsed from, and
+//!    reproduces them exactly.  This is what a remuxer passing a configuration through
+//!    unchanged needs.
+//!  - `to_bitstream()` on the structures themselves, and their `to_bytes()` methods, always
+//!    produce the minimal encoding of each field, with zero padding, so equal structures
+//!    always give identical bits.  This is what an encoder needs.  Some structures hold
+//!    different values with the same meaning (such as a sampling frequency given either
+//!    explicitly or by index), which
+//!    [`AudioSpecificConfig::canonicalize()`](../asc/struct.AudioSpecificConfig.html#method.canonicalize)
+//!    normalizes before serializing.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::bitstream::*;
+//! # use mpeg4_audio_const::asc::*;
+//! // AAC LC, 44.1kHz stereo with a core coder delay, followed by two padding bits set to 1
+//! let buf = [0x12, 0x12, 0x00, 0x03];
+//! let verbatim = Verbatim::<AudioSpecificConfig>::from_bitstream(&buf, ()).unwrap();
+//! assert_eq!(30, verbatim.bit_length());
+//! assert_eq!(&buf[..], verbatim.original_bytes());
+//! assert_eq!(vec![0x12, 0x12, 0x00, 0x00], verbatim.value().to_bytes());
+//! ```
+
+use crate::asc::RawBits;
+use crate::bits::{BitReader, BitWriter};
+use alloc::vec::Vec;
+
+/// A structure which can be parsed from the bits at the start of a buffer
+pub trait FromBitstream: Sized {
+    /// The values from the enclosing structure that determine this structure's syntax, or `()`
+    /// for structures that stand alone
+    type Context: Copy;
+    type Error;
+
+    /// Parses the structure from the start of the given data, ignoring any data that follows
+    /// it.
+    fn from_bitstream(data: &[u8], context: Self::Context) -> Result<Self, Self::Error> {
+        Self::from_bitstream_with_length(data, context).map(|(value, _)| value)
+    }
+
+    /// Parses the structure as for `from_bitstream()`, also giving the number of bits it
+    /// occupied.
+    fn from_bitstream_with_length(
+        data: &[u8],
+        context: Self::Context,
+    ) -> Result<(Self, usize), Self::Error>;
+}
+
+/// A structure which can be serialized to bits
+pub trait ToBitstream {
+    /// Serializes the structure, giving exactly the bits it occupies (which need not be a whole
+    /// number of bytes).
+    fn to_bitstream(&self) -> RawBits;
+}
+
+/// A parsed structure along with the exact bits it was parsed from, for reproducing them
+/// unchanged
+///
+/// `to_bitstream()` gives the original bits, and `original_bytes()` the original bytes
+/// including any padding bits following the structure in its final byte.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Verbatim<T> {
+    value: T,
+    data: Vec<u8>,
+    bit_length: usize,
+}
+
+impl<T: FromBitstream> Verbatim<T> {
+    /// Parses the structure from the start of the given data, as for
+    /// `T::from_bitstream()`, retaining the bytes it occupies.
+    pub fn from_bitstream(data: &[u8], context: T::Context) -> Result<Verbatim<T>, T::Error> {
+        let (value, bit_length) = T::from_bitstream_with_length(data, context)?;
+        Ok(Verbatim {
+            value,
+            data: data[..bit_length.div_ceil(8)].to_vec(),
+            bit_length,
+        })
+    }
+}
+
+impl<T> Verbatim<T> {
+    /// The parsed structure
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Discards the original bits, giving the parsed structure (which, if modified, can then be
+    /// serialized again in its canonical form).
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// The bytes that the structure was parsed from, the final byte holding any bits that
+    /// followed the structure unmodified
+    pub fn original_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The number of bits that the structure occupied
+    pub fn bit_length(&self) -> usize {
+        self.bit_length
+    }
+}
+
+impl<T> core::ops::Deref for Verbatim<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Gives exactly the bits originally parsed, which need not be the encoding that `T` itself
+/// would produce.
+impl<T> ToBitstream for Verbatim<T> {
+    fn to_bitstream(&self) -> RawBits {
+        let mut data = self.data.clone();
+        if self.bit_length % 8 != 0 {
+            if let Some(last) = data.last_mut() {
+                *last &= 0xff << (8 - self.bit_length % 8);
+            }
+        }
+        RawBits {
+            data,
+            bit_length: self.bit_length,
+        }
+    }
+}
+
+/// Reads a structure from the start of the given data with the given function, also giving
+/// the number of bits consumed.
+pub(crate) fn read_counted<'a, T, E>(
+    data: &'a [u8],
+    read: impl FnOnce(&mut BitReader<'a>) -> Result<T, E>,
+) -> Result<(T, usize), E> {
+    let mut r = BitReader::new(data);
+    let value = read(&mut r)?;
+    Ok((value, r.position()))
+}
+
+/// Collects the bits written by the given function.
+pub(crate) fn write_bits(write: impl FnOnce(&mut BitWriter)) -> RawBits {
+    let mut w = BitWriter::new();
+    write(&mut w);
+    let bit_length = w.bit_len();
+    RawBits {
+        data: w.into_bytes(),
+        bit_length,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asc::{AudioSpecificConfig, SpecificConfig};
+    use crate::latm::StreamMuxConfig;
+    use crate::pce::ProgramConfigElement;
+    use crate::{AudioObjectType, ChannelConfiguration};
+    use core::fmt::Debug;
+
+    fn round_trip<T>(value: &T, context: T::Context, bit_length: usize)
+    where
+        T: FromBitstream + ToBitstream + PartialEq + Debug,
+        T::Error: Debug,
+    {
+        let bits = value.to_bitstream();
+        assert_eq!(bit_length, bits.bit_length);
+        assert_eq!(value, &T::from_bitstream(&bits.data, context).unwrap());
+    }
+
+    #[test]
+    fn structures() {
+        let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+        round_trip(&asc, (), 16);
+        match asc.specific_config {
+            SpecificConfig::Ga(ref ga) => round_trip(
+                ga,
+                (ChannelConfiguration::STEREO, AudioObjectType::AAC_LC),
+                3,
+            ),
+            ref other => panic!("unexpected {:?}", other),
+        }
+        // ER AAC ELD, mono, with an SBR header
+        let asc = AudioSpecificConfig::from_bytes(&[0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x00]).unwrap();
+        match asc.specific_config {
+            SpecificConfig::Eld(ref eld) => {
+                round_trip(eld, ChannelConfiguration::MONO, 27);
+                round_trip(&eld.ld_sbr.as_ref().unwrap().headers[0], (), 16);
+            }
+            ref other => panic!("unexpected {:?}", other),
+        }
+        let pce = ProgramConfigElement::from_bytes(&[
+            0x04, 0xc8, 0x05, 0x00, 0x01, 0x08, 0x80, 0x01, b'x',
+        ])
+        .unwrap();
+        round_trip(&pce, (), 72);
+    }
+
+    #[test]
+    fn verbatim() {
+        // a version 1 StreamMuxConfig, signalling taraBufferFullness in two bytes where one
+        // would do
+        let mut w = BitWriter::new();
+        w.write(1, 1); // audioMuxVersion
+        w.write(1, 0); // audioMuxVersionA
+        w.write(2, 1); // bytesForValue
+        w.write(16, 0xff);
+        w.write(1, 1); // allStreamsSameTimeFraming
+        w.write(6, 0); // numSubFrames
+        w.write(4, 0); // numProgram
+        w.write(3, 0); // numLayer
+        w.write(2, 0); // bytesForValue
+        w.write(8, 16); // ascLen
+        w.write(16, 0x1210);
+        w.write(3, 0); // frameLengthType
+        w.write(8, 0xff); // latmBufferFullness
+        w.write(1, 0); // otherDataPresent
+        w.write(1, 0); // crcCheckPresent
+        let bit_length = w.bit_len();
+        let data = w.into_bytes();
+
+        let verbatim = Verbatim::<StreamMuxConfig>::from_bitstream(&data, ()).unwrap();
+        assert_eq!(Some(0xff), verbatim.tara_buffer_fullness);
+        let exact = verbatim.to_bitstream();
+        assert_eq!(bit_length, exact.bit_length);
+        assert_eq!(data, exact.data);
+        let canonical = verbatim.value().to_bitstream();
+        assert_eq!(bit_length - 8, canonical.bit_length);
+        assert_eq!(
+            verbatim.into_value(),
+            StreamMuxConfig::from_bytes(&canonical.data).unwrap()
+        );
+    }
+}
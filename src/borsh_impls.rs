@@ -0,0 +1,60 @@
+//! borsh implementations for the value types, each encoded as its single-byte value.
+//! Deserialization rejects the same values as the type's `TryFrom<u8>` implementation (or
+//! `From<u8>`, which accepts every value).
+
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::convert::TryFrom;
+
+macro_rules! implement_borsh {
+    ( $( $t:ident ),* $(,)? ) => {
+        $(
+            impl BorshSerialize for $t {
+                fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                    u8::from(*self).serialize(writer)
+                }
+            }
+
+            impl BorshDeserialize for $t {
+                fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                    $t::try_from(u8::deserialize_reader(reader)?).map_err(|_| {
+                        Error::new(
+                            ErrorKind::InvalidData,
+                            concat!("invalid ", stringify!($t), " value"),
+                        )
+                    })
+                }
+            }
+        )*
+    }
+}
+
+implement_borsh! {
+    AudioObjectType,
+    SamplingFrequencyIndex,
+    ChannelConfiguration,
+    AudioProfileLevelIndication,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let value = (
+            AudioObjectType::AAC_LC,
+            SamplingFrequencyIndex::FREQ_48000,
+            ChannelConfiguration::STEREO,
+            AudioProfileLevelIndication::AAC_L2,
+        );
+        let bytes = borsh::to_vec(&value).unwrap();
+        assert_eq!(vec![2, 3, 2, 0x29], bytes);
+        assert_eq!(value, borsh::from_slice(&bytes).unwrap());
+        assert!(borsh::from_slice::<AudioObjectType>(&[31]).is_err());
+        assert!(borsh::from_slice::<SamplingFrequencyIndex>(&[15]).is_err());
+    }
+}
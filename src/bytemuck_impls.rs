@@ -0,0 +1,67 @@
+//! bytemuck implementations for the value types, so that they may be held within `Pod`-style
+//! structures.  Casts from bytes are checked (via `CheckedBitPattern`) for the types that don't
+//! accept every byte value, rejecting the same values as their `TryFrom<u8>` implementations.
+
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use bytemuck::{CheckedBitPattern, NoUninit, Pod, Zeroable};
+use core::convert::TryFrom;
+
+macro_rules! implement_bytemuck {
+    ( $( $t:ident ),* $(,)? ) => {
+        $(
+            // SAFETY: the type is a `#[repr(transparent)]` wrapper of a `u8`, and `0` is valid
+            unsafe impl Zeroable for $t {}
+            // SAFETY: the type is a `#[repr(transparent)]` wrapper of a `u8`, so has no padding
+            unsafe impl NoUninit for $t {}
+            // SAFETY: the type is a `#[repr(transparent)]` wrapper of a `u8`, and accepts
+            // exactly the values that `TryFrom<u8>` does
+            unsafe impl CheckedBitPattern for $t {
+                type Bits = u8;
+
+                fn is_valid_bit_pattern(bits: &u8) -> bool {
+                    $t::try_from(*bits).is_ok()
+                }
+            }
+        )*
+    }
+}
+
+implement_bytemuck! {
+    AudioObjectType,
+    SamplingFrequencyIndex,
+    ChannelConfiguration,
+}
+
+// SAFETY: a `#[repr(transparent)]` wrapper of a `u8`, accepting every value
+unsafe impl Zeroable for AudioProfileLevelIndication {}
+// SAFETY: as above
+unsafe impl Pod for AudioProfileLevelIndication {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::checked::{self, CheckedCastError};
+
+    #[test]
+    fn casts() {
+        assert_eq!(
+            Ok(&[AudioObjectType::AAC_LC, AudioObjectType::SBR][..]),
+            checked::try_cast_slice::<u8, AudioObjectType>(&[2, 5])
+        );
+        assert_eq!(
+            Err(CheckedCastError::InvalidBitPattern),
+            checked::try_cast_slice::<u8, AudioObjectType>(&[2, 31])
+        );
+        assert_eq!(
+            Err(CheckedCastError::InvalidBitPattern),
+            checked::try_cast::<u8, SamplingFrequencyIndex>(15)
+        );
+        assert_eq!(&[6], bytemuck::bytes_of(&ChannelConfiguration::FIVE_ONE));
+        assert_eq!(
+            AudioProfileLevelIndication::AAC_L2,
+            bytemuck::cast::<u8, AudioProfileLevelIndication>(0x29)
+        );
+    }
+}
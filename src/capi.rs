@@ -0,0 +1,368 @@
+//! A C API, for C and C++ media pipelines to reuse this crate's tables and parsers.
+//!
+//! The functions and `#[repr(C)]` types here are intended to be described by a
+//! [cbindgen](https://github.com/mozilla/cbindgen)-generated header, with the library built
+//! as a `staticlib` or `cdylib` (e.g. using `cargo rustc --features capi --crate-type
+//! staticlib`).  Each function reports failure by a [`Status`](enum.Status.html) value rather
+//! than by panicking, and none retain the pointers they are given.
+
+use crate::adts::{AdtsHeader, AdtsHeaderError, MpegVersion};
+use crate::asc::{
+    has_ep_config, is_ga, AscError, AudioExtension, AudioSpecificConfig, FixedAudioSpecificConfig,
+    SamplingFrequency, SpecificConfig,
+};
+use crate::{
+    AudioObjectType, ChannelConfiguration, SamplingFrequencyIndex,
+    SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE,
+};
+use core::convert::TryFrom;
+use core::slice;
+
+/// The outcome of a call
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Status {
+    Ok = 0,
+    /// A pointer argument was null.
+    NullPointer = 1,
+    /// A value was outside the range that the field can represent.
+    InvalidValue = 2,
+    /// The data ended before the end of the structure.
+    NotEnoughData = 3,
+    /// The data was malformed.
+    InvalidData = 4,
+    /// The configuration can't be represented by an [`AscInfo`](struct.AscInfo.html).
+    Unsupported = 5,
+    /// The output buffer was too small.
+    BufferTooSmall = 6,
+}
+
+/// The result of [`aot_try_from_u8()`](fn.aot_try_from_u8.html)
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct AotResult {
+    pub status: Status,
+    /// The validated audio object type, when `status` is `Ok`
+    pub audio_object_type: u8,
+}
+
+/// The main fields of an `AudioSpecificConfig`
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct AscInfo {
+    /// The object type of the core codec (i.e. `2` for HE-AAC, rather than `5`)
+    pub audio_object_type: u8,
+    /// `15` when the frequency is given explicitly
+    pub sampling_frequency_index: u8,
+    /// The frequency in Hz (or `0` when given by a reserved index); ignored by
+    /// [`asc_serialize()`](fn.asc_serialize.html) unless `sampling_frequency_index` is `15`
+    pub sampling_frequency: u32,
+    pub channel_configuration: u8,
+    pub sbr_present: bool,
+    pub ps_present: bool,
+    /// As for `sampling_frequency_index`, but for the SBR output rate, when `sbr_present`
+    pub extension_sampling_frequency_index: u8,
+    /// As for `sampling_frequency`, but for the SBR output rate, when `sbr_present`
+    pub extension_sampling_frequency: u32,
+    /// The `GASpecificConfig` `frameLengthFlag` (`false` for other object types)
+    pub frame_length_flag: bool,
+}
+
+/// The result of [`asc_parse()`](fn.asc_parse.html)
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct AscResult {
+    pub status: Status,
+    /// The parsed configuration, when `status` is `Ok`
+    pub asc: AscInfo,
+}
+
+/// The fields of an ADTS header
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+pub struct AdtsHeaderInfo {
+    /// `2` or `4`
+    pub mpeg_version: u8,
+    pub protected: bool,
+    pub audio_object_type: u8,
+    pub sampling_frequency_index: u8,
+    pub channel_configuration: u8,
+    /// The length of the whole frame in bytes, including the header
+    pub frame_length: u16,
+    pub buffer_fullness: u16,
+    /// The number of `raw_data_block()`s in the frame, _minus one_
+    pub number_of_raw_data_blocks_in_frame: u8,
+    /// The CRC value, when `protected`
+    pub crc: u16,
+}
+
+/// The result of [`adts_header_parse()`](fn.adts_header_parse.html)
+#[repr(C)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct AdtsHeaderResult {
+    pub status: Status,
+    /// The parsed header, when `status` is `Ok`
+    pub header: AdtsHeaderInfo,
+}
+
+/// Validates an audio object type value, failing with `InvalidValue` for the escape value
+/// `31` and for values over `95`.
+#[no_mangle]
+pub extern "C" fn aot_try_from_u8(value: u8) -> AotResult {
+    match AudioObjectType::try_from(value) {
+        Ok(aot) => AotResult {
+            status: Status::Ok,
+            audio_object_type: u8::from(aot),
+        },
+        Err(_) => AotResult {
+            status: Status::InvalidValue,
+            audio_object_type: 0,
+        },
+    }
+}
+
+/// Parses an `AudioSpecificConfig` from the `len` bytes at `data`.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn asc_parse(data: *const u8, len: usize) -> AscResult {
+    if data.is_null() {
+        return AscResult {
+            status: Status::NullPointer,
+            asc: AscInfo::default(),
+        };
+    }
+    match AudioSpecificConfig::from_bytes(slice::from_raw_parts(data, len)) {
+        Ok(asc) => AscResult {
+            status: Status::Ok,
+            asc: asc_info(&asc),
+        },
+        Err(e) => AscResult {
+            status: match e {
+                AscError::NotEnoughData(_) => Status::NotEnoughData,
+                AscError::Violation(_) | AscError::LengthLimitExceeded(_) | AscError::Pce(_) => {
+                    Status::InvalidData
+                }
+            },
+            asc: AscInfo::default(),
+        },
+    }
+}
+
+/// Serializes an `AudioSpecificConfig` described by `info` into the `out_len` bytes at `out`,
+/// storing the number of bytes written at `written`.
+///
+/// Only the object types using the `GASpecificConfig` are supported, and not with
+/// `channel_configuration` `0` (which would need a program config element), failing with
+/// `Unsupported` otherwise.  SBR and PS are signalled hierarchically.
+///
+/// # Safety
+///
+/// `info` must point to a valid `AscInfo`, `out` must be valid for writes of `out_len` bytes,
+/// and `written` must be valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn asc_serialize(
+    info: *const AscInfo,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    if info.is_null() || out.is_null() || written.is_null() {
+        return Status::NullPointer;
+    }
+    let asc = match fixed_asc(&*info) {
+        Ok(asc) => asc,
+        Err(status) => return status,
+    };
+    match asc.write_to(slice::from_raw_parts_mut(out, out_len)) {
+        Some(len) => {
+            *written = len;
+            Status::Ok
+        }
+        None => Status::BufferTooSmall,
+    }
+}
+
+/// Parses the ADTS header at the start of the `len` bytes at `data`.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn adts_header_parse(data: *const u8, len: usize) -> AdtsHeaderResult {
+    if data.is_null() {
+        return AdtsHeaderResult {
+            status: Status::NullPointer,
+            header: AdtsHeaderInfo::default(),
+        };
+    }
+    match AdtsHeader::from_bytes(slice::from_raw_parts(data, len)) {
+        Ok(header) => AdtsHeaderResult {
+            status: Status::Ok,
+            header: adts_header_info(&header),
+        },
+        Err(e) => AdtsHeaderResult {
+            status: match e {
+                AdtsHeaderError::NotEnoughData(_) => Status::NotEnoughData,
+                _ => Status::InvalidData,
+            },
+            header: AdtsHeaderInfo::default(),
+        },
+    }
+}
+
+fn sampling_frequency_fields(sampling_frequency: SamplingFrequency) -> (u8, u32) {
+    match sampling_frequency {
+        SamplingFrequency::Index(i) => (u8::from(i), i.frequency().unwrap_or(0)),
+        SamplingFrequency::Explicit(f) => (SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE, f),
+    }
+}
+
+fn sampling_frequency_from_fields(index: u8, frequency: u32) -> Result<SamplingFrequency, Status> {
+    if index == SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE {
+        if frequency > 0xff_ffff {
+            return Err(Status::InvalidValue);
+        }
+        Ok(SamplingFrequency::Explicit(frequency))
+    } else {
+        SamplingFrequencyIndex::try_from(index)
+            .map(SamplingFrequency::Index)
+            .map_err(|_| Status::InvalidValue)
+    }
+}
+
+fn asc_info(asc: &AudioSpecificConfig) -> AscInfo {
+    let (sampling_frequency_index, sampling_frequency) =
+        sampling_frequency_fields(asc.sampling_frequency);
+    let (extension_sampling_frequency_index, extension_sampling_frequency) = asc
+        .extension
+        .as_ref()
+        .and_then(AudioExtension::sampling_frequency)
+        .map(sampling_frequency_fields)
+        .unwrap_or((0, 0));
+    AscInfo {
+        audio_object_type: u8::from(asc.audio_object_type),
+        sampling_frequency_index,
+        sampling_frequency,
+        channel_configuration: u8::from(asc.channel_configuration),
+        sbr_present: asc.extension.as_ref().is_some_and(|e| e.sbr_present()),
+        ps_present: asc.extension.as_ref().is_some_and(|e| e.ps_present()),
+        extension_sampling_frequency_index,
+        extension_sampling_frequency,
+        frame_length_flag: match asc.specific_config {
+            SpecificConfig::Ga(ref ga) => ga.frame_length_flag,
+            _ => false,
+        },
+    }
+}
+
+fn fixed_asc(info: &AscInfo) -> Result<FixedAudioSpecificConfig, Status> {
+    let audio_object_type =
+        AudioObjectType::try_from(info.audio_object_type).map_err(|_| Status::InvalidValue)?;
+    let channel_configuration = ChannelConfiguration::try_from(info.channel_configuration)
+        .map_err(|_| Status::InvalidValue)?;
+    if !is_ga(audio_object_type)
+        || channel_configuration == ChannelConfiguration::AOT_SPECIFIC_CONFIG
+    {
+        return Err(Status::Unsupported);
+    }
+    let extension = if info.sbr_present {
+        Some(AudioExtension::Hierarchical {
+            ps_present: info.ps_present,
+            sampling_frequency: sampling_frequency_from_fields(
+                info.extension_sampling_frequency_index,
+                info.extension_sampling_frequency,
+            )?,
+            channel_configuration: None,
+        })
+    } else {
+        None
+    };
+    Ok(FixedAudioSpecificConfig {
+        audio_object_type,
+        sampling_frequency: sampling_frequency_from_fields(
+            info.sampling_frequency_index,
+            info.sampling_frequency,
+        )?,
+        channel_configuration,
+        extension,
+        frame_length_flag: info.frame_length_flag,
+        core_coder_delay: None,
+        layer_nr: match audio_object_type {
+            AudioObjectType::AAC_SCALABLE | AudioObjectType::ER_AAC_SCALABLE => Some(0),
+            _ => None,
+        },
+        ga_extension: None,
+        ep_config: if has_ep_config(audio_object_type) {
+            Some(0)
+        } else {
+            None
+        },
+    })
+}
+
+fn adts_header_info(header: &AdtsHeader) -> AdtsHeaderInfo {
+    AdtsHeaderInfo {
+        mpeg_version: match header.mpeg_version {
+            MpegVersion::Mpeg2 => 2,
+            MpegVersion::Mpeg4 => 4,
+        },
+        protected: header.protected,
+        audio_object_type: u8::from(header.audio_object_type),
+        sampling_frequency_index: u8::from(header.sampling_frequency_index),
+        channel_configuration: u8::from(header.channel_configuration),
+        frame_length: header.frame_length,
+        buffer_fullness: header.buffer_fullness,
+        number_of_raw_data_blocks_in_frame: header.number_of_raw_data_blocks_in_frame,
+        crc: header.crc.unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asc_roundtrip() {
+        let data = [0xeb, 0x09, 0x88, 0x00];
+        let result = unsafe { asc_parse(data.as_ptr(), data.len()) };
+        assert_eq!(Status::Ok, result.status);
+        assert_eq!(2, result.asc.audio_object_type);
+        assert_eq!(24000, result.asc.sampling_frequency);
+        assert!(result.asc.sbr_present && result.asc.ps_present);
+        assert_eq!(48000, result.asc.extension_sampling_frequency);
+        let mut out = [0; 16];
+        let mut written = 0;
+        let status =
+            unsafe { asc_serialize(&result.asc, out.as_mut_ptr(), out.len(), &mut written) };
+        assert_eq!(Status::Ok, status);
+        assert_eq!(&data[..], &out[..written]);
+        let status = unsafe { asc_serialize(&result.asc, out.as_mut_ptr(), 2, &mut written) };
+        assert_eq!(Status::BufferTooSmall, status);
+        assert_eq!(
+            Status::NotEnoughData,
+            unsafe { asc_parse(data.as_ptr(), 1) }.status
+        );
+        assert_eq!(
+            Status::NullPointer,
+            unsafe { asc_parse(core::ptr::null(), 0) }.status
+        );
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(Status::Ok, aot_try_from_u8(2).status);
+        assert_eq!(Status::InvalidValue, aot_try_from_u8(31).status);
+        let data = [0xff, 0xf1, 0x50, 0x80, 0x02, 0x1f, 0xfc];
+        let result = unsafe { adts_header_parse(data.as_ptr(), data.len()) };
+        assert_eq!(Status::Ok, result.status);
+        assert_eq!(2, result.header.audio_object_type);
+        assert_eq!(16, result.header.frame_length);
+        assert_eq!(
+            Status::InvalidData,
+            unsafe { adts_header_parse([0; 7].as_ptr(), 7) }.status
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Conversion between channel configurations and the Windows speaker position bit mask, as
+//! used in the `dwChannelMask` field of `WAVEFORMATEXTENSIBLE`, so that decoded PCM can be
+//! labelled for WASAPI and similar APIs.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::channel_mask::*;
+//! assert_eq!(Some(0x3f), channel_mask(ChannelConfiguration::FIVE_ONE));
+//! let mask = SPEAKER_FRONT_LEFT | SPEAKER_FRONT_RIGHT;
+//! assert_eq!(Some(ChannelConfiguration::STEREO), from_channel_mask(mask));
+//! ```
+//!
+//! The mask describes only which positions are present; PCM channels are always interleaved in
+//! the order of the bits, which is generally not the order in which an AAC decoder outputs
+//! them.
+
+use crate::ChannelConfiguration;
+use core::convert::TryFrom;
+
+/// The `SPEAKER_*` position bits of `dwChannelMask`
+pub const SPEAKER_FRONT_LEFT: u32 = 0x1;
+pub const SPEAKER_FRONT_RIGHT: u32 = 0x2;
+pub const SPEAKER_FRONT_CENTER: u32 = 0x4;
+pub const SPEAKER_LOW_FREQUENCY: u32 = 0x8;
+pub const SPEAKER_BACK_LEFT: u32 = 0x10;
+pub const SPEAKER_BACK_RIGHT: u32 = 0x20;
+pub const SPEAKER_FRONT_LEFT_OF_CENTER: u32 = 0x40;
+pub const SPEAKER_FRONT_RIGHT_OF_CENTER: u32 = 0x80;
+pub const SPEAKER_BACK_CENTER: u32 = 0x100;
+pub const SPEAKER_SIDE_LEFT: u32 = 0x200;
+pub const SPEAKER_SIDE_RIGHT: u32 = 0x400;
+pub const SPEAKER_TOP_CENTER: u32 = 0x800;
+pub const SPEAKER_TOP_FRONT_LEFT: u32 = 0x1000;
+pub const SPEAKER_TOP_FRONT_CENTER: u32 = 0x2000;
+pub const SPEAKER_TOP_FRONT_RIGHT: u32 = 0x4000;
+pub const SPEAKER_TOP_BACK_LEFT: u32 = 0x8000;
+pub const SPEAKER_TOP_BACK_CENTER: u32 = 0x10000;
+pub const SPEAKER_TOP_BACK_RIGHT: u32 = 0x20000;
+
+const FRONT: u32 = SPEAKER_FRONT_LEFT | SPEAKER_FRONT_RIGHT;
+const FRONT_CENTER: u32 = FRONT | SPEAKER_FRONT_CENTER;
+const BACK: u32 = SPEAKER_BACK_LEFT | SPEAKER_BACK_RIGHT;
+const SIDE: u32 = SPEAKER_SIDE_LEFT | SPEAKER_SIDE_RIGHT;
+
+/// The speaker mask for the given channel configuration, or `None` for `0`, for reserved
+/// values, and for the 22.2 layout (which uses positions the mask can't represent).
+///
+/// The surround channels of the 5.0 and 5.1 layouts are given as the 'back' positions, as for
+/// `KSAUDIO_SPEAKER_5POINT1`.  In the 7.1 layout with rear surrounds, the surround channels
+/// are 'side' and the rear surrounds are 'back', as for `KSAUDIO_SPEAKER_7POINT1_SURROUND`.
+pub fn channel_mask(channel_configuration: ChannelConfiguration) -> Option<u32> {
+    Some(match channel_configuration {
+        ChannelConfiguration::MONO => SPEAKER_FRONT_CENTER,
+        ChannelConfiguration::STEREO => FRONT,
+        ChannelConfiguration::THREE => FRONT_CENTER,
+        ChannelConfiguration::FOUR => FRONT_CENTER | SPEAKER_BACK_CENTER,
+        ChannelConfiguration::FIVE => FRONT_CENTER | BACK,
+        ChannelConfiguration::FIVE_ONE => FRONT_CENTER | BACK | SPEAKER_LOW_FREQUENCY,
+        ChannelConfiguration::SEVEN_ONE => {
+            FRONT_CENTER
+                | SPEAKER_FRONT_LEFT_OF_CENTER
+                | SPEAKER_FRONT_RIGHT_OF_CENTER
+                | BACK
+                | SPEAKER_LOW_FREQUENCY
+        }
+        ChannelConfiguration::SIX_ONE => {
+            FRONT_CENTER | BACK | SPEAKER_BACK_CENTER | SPEAKER_LOW_FREQUENCY
+        }
+        ChannelConfiguration::SEVEN_ONE_BACK => FRONT_CENTER | SIDE | BACK | SPEAKER_LOW_FREQUENCY,
+        ChannelConfiguration::SEVEN_ONE_TOP_FRONT => {
+            FRONT_CENTER
+                | BACK
+                | SPEAKER_LOW_FREQUENCY
+                | SPEAKER_TOP_FRONT_LEFT
+                | SPEAKER_TOP_FRONT_RIGHT
+        }
+        _ => return None,
+    })
+}
+
+/// The channel configuration for the given speaker mask, or `None` if no configuration has
+/// that layout.  Masks giving the 5.0 or 5.1 surround channels as 'side' rather than 'back'
+/// positions are also accepted.
+pub fn from_channel_mask(mask: u32) -> Option<ChannelConfiguration> {
+    if mask & (BACK | SIDE) == SIDE && mask & !(FRONT_CENTER | SIDE | SPEAKER_LOW_FREQUENCY) == 0 {
+        return from_channel_mask(mask & !SIDE | BACK);
+    }
+    (1..=14)
+        .map(|c| ChannelConfiguration::try_from(c).unwrap())
+        .find(|conf| channel_mask(*conf) == Some(mask))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        for c in 0..=15 {
+            let conf = ChannelConfiguration::try_from(c).unwrap();
+            if let Some(mask) = channel_mask(conf) {
+                assert_eq!(Some(conf), from_channel_mask(mask));
+                assert_eq!(conf.channel_count(), Some(mask.count_ones()));
+            }
+        }
+        assert_eq!(
+            Some(ChannelConfiguration::FIVE_ONE),
+            from_channel_mask(FRONT_CENTER | SIDE | SPEAKER_LOW_FREQUENCY)
+        );
+        assert_eq!(None, from_channel_mask(FRONT | SIDE));
+        assert_eq!(None, channel_mask(ChannelConfiguration::TWENTY_TWO_TWO));
+    }
+}
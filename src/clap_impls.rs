@@ -0,0 +1,73 @@
+//! clap `ValueEnum` implementations, accepting the names of the constants in kebab-case (e.g.
+//! `aac-lc`), with the constants' descriptions as help text.
+
+use crate::{AudioObjectType, AudioProfileLevelIndication};
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+
+/// The kebab-case form of the given constant name
+fn arg_name(const_name: &str) -> String {
+    const_name.to_ascii_lowercase().replace('_', "-")
+}
+
+impl ValueEnum for AudioObjectType {
+    fn value_variants<'a>() -> &'a [Self] {
+        AudioObjectType::ALL
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        let value = PossibleValue::new(arg_name(self.name()?)).help(self.description()?);
+        Some(match *self {
+            AudioObjectType::SBR => value.alias("he-aac"),
+            AudioObjectType::PS => value.alias("he-aac-v2"),
+            _ => value,
+        })
+    }
+}
+
+impl ValueEnum for AudioProfileLevelIndication {
+    fn value_variants<'a>() -> &'a [Self] {
+        AudioProfileLevelIndication::DEFINED
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(arg_name(self.name()?)).help(self.description()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Ok(AudioObjectType::AAC_LC),
+            AudioObjectType::from_str("aac-lc", false)
+        );
+        assert_eq!(
+            Ok(AudioObjectType::SBR),
+            AudioObjectType::from_str("HE-AAC", true)
+        );
+        assert_eq!(
+            Ok(AudioObjectType::PS),
+            AudioObjectType::from_str("he-aac-v2", false)
+        );
+        assert!(AudioObjectType::from_str("aac", false).is_err());
+        assert_eq!(
+            Ok(AudioProfileLevelIndication::HE_AAC_V2_L2),
+            AudioProfileLevelIndication::from_str("he-aac-v2-l2", false)
+        );
+    }
+
+    #[test]
+    fn argument() {
+        let cmd = clap::Command::new("test")
+            .arg(clap::Arg::new("aot").value_parser(clap::value_parser!(AudioObjectType)));
+        let matches = cmd.try_get_matches_from(["test", "er-aac-ld"]).unwrap();
+        assert_eq!(
+            Some(&AudioObjectType::ER_AAC_LD),
+            matches.get_one::<AudioObjectType>("aot")
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Parsing of RFC 6381 `mp4a` codec strings, as found in HLS `CODECS` attributes, DASH `codecs`
+//! attributes and MIME type parameters.
+//!
+//! Formatting is provided by
+//! [`AudioObjectType::codec_string()`](../struct.AudioObjectType.html#method.codec_string) and
+//! [`AudioSpecificConfig::codec_string()`](../asc/struct.AudioSpecificConfig.html#method.codec_string).
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::codec_string::*;
+//! let codec: Mp4aCodec = "mp4a.40.02".parse().unwrap();
+//! assert_eq!(Some(AudioObjectType::AAC_LC), codec.audio_object_type);
+//! assert_eq!("mp4a.40.2", codec.to_string());
+//! ```
+
+use crate::{AudioObjectType, AudioObjectTypeError};
+use alloc::string::String;
+use alloc::string::ToString;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+/// The _object type indication_ value for MPEG-4 Audio (ISO/IEC 14496-3), which is the only
+/// value for which an audio object type is given in the codec string
+pub const OTI_MPEG4_AUDIO: u8 = 0x40;
+/// The _object type indication_ value for MPEG-2 AAC Main profile
+pub const OTI_MPEG2_AAC_MAIN: u8 = 0x66;
+/// The _object type indication_ value for MPEG-2 AAC LC profile
+pub const OTI_MPEG2_AAC_LC: u8 = 0x67;
+/// The _object type indication_ value for MPEG-2 AAC SSR profile
+pub const OTI_MPEG2_AAC_SSR: u8 = 0x68;
+/// The _object type indication_ value for MPEG-2 (13818-3) audio
+pub const OTI_MPEG2_AUDIO: u8 = 0x69;
+/// The _object type indication_ value for MPEG-1 (11172-3) audio
+pub const OTI_MPEG1_AUDIO: u8 = 0x6b;
+
+/// Errors produced when parsing a codec string
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum CodecStringError {
+    /// The codec string did not start with the `mp4a` sample entry code.
+    NotMp4a(String),
+    /// No object type indication followed the `mp4a` code.
+    MissingObjectTypeIndication,
+    /// The object type indication was not a hexadecimal byte value.
+    BadObjectTypeIndication(String),
+    /// The object type indication was `0x40`, but no audio object type followed it.
+    MissingAudioObjectType,
+    /// The audio object type was not a decimal number.
+    BadAudioObjectType(String),
+    /// The audio object type was a number that is not a valid audio object type.
+    InvalidAudioObjectType(AudioObjectTypeError),
+    /// An audio object type was given for an object type indication other than `0x40`.
+    UnexpectedAudioObjectType(u8),
+    /// Further `.`-separated components followed the audio object type.
+    TrailingData(String),
+}
+
+impl fmt::Display for CodecStringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecStringError::NotMp4a(s) => write!(f, "{:?} is not an mp4a codec string", s),
+            CodecStringError::MissingObjectTypeIndication => {
+                f.write_str("missing object type indication")
+            }
+            CodecStringError::BadObjectTypeIndication(s) => {
+                write!(f, "bad object type indication {:?}", s)
+            }
+            CodecStringError::MissingAudioObjectType => f.write_str("missing audio object type"),
+            CodecStringError::BadAudioObjectType(s) => write!(f, "bad audio object type {:?}", s),
+            CodecStringError::InvalidAudioObjectType(_) => f.write_str("invalid audio object type"),
+            CodecStringError::UnexpectedAudioObjectType(oti) => write!(
+                f,
+                "audio object type given for object type indication {:#04x}",
+                oti
+            ),
+            CodecStringError::TrailingData(s) => write!(f, "unexpected trailing data {:?}", s),
+        }
+    }
+}
+
+impl core::error::Error for CodecStringError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            CodecStringError::InvalidAudioObjectType(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed `mp4a` codec string: the _object type indication_ and, for MPEG-4 Audio, the
+/// audio object type.
+///
+/// Parsing tolerates upper-case letters and zero-padded audio object type values,
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::codec_string::*;
+/// assert_eq!(
+///     Mp4aCodec::mpeg4(AudioObjectType::AAC_LC),
+///     "MP4A.40.02".parse().unwrap()
+/// );
+/// let mp3: Mp4aCodec = "mp4a.6b".parse().unwrap();
+/// assert_eq!(OTI_MPEG1_AUDIO, mp3.object_type_indication);
+/// assert_eq!(None, mp3.audio_object_type);
+/// assert_eq!("mp4a.6B", mp3.to_string());
+/// ```
+///
+/// and produces structured errors for malformed values,
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::codec_string::*;
+/// assert_eq!(
+///     Err(CodecStringError::InvalidAudioObjectType(AudioObjectTypeError::EscapeValue)),
+///     "mp4a.40.31".parse::<Mp4aCodec>()
+/// );
+/// ```
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct Mp4aCodec {
+    pub object_type_indication: u8,
+    /// The audio object type, present only when `object_type_indication` is `0x40`
+    pub audio_object_type: Option<AudioObjectType>,
+}
+
+impl Mp4aCodec {
+    /// The codec for the given MPEG-4 Audio object type.
+    pub fn mpeg4(audio_object_type: AudioObjectType) -> Mp4aCodec {
+        Mp4aCodec {
+            object_type_indication: OTI_MPEG4_AUDIO,
+            audio_object_type: Some(audio_object_type),
+        }
+    }
+}
+
+impl FromStr for Mp4aCodec {
+    type Err = CodecStringError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('.');
+        let fourcc = parts.next().unwrap_or("");
+        if !fourcc.eq_ignore_ascii_case("mp4a") {
+            return Err(CodecStringError::NotMp4a(fourcc.to_string()));
+        }
+        let oti = parts
+            .next()
+            .ok_or(CodecStringError::MissingObjectTypeIndication)?;
+        let object_type_indication = if oti.len() == 2 {
+            u8::from_str_radix(oti, 16).ok()
+        } else {
+            None
+        }
+        .ok_or_else(|| CodecStringError::BadObjectTypeIndication(oti.to_string()))?;
+        let audio_object_type = match parts.next() {
+            Some(aot) => {
+                if object_type_indication != OTI_MPEG4_AUDIO {
+                    return Err(CodecStringError::UnexpectedAudioObjectType(
+                        object_type_indication,
+                    ));
+                }
+                if aot.is_empty() || !aot.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(CodecStringError::BadAudioObjectType(aot.to_string()));
+                }
+                // given only digits, parsing can fail only because the value overflows
+                let value = aot.parse::<u8>().map_err(|_| {
+                    CodecStringError::InvalidAudioObjectType(AudioObjectTypeError::OutOfRange)
+                })?;
+                Some(
+                    AudioObjectType::try_from(value)
+                        .map_err(CodecStringError::InvalidAudioObjectType)?,
+                )
+            }
+            None if object_type_indication == OTI_MPEG4_AUDIO => {
+                return Err(CodecStringError::MissingAudioObjectType)
+            }
+            None => None,
+        };
+        if let Some(rest) = parts.next() {
+            return Err(CodecStringError::TrailingData(rest.to_string()));
+        }
+        Ok(Mp4aCodec {
+            object_type_indication,
+            audio_object_type,
+        })
+    }
+}
+
+impl fmt::Display for Mp4aCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mp4a.{:02X}", self.object_type_indication)?;
+        if let Some(aot) = self.audio_object_type {
+            write!(f, ".{}", u8::from(aot))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Ok(Mp4aCodec::mpeg4(AudioObjectType::LAYER3)),
+            "mp4a.40.34".parse()
+        );
+        assert_eq!(
+            Ok(Mp4aCodec::mpeg4(AudioObjectType::SBR)),
+            " mp4a.40.005 ".parse()
+        );
+        assert_eq!(
+            Err(CodecStringError::NotMp4a("avc1".to_string())),
+            "avc1.64001f".parse::<Mp4aCodec>()
+        );
+        assert_eq!(
+            Err(CodecStringError::MissingAudioObjectType),
+            "mp4a.40".parse::<Mp4aCodec>()
+        );
+        assert_eq!(
+            Err(CodecStringError::BadObjectTypeIndication("4".to_string())),
+            "mp4a.4.2".parse::<Mp4aCodec>()
+        );
+        assert_eq!(
+            Err(CodecStringError::BadAudioObjectType("-2".to_string())),
+            "mp4a.40.-2".parse::<Mp4aCodec>()
+        );
+        assert_eq!(
+            Err(CodecStringError::InvalidAudioObjectType(
+                AudioObjectTypeError::TooLarge(200)
+            )),
+            "mp4a.40.200".parse::<Mp4aCodec>()
+        );
+        assert_eq!(
+            Err(CodecStringError::InvalidAudioObjectType(
+                AudioObjectTypeError::OutOfRange
+            )),
+            "mp4a.40.256".parse::<Mp4aCodec>()
+        );
+        assert_eq!(
+            Err(CodecStringError::UnexpectedAudioObjectType(0x67)),
+            "mp4a.67.2".parse::<Mp4aCodec>()
+        );
+        assert_eq!(
+            Err(CodecStringError::TrailingData("1".to_string())),
+            "mp4a.40.2.1".parse::<Mp4aCodec>()
+        );
+    }
+
+    #[test]
+    fn display_roundtrip() {
+        for s in &["mp4a.40.2", "mp4a.40.42", "mp4a.69", "mp4a.6B"] {
+            assert_eq!(*s, s.parse::<Mp4aCodec>().unwrap().to_string());
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Helpers for describing MPEG-4 audio in MPEG-DASH manifests.
+//!
+//! xHE-AAC (i.e. the `USAC` audio object type) is signalled in an MPD using the `@codecs`
+//! value `"mp4a.40.42"`, on an `AdaptationSet` or `Representation` whose `@mimeType` is
+//! `audio/mp4`.  Unlike some other codecs, no additional `@profiles` value is needed to
+//! identify xHE-AAC: the MPD `@profiles` attribute identifies DASH profiles and interoperability
+//! points (e.g. `urn:mpeg:dash:profile:isoff-live:2011`) and is not affected by the choice of
+//! audio codec.
+//!
+//! The channel layout of a `Representation` is described using `AudioChannelConfiguration`
+//! elements, the values for which can be produced by
+//! [`AudioChannelConfiguration`](enum.AudioChannelConfiguration.html).
+
+use crate::asc::AudioSpecificConfig;
+use crate::codec_string::{CodecStringError, Mp4aCodec};
+use crate::{AudioObjectType, ChannelConfiguration};
+use alloc::string::String;
+use alloc::string::ToString;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The `@codecs` value for xHE-AAC (MPEG-D USAC) content
+pub const USAC_CODECS: &str = "mp4a.40.42";
+
+/// The `@schemeIdUri` of the ISO/IEC 23003-3 `AudioChannelConfiguration` scheme, whose
+/// `@value` is the number of channels
+pub const CHANNEL_COUNT_SCHEME: &str = "urn:mpeg:dash:23003:3:audio_channel_configuration:2011";
+/// The `@schemeIdUri` of the ISO/IEC 23001-8 (CICP) `AudioChannelConfiguration` scheme, whose
+/// `@value` is a `ChannelConfiguration` code point
+pub const CICP_SCHEME: &str = "urn:mpeg:mpegB:cicp:ChannelConfiguration";
+
+/// Problems parsing an `AudioChannelConfiguration` element
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum AudioChannelConfigurationError {
+    /// The `@schemeIdUri` was not one of the schemes supported here.
+    UnknownScheme(String),
+    /// The `@value` was not valid for the scheme.
+    BadValue(String),
+}
+
+impl fmt::Display for AudioChannelConfigurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioChannelConfigurationError::UnknownScheme(s) => write!(f, "unknown scheme {:?}", s),
+            AudioChannelConfigurationError::BadValue(v) => {
+                write!(f, "value {:?} not valid for the scheme", v)
+            }
+        }
+    }
+}
+
+impl core::error::Error for AudioChannelConfigurationError {}
+
+/// The value of an MPD `AudioChannelConfiguration` element, in either of the MPEG-defined
+/// schemes.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::dash::*;
+/// let conf = AudioChannelConfiguration::cicp(ChannelConfiguration::FIVE_ONE).unwrap();
+/// assert_eq!("urn:mpeg:mpegB:cicp:ChannelConfiguration", conf.scheme_id_uri());
+/// assert_eq!("6", conf.value());
+/// let conf = AudioChannelConfiguration::channel_count(ChannelConfiguration::SEVEN_ONE).unwrap();
+/// assert_eq!(CHANNEL_COUNT_SCHEME, conf.scheme_id_uri());
+/// assert_eq!("8", conf.value());
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum AudioChannelConfiguration {
+    /// The ISO/IEC 23003-3 scheme, giving the number of channels (including any LFE channels)
+    ChannelCount(u32),
+    /// The CICP scheme, giving a `ChannelConfiguration` code point from ISO/IEC 23001-8
+    Cicp(u8),
+}
+
+impl AudioChannelConfiguration {
+    /// The channel count scheme value for the given configuration, or `None` if the
+    /// configuration implies no channel count (i.e. it is `0` or reserved).
+    pub fn channel_count(
+        channel_configuration: ChannelConfiguration,
+    ) -> Option<AudioChannelConfiguration> {
+        channel_configuration
+            .channel_count()
+            .map(AudioChannelConfiguration::ChannelCount)
+    }
+
+    /// The CICP scheme value for the given configuration, or `None` if the configuration is `0`
+    /// or reserved.
+    ///
+    /// The CICP code points are those of the MPEG-4 channel configuration table wherever that
+    /// table defines a layout, so the value is that of the configuration.  Note however that
+    /// CICP defines layouts for `8` to `10`, which MPEG-4 audio reserves, and so a CICP value
+    /// is not necessarily a valid channel configuration.
+    pub fn cicp(channel_configuration: ChannelConfiguration) -> Option<AudioChannelConfiguration> {
+        channel_configuration
+            .channel_count()
+            .map(|_| AudioChannelConfiguration::Cicp(channel_configuration.into()))
+    }
+
+    /// Parses the `@schemeIdUri` and `@value` attributes of an `AudioChannelConfiguration`
+    /// element.
+    pub fn parse(
+        scheme_id_uri: &str,
+        value: &str,
+    ) -> Result<AudioChannelConfiguration, AudioChannelConfigurationError> {
+        let bad_value = || AudioChannelConfigurationError::BadValue(value.to_string());
+        match scheme_id_uri {
+            CHANNEL_COUNT_SCHEME => value
+                .parse()
+                .map(AudioChannelConfiguration::ChannelCount)
+                .map_err(|_| bad_value()),
+            CICP_SCHEME => value
+                .parse()
+                .map(AudioChannelConfiguration::Cicp)
+                .map_err(|_| bad_value()),
+            _ => Err(AudioChannelConfigurationError::UnknownScheme(
+                scheme_id_uri.to_string(),
+            )),
+        }
+    }
+
+    pub fn scheme_id_uri(&self) -> &'static str {
+        match self {
+            AudioChannelConfiguration::ChannelCount(_) => CHANNEL_COUNT_SCHEME,
+            AudioChannelConfiguration::Cicp(_) => CICP_SCHEME,
+        }
+    }
+
+    pub fn value(&self) -> String {
+        match self {
+            AudioChannelConfiguration::ChannelCount(n) => n.to_string(),
+            AudioChannelConfiguration::Cicp(n) => n.to_string(),
+        }
+    }
+
+    /// The MPEG-4 channel configuration with the same layout as this CICP value, or `None` for
+    /// channel counts and for CICP values with no MPEG-4 equivalent.
+    pub fn channel_configuration(&self) -> Option<ChannelConfiguration> {
+        match self {
+            AudioChannelConfiguration::Cicp(n) => ChannelConfiguration::try_from(*n)
+                .ok()
+                .filter(|conf| conf.channel_count().is_some()),
+            AudioChannelConfiguration::ChannelCount(_) => None,
+        }
+    }
+}
+
+/// Problems found when generating or validating xHE-AAC signalling
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum UsacSignallingError {
+    /// The codec string could not be parsed.
+    CodecString(CodecStringError),
+    /// The object type indication was not `0x40` (a common mistake is `"mp4a.42"`, putting the
+    /// object type in place of the object type indication).
+    NotMpeg4Audio(u8),
+    /// The configuration or codec string was for an object type other than `USAC`.
+    NotUsac(AudioObjectType),
+    /// The codec string identifies USAC, but not in the canonical form `"mp4a.40.42"`, so
+    /// may not be recognised by all clients.
+    NonCanonical(String),
+}
+
+impl fmt::Display for UsacSignallingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UsacSignallingError::CodecString(_) => f.write_str("invalid codec string"),
+            UsacSignallingError::NotMpeg4Audio(oti) => write!(
+                f,
+                "object type indication {:#04x} is not MPEG-4 audio (0x40)",
+                oti
+            ),
+            UsacSignallingError::NotUsac(aot) => {
+                write!(f, "object type {} is not USAC (42)", u8::from(*aot))
+            }
+            UsacSignallingError::NonCanonical(s) => {
+                write!(
+                    f,
+                    "codec string {:?} is not the canonical \"mp4a.40.42\"",
+                    s
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for UsacSignallingError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            UsacSignallingError::CodecString(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Produces the `@codecs` value for the given xHE-AAC configuration, failing if the
+/// configuration is not for the `USAC` object type.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::asc::*;
+/// # use mpeg4_audio_const::dash::*;
+/// let asc = AudioSpecificConfig::from_bytes(&[0xf9, 0x46, 0x40, 0x00]).unwrap();
+/// assert_eq!(Ok("mp4a.40.42"), usac_codecs(&asc));
+/// let aac = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+/// assert_eq!(Err(UsacSignallingError::NotUsac(AudioObjectType::AAC_LC)), usac_codecs(&aac));
+/// ```
+pub fn usac_codecs(asc: &AudioSpecificConfig) -> Result<&'static str, UsacSignallingError> {
+    if asc.audio_object_type == AudioObjectType::USAC {
+        Ok(USAC_CODECS)
+    } else {
+        Err(UsacSignallingError::NotUsac(asc.audio_object_type))
+    }
+}
+
+/// Checks that the given `@codecs` value correctly signals xHE-AAC.
+///
+/// Unlike the lenient [`Mp4aCodec`](../codec_string/struct.Mp4aCodec.html) parser, this
+/// requires the canonical form, since it is intended for checking the output of packagers.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::dash::*;
+/// assert_eq!(Ok(()), validate_usac_codecs("mp4a.40.42"));
+/// assert_eq!(
+///     Err(UsacSignallingError::NotMpeg4Audio(0x42)),
+///     validate_usac_codecs("mp4a.42")
+/// );
+/// assert_eq!(
+///     Err(UsacSignallingError::NonCanonical("MP4A.40.042".to_string())),
+///     validate_usac_codecs("MP4A.40.042")
+/// );
+/// ```
+pub fn validate_usac_codecs(codecs: &str) -> Result<(), UsacSignallingError> {
+    let codec: Mp4aCodec = codecs.parse().map_err(UsacSignallingError::CodecString)?;
+    match codec.audio_object_type {
+        Some(AudioObjectType::USAC) => (),
+        Some(other) => return Err(UsacSignallingError::NotUsac(other)),
+        // only an OTI of 0x40 is accompanied by an object type
+        None => {
+            return Err(UsacSignallingError::NotMpeg4Audio(
+                codec.object_type_indication,
+            ))
+        }
+    }
+    if codecs != USAC_CODECS {
+        return Err(UsacSignallingError::NonCanonical(codecs.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        assert_eq!(
+            Err(UsacSignallingError::NotUsac(AudioObjectType::PS)),
+            validate_usac_codecs("mp4a.40.29")
+        );
+        assert_eq!(
+            Err(UsacSignallingError::CodecString(
+                CodecStringError::MissingAudioObjectType
+            )),
+            validate_usac_codecs("mp4a.40")
+        );
+        assert_eq!(
+            Err(UsacSignallingError::NonCanonical(" mp4a.40.42".to_string())),
+            validate_usac_codecs(" mp4a.40.42")
+        );
+    }
+
+    #[test]
+    fn audio_channel_configuration() {
+        for c in 0..=15 {
+            let conf = ChannelConfiguration::try_from(c).unwrap();
+            if let Some(cicp) = AudioChannelConfiguration::cicp(conf) {
+                let parsed = AudioChannelConfiguration::parse(cicp.scheme_id_uri(), &cicp.value());
+                assert_eq!(Ok(cicp), parsed);
+                assert_eq!(Some(conf), cicp.channel_configuration());
+            }
+        }
+        assert_eq!(
+            None,
+            AudioChannelConfiguration::cicp(ChannelConfiguration::AOT_SPECIFIC_CONFIG)
+        );
+        assert_eq!(
+            None,
+            AudioChannelConfiguration::Cicp(9).channel_configuration()
+        );
+        assert_eq!(
+            Ok(AudioChannelConfiguration::ChannelCount(2)),
+            AudioChannelConfiguration::parse(CHANNEL_COUNT_SCHEME, "2")
+        );
+        assert_eq!(
+            Err(AudioChannelConfigurationError::BadValue("F801".to_string())),
+            AudioChannelConfiguration::parse(CHANNEL_COUNT_SCHEME, "F801")
+        );
+        assert!(AudioChannelConfiguration::parse("urn:example", "2").is_err());
+    }
+}
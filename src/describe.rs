@@ -0,0 +1,78 @@
+//! Support for the multi-line, human-readable descriptions produced by the `describe()` methods
+//! of the parsed structures.
+
+use core::fmt;
+
+/// Writes `name: value` lines, indented according to the nesting of sections.
+pub(crate) struct Describer<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    indent: usize,
+}
+
+impl<'a, 'b> Describer<'a, 'b> {
+    pub fn field(&mut self, name: &str, value: impl fmt::Display) -> fmt::Result {
+        writeln!(
+            self.f,
+            "{:indent$}{}: {}",
+            "",
+            name,
+            value,
+            indent = self.indent
+        )
+    }
+
+    /// Writes the section's title, followed by the lines it writes indented one more level.
+    pub fn section(
+        &mut self,
+        title: impl fmt::Display,
+        body: impl FnOnce(&mut Describer<'_, '_>) -> fmt::Result,
+    ) -> fmt::Result {
+        writeln!(self.f, "{:indent$}{}", "", title, indent = self.indent)?;
+        self.indent += 2;
+        let result = body(self);
+        self.indent -= 2;
+        result
+    }
+}
+
+/// A structure having a description.
+pub(crate) trait Describe {
+    /// Writes the description, starting with a line giving the name of the structure.
+    fn describe_to(&self, d: &mut Describer<'_, '_>) -> fmt::Result;
+}
+
+/// Formats a description of the wrapped structure.
+pub(crate) struct Description<'a, T>(pub &'a T);
+
+impl<T: Describe> fmt::Display for Description<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.describe_to(&mut Describer { f, indent: 0 })
+    }
+}
+
+/// Formats one of the value types as its numeric value followed by its name (i.e. its
+/// alternate `Display` form), or just the numeric value without the `names` feature.
+pub(crate) struct Value<T>(pub T);
+
+#[cfg(feature = "names")]
+impl<T: fmt::Display> fmt::Display for Value<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.0)
+    }
+}
+
+#[cfg(not(feature = "names"))]
+impl<T: Copy + Into<u8>> fmt::Display for Value<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.into())
+    }
+}
+
+/// Formats a flag as `yes` or `no`.
+pub(crate) fn yes_no(flag: bool) -> &'static str {
+    if flag {
+        "yes"
+    } else {
+        "no"
+    }
+}
@@ -0,0 +1,386 @@
+//! Parsing of the MPEG-4 Systems (ISO/IEC 14496-1) descriptors found in the `esds` box of an
+//! MP4 audio sample entry, which carry the `AudioSpecificConfig` as their
+//! _DecoderSpecificInfo_.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::esds;
+//! let esds_body = [
+//!     0x00, 0x00, 0x00, 0x00, // version and flags
+//!     0x03, 0x19, 0x00, 0x01, 0x00, // ES_Descriptor
+//!     0x04, 0x11, 0x40, 0x15, 0x00, 0x00, 0x00, // DecoderConfigDescriptor
+//!     0x00, 0x01, 0xf4, 0x00, 0x00, 0x01, 0xf4, 0x00,
+//!     0x05, 0x02, 0x12, 0x10, // DecoderSpecificInfo
+//!     0x06, 0x01, 0x02, // SLConfigDescriptor
+//! ];
+//! let asc = esds::audio_specific_config(&esds_body).unwrap();
+//! assert_eq!(AudioObjectType::AAC_LC, asc.audio_object_type);
+//! ```
+//!
+//! The descriptors can also be produced, for muxers writing an `esds` box,
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::esds::*;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! let mut dcd = DecoderConfigDescriptor::new(&asc);
+//! dcd.max_bitrate = 128000;
+//! dcd.avg_bitrate = 128000;
+//! let body = esds_body(1, &dcd);
+//! assert_eq!(dcd, decoder_config_descriptor(&body).unwrap());
+//! ```
+
+use crate::asc::{AscError, AudioSpecificConfig};
+use crate::bits::{BitReader, EndOfData};
+use crate::codec_string::OTI_MPEG4_AUDIO;
+use crate::trace::TraceEntry;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The tag of the `ES_Descriptor`
+pub const ES_DESCR_TAG: u8 = 0x03;
+/// The tag of the `DecoderConfigDescriptor`
+pub const DECODER_CONFIG_DESCR_TAG: u8 = 0x04;
+/// The tag of the `DecoderSpecificInfo`
+pub const DEC_SPECIFIC_INFO_TAG: u8 = 0x05;
+/// The tag of the `SLConfigDescriptor`
+pub const SL_CONFIG_DESCR_TAG: u8 = 0x06;
+
+/// The `streamType` value for audio streams
+pub const STREAM_TYPE_AUDIO: u8 = 0x05;
+/// The `predefined` value of the `SLConfigDescriptor` reserved for use in MP4 files
+pub const SL_CONFIG_PREDEFINED_MP4: u8 = 0x02;
+
+/// Errors that may be produced while parsing `esds` descriptors
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum EsdsError {
+    /// The data ended before the end of a descriptor, while reading the given element.
+    NotEnoughData(TraceEntry),
+    /// The `esds` box had a version other than `0`.
+    UnsupportedVersion(u8),
+    /// A descriptor had a different tag to the one required at that position.
+    UnexpectedTag { expected: u8, actual: u8 },
+    /// A descriptor's size field used more than the permitted four bytes.
+    BadSize,
+    /// The `DecoderConfigDescriptor` contained no `DecoderSpecificInfo`.
+    MissingDecoderSpecificInfo,
+    /// The `DecoderSpecificInfo` did not hold a valid `AudioSpecificConfig`.
+    Asc(AscError),
+}
+
+impl fmt::Display for EsdsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EsdsError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            EsdsError::UnsupportedVersion(v) => write!(f, "unsupported esds version {}", v),
+            EsdsError::UnexpectedTag { expected, actual } => write!(
+                f,
+                "unexpected descriptor tag {:#04x}, expected {:#04x}",
+                actual, expected
+            ),
+            EsdsError::BadSize => f.write_str("descriptor size field longer than four bytes"),
+            EsdsError::MissingDecoderSpecificInfo => f.write_str("no DecoderSpecificInfo"),
+            EsdsError::Asc(_) => f.write_str("invalid AudioSpecificConfig"),
+        }
+    }
+}
+
+impl core::error::Error for EsdsError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            EsdsError::Asc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EndOfData> for EsdsError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        EsdsError::NotEnoughData(at)
+    }
+}
+
+/// A parsed `DecoderConfigDescriptor`
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct DecoderConfigDescriptor {
+    /// The _object type indication_, e.g.
+    /// [`OTI_MPEG4_AUDIO`](../codec_string/constant.OTI_MPEG4_AUDIO.html)
+    pub object_type_indication: u8,
+    /// The 6-bit `streamType`, which is `0x05` for audio
+    pub stream_type: u8,
+    pub up_stream: bool,
+    /// The 24-bit `bufferSizeDB`, in bytes
+    pub buffer_size_db: u32,
+    pub max_bitrate: u32,
+    pub avg_bitrate: u32,
+    /// The payload of the `DecoderSpecificInfo`, if present
+    pub decoder_specific_info: Option<Vec<u8>>,
+}
+
+impl DecoderConfigDescriptor {
+    /// A descriptor for the given configuration, with the MPEG-4 Audio object type indication
+    /// and the configuration as its _DecoderSpecificInfo_.  The buffer size and bitrate fields
+    /// are zero, and should be set by the caller where known.
+    pub fn new(asc: &AudioSpecificConfig) -> DecoderConfigDescriptor {
+        DecoderConfigDescriptor {
+            object_type_indication: OTI_MPEG4_AUDIO,
+            stream_type: STREAM_TYPE_AUDIO,
+            up_stream: false,
+            buffer_size_db: 0,
+            max_bitrate: 0,
+            avg_bitrate: 0,
+            decoder_specific_info: Some(decoder_specific_info_bytes(asc)),
+        }
+    }
+
+    /// Parses a `DecoderConfigDescriptor` (including its tag and size) from the start of the
+    /// given buffer.
+    pub fn from_bytes(buf: &[u8]) -> Result<DecoderConfigDescriptor, EsdsError> {
+        Self::read(&mut BitReader::new(buf))
+    }
+
+    fn read(r: &mut BitReader<'_>) -> Result<DecoderConfigDescriptor, EsdsError> {
+        read_descriptor(
+            r,
+            DECODER_CONFIG_DESCR_TAG,
+            "DecoderConfigDescriptor",
+            |r| {
+                let object_type_indication = r.named("objectTypeIndication").read_u8(8)?;
+                let stream_type = r.named("streamType").read_u8(6)?;
+                let up_stream = r.named("upStream").read_bool()?;
+                r.named("reserved").skip(1)?;
+                let buffer_size_db = r.named("bufferSizeDB").read(24)?;
+                let max_bitrate = r.named("maxBitrate").read(32)?;
+                let avg_bitrate = r.named("avgBitrate").read(32)?;
+                let mut decoder_specific_info = None;
+                while r.remaining() > 0 && decoder_specific_info.is_none() {
+                    let (tag, size) = read_descriptor_header(r)?;
+                    if tag == DEC_SPECIFIC_INFO_TAG {
+                        decoder_specific_info =
+                            Some(r.named("DecoderSpecificInfo").read_bits(size * 8)?);
+                    } else {
+                        r.named("descriptor").skip(size * 8)?;
+                    }
+                }
+                Ok(DecoderConfigDescriptor {
+                    object_type_indication,
+                    stream_type,
+                    up_stream,
+                    buffer_size_db,
+                    max_bitrate,
+                    avg_bitrate,
+                    decoder_specific_info,
+                })
+            },
+        )
+    }
+
+    /// Serializes the descriptor, including its tag and size, and any _DecoderSpecificInfo_.
+    /// The `stream_type` and `buffer_size_db` values are truncated to the widths of their
+    /// fields.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = vec![
+            self.object_type_indication,
+            (self.stream_type << 2) | if self.up_stream { 0b10 } else { 0 } | 0b1,
+        ];
+        body.extend_from_slice(&self.buffer_size_db.to_be_bytes()[1..]);
+        body.extend_from_slice(&self.max_bitrate.to_be_bytes());
+        body.extend_from_slice(&self.avg_bitrate.to_be_bytes());
+        if let Some(ref dsi) = self.decoder_specific_info {
+            write_descriptor(&mut body, DEC_SPECIFIC_INFO_TAG, dsi);
+        }
+        let mut buf = vec![];
+        write_descriptor(&mut buf, DECODER_CONFIG_DESCR_TAG, &body);
+        buf
+    }
+}
+
+/// The _DecoderSpecificInfo_ payload for the given configuration (i.e. the serialized
+/// `AudioSpecificConfig`).
+pub fn decoder_specific_info_bytes(asc: &AudioSpecificConfig) -> Vec<u8> {
+    asc.to_bytes()
+}
+
+/// Produces the body of an `esds` box (i.e. the data following the box header, starting with
+/// the version and flags) holding an `ES_Descriptor` with the given `ES_ID` (which is
+/// conventionally `0` or the track ID), the given `DecoderConfigDescriptor`, and an
+/// `SLConfigDescriptor` with the predefined value used in MP4 files.
+pub fn esds_body(es_id: u16, decoder_config: &DecoderConfigDescriptor) -> Vec<u8> {
+    let mut es = es_id.to_be_bytes().to_vec();
+    // no dependency, URL or OCR stream, and zero priority
+    es.push(0);
+    es.extend_from_slice(&decoder_config.to_bytes());
+    write_descriptor(&mut es, SL_CONFIG_DESCR_TAG, &[SL_CONFIG_PREDEFINED_MP4]);
+    let mut buf = vec![0, 0, 0, 0];
+    write_descriptor(&mut buf, ES_DESCR_TAG, &es);
+    buf
+}
+
+/// Parses the `DecoderConfigDescriptor` from the body of an `esds` box (i.e. the data
+/// following the box header, starting with the version and flags).
+pub fn decoder_config_descriptor(esds_body: &[u8]) -> Result<DecoderConfigDescriptor, EsdsError> {
+    let mut r = BitReader::new(esds_body);
+    let version = r.named("version").read_u8(8)?;
+    if version != 0 {
+        return Err(EsdsError::UnsupportedVersion(version));
+    }
+    r.named("flags").skip(24)?;
+    read_descriptor(&mut r, ES_DESCR_TAG, "ES_Descriptor", |r| {
+        r.named("ES_ID").skip(16)?;
+        let stream_dependence_flag = r.named("streamDependenceFlag").read_bool()?;
+        let url_flag = r.named("URL_Flag").read_bool()?;
+        let ocr_stream_flag = r.named("OCRstreamFlag").read_bool()?;
+        r.named("streamPriority").skip(5)?;
+        if stream_dependence_flag {
+            r.named("dependsOn_ES_ID").skip(16)?;
+        }
+        if url_flag {
+            let url_length = r.named("URLlength").read(8)? as usize;
+            r.named("URLstring").skip(url_length * 8)?;
+        }
+        if ocr_stream_flag {
+            r.named("OCR_ES_Id").skip(16)?;
+        }
+        DecoderConfigDescriptor::read(r)
+    })
+}
+
+/// Parses the `AudioSpecificConfig` carried as the _DecoderSpecificInfo_ within the body of
+/// an `esds` box (i.e. the data following the box header, starting with the version and
+/// flags).
+pub fn audio_specific_config(esds_body: &[u8]) -> Result<AudioSpecificConfig, EsdsError> {
+    let dsi = decoder_config_descriptor(esds_body)?
+        .decoder_specific_info
+        .ok_or(EsdsError::MissingDecoderSpecificInfo)?;
+    AudioSpecificConfig::from_bytes(&dsi).map_err(EsdsError::Asc)
+}
+
+/// Appends a descriptor with the given tag and body, using the fewest size bytes possible.
+fn write_descriptor(buf: &mut Vec<u8>, tag: u8, body: &[u8]) {
+    buf.push(tag);
+    let size = body.len();
+    let size_bytes = (1..4).find(|n| size < 1 << (7 * n)).unwrap_or(4);
+    for i in (0..size_bytes).rev() {
+        let more = if i > 0 { 0x80 } else { 0 };
+        buf.push(((size >> (7 * i)) & 0x7f) as u8 | more);
+    }
+    buf.extend_from_slice(body);
+}
+
+/// Reads a descriptor having the given tag and name, reading its body with the given function,
+/// which sees the end of the data at the end of the descriptor.
+fn read_descriptor<T>(
+    r: &mut BitReader<'_>,
+    expected: u8,
+    name: &'static str,
+    read: impl FnOnce(&mut BitReader<'_>) -> Result<T, EsdsError>,
+) -> Result<T, EsdsError> {
+    let (tag, size) = read_descriptor_header(r)?;
+    if tag != expected {
+        return Err(EsdsError::UnexpectedTag {
+            expected,
+            actual: tag,
+        });
+    }
+    let start = r.position();
+    let data = r.named(name).read_bits(size * 8)?;
+    r.nested(&data, size * 8, start, read)
+}
+
+/// Reads a descriptor's tag and _expandable_ size (one to four bytes, each contributing 7 bits
+/// with the top bit set if another byte follows), returning the tag and the size in bytes.
+fn read_descriptor_header(r: &mut BitReader<'_>) -> Result<(u8, usize), EsdsError> {
+    let tag = r.named("tag").read_u8(8)?;
+    let mut size = 0usize;
+    for _ in 0..4 {
+        let b = r.named("sizeOfInstance").read_u8(8)?;
+        size = (size << 7) | usize::from(b & 0x7f);
+        if b & 0x80 == 0 {
+            return Ok((tag, size));
+        }
+    }
+    Err(EsdsError::BadSize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_sizes_and_flags() {
+        // ES_Descriptor with 4-byte sizes (as written by some muxers), and dependsOn_ES_ID and
+        // URL fields
+        let esds_body = [
+            0x00, 0x00, 0x00, 0x00, // version and flags
+            0x03, 0x80, 0x80, 0x80, 0x26, 0x00, 0x01, 0xc0, 0x00, 0x02, 0x01, b'x', 0x04, 0x80,
+            0x80, 0x80, 0x14, 0x40, 0x15, 0x00, 0x00, 0x00, 0x00, 0x01, 0xf4, 0x00, 0x00, 0x01,
+            0xf4, 0x00, 0x05, 0x80, 0x80, 0x80, 0x02, 0x11, 0x90, 0x06, 0x80, 0x80, 0x80, 0x01,
+            0x02,
+        ];
+        let dcd = decoder_config_descriptor(&esds_body).unwrap();
+        assert_eq!(0x40, dcd.object_type_indication);
+        assert_eq!(0x05, dcd.stream_type);
+        assert_eq!(128000, dcd.avg_bitrate);
+        assert_eq!(Some(vec![0x11, 0x90]), dcd.decoder_specific_info);
+        assert!(audio_specific_config(&esds_body).is_ok());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(EsdsError::UnexpectedTag {
+                expected: ES_DESCR_TAG,
+                actual: DECODER_CONFIG_DESCR_TAG
+            }),
+            audio_specific_config(&[0, 0, 0, 0, 0x04, 0x00])
+        );
+        assert_eq!(
+            Err(EsdsError::BadSize),
+            audio_specific_config(&[0, 0, 0, 0, 0x03, 0x80, 0x80, 0x80, 0x80, 0x00])
+        );
+        assert_eq!(
+            Err(EsdsError::NotEnoughData(TraceEntry {
+                bit_offset: 48,
+                bit_length: 200,
+                field: "ES_Descriptor",
+                value: None,
+            })),
+            audio_specific_config(&[0, 0, 0, 0, 0x03, 0x19, 0x00, 0x01])
+        );
+        // DecoderConfigDescriptor without DecoderSpecificInfo
+        let esds_body = [
+            0, 0, 0, 0, 0x03, 0x15, 0x00, 0x01, 0x00, 0x04, 0x0d, 0x40, 0x15, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0x06, 0x01, 0x02,
+        ];
+        assert_eq!(
+            Err(EsdsError::MissingDecoderSpecificInfo),
+            audio_specific_config(&esds_body)
+        );
+    }
+
+    #[test]
+    fn write() {
+        let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+        let mut dcd = DecoderConfigDescriptor::new(&asc);
+        dcd.buffer_size_db = 0x1234;
+        dcd.max_bitrate = 128000;
+        dcd.avg_bitrate = 128000;
+        assert_eq!(
+            vec![
+                0, 0, 0, 0, 0x03, 0x19, 0x00, 0x01, 0x00, 0x04, 0x11, 0x40, 0x15, 0x00, 0x12, 0x34,
+                0x00, 0x01, 0xf4, 0x00, 0x00, 0x01, 0xf4, 0x00, 0x05, 0x02, 0x12, 0x10, 0x06, 0x01,
+                0x02
+            ],
+            esds_body(1, &dcd)
+        );
+        let mut buf = vec![];
+        write_descriptor(&mut buf, DEC_SPECIFIC_INFO_TAG, &[0; 200]);
+        assert_eq!(&[0x05, 0x81, 0x48], &buf[..3]);
+        assert_eq!(
+            Ok((0x05, 200)),
+            read_descriptor_header(&mut BitReader::new(&buf))
+        );
+    }
+}
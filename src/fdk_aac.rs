@@ -0,0 +1,125 @@
+//! Mappings to the values of the `AUDIO_OBJECT_TYPE` and `TRANSPORT_TYPE` enums of the
+//! Fraunhofer FDK AAC library, as used when configuring its encoder (`AACENC_AOT`,
+//! `AACENC_TRANSMUX`) and decoder (`aacDecoder_Open()`).
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::fdk_aac::*;
+//! # use mpeg4_audio_const::mime::Transport;
+//! assert_eq!(AOT_AAC_LC, to_audio_object_type(AudioObjectType::AAC_LC));
+//! assert_eq!(Some(AudioObjectType::SBR), from_audio_object_type(AOT_MP2_SBR));
+//! assert_eq!(TT_MP4_ADTS, transport_type(Transport::Adts));
+//! ```
+
+use crate::mime::Transport;
+use crate::AudioObjectType;
+use core::convert::TryFrom;
+
+/// `AOT_NONE`, indicating that no object type is given
+pub const AOT_NONE: i32 = -1;
+/// `AOT_AAC_MAIN`
+pub const AOT_AAC_MAIN: i32 = 1;
+/// `AOT_AAC_LC`
+pub const AOT_AAC_LC: i32 = 2;
+/// `AOT_SBR`, HE-AAC
+pub const AOT_SBR: i32 = 5;
+/// `AOT_ER_AAC_LD`
+pub const AOT_ER_AAC_LD: i32 = 23;
+/// `AOT_PS`, HE-AAC v2
+pub const AOT_PS: i32 = 29;
+/// `AOT_ER_AAC_ELD`
+pub const AOT_ER_AAC_ELD: i32 = 39;
+/// `AOT_USAC`
+pub const AOT_USAC: i32 = 42;
+/// `AOT_MP2_AAC_LC`, for MPEG-2 AAC LC
+pub const AOT_MP2_AAC_LC: i32 = 129;
+/// `AOT_MP2_SBR`, for MPEG-2 AAC LC with SBR
+pub const AOT_MP2_SBR: i32 = 132;
+
+/// `TT_UNKNOWN`
+pub const TT_UNKNOWN: i32 = -1;
+/// `TT_MP4_RAW`: raw access units, with the configuration given out-of-band
+pub const TT_MP4_RAW: i32 = 0;
+/// `TT_MP4_ADIF`
+pub const TT_MP4_ADIF: i32 = 1;
+/// `TT_MP4_ADTS`
+pub const TT_MP4_ADTS: i32 = 2;
+/// `TT_MP4_LATM_MCP1`: LATM with the `StreamMuxConfig` in-band (`muxConfigPresent` = 1)
+pub const TT_MP4_LATM_MCP1: i32 = 6;
+/// `TT_MP4_LATM_MCP0`: LATM with the `StreamMuxConfig` out-of-band (`muxConfigPresent` = 0)
+pub const TT_MP4_LATM_MCP0: i32 = 7;
+/// `TT_MP4_LOAS`: LATM within the LOAS `AudioSyncStream`
+pub const TT_MP4_LOAS: i32 = 10;
+/// `TT_DRM`
+pub const TT_DRM: i32 = 12;
+
+/// The `AUDIO_OBJECT_TYPE` value for the given audio object type.
+///
+/// The library numbers object types as the spec does, so the value is simply that of the
+/// object type.
+pub fn to_audio_object_type(audio_object_type: AudioObjectType) -> i32 {
+    i32::from(u8::from(audio_object_type))
+}
+
+/// The audio object type for the given `AUDIO_OBJECT_TYPE` value, or `None` for `AOT_NONE`
+/// and the library's DRM-specific values.  The MPEG-2 values map to their MPEG-4
+/// equivalents.
+pub fn from_audio_object_type(value: i32) -> Option<AudioObjectType> {
+    match value {
+        AOT_MP2_AAC_LC => Some(AudioObjectType::AAC_LC),
+        AOT_MP2_SBR => Some(AudioObjectType::SBR),
+        _ => u8::try_from(value)
+            .ok()
+            .and_then(|v| AudioObjectType::try_from(v).ok()),
+    }
+}
+
+/// The `TRANSPORT_TYPE` value for the given transport.
+///
+/// Both ISO-BMFF and RFC 3640 RTP carry raw access units, so map to `TT_MP4_RAW`, and RFC
+/// 6416 RTP maps to `TT_MP4_LATM_MCP1`, since the `StreamMuxConfig` is carried in-band unless
+/// the SDP gives `cpresent=0` (in which case `TT_MP4_LATM_MCP0` should be used instead).
+pub fn transport_type(transport: Transport) -> i32 {
+    match transport {
+        Transport::Adts => TT_MP4_ADTS,
+        Transport::IsoBmff | Transport::Rtp => TT_MP4_RAW,
+        Transport::RtpLatm => TT_MP4_LATM_MCP1,
+    }
+}
+
+/// The transport for the given `TRANSPORT_TYPE` value, or `None` if there is no corresponding
+/// [`Transport`](../mime/enum.Transport.html).  Since `TT_MP4_RAW` could describe several
+/// transports, it maps to `IsoBmff`.
+pub fn from_transport_type(value: i32) -> Option<Transport> {
+    match value {
+        TT_MP4_RAW => Some(Transport::IsoBmff),
+        TT_MP4_ADTS => Some(Transport::Adts),
+        TT_MP4_LATM_MCP1 | TT_MP4_LATM_MCP0 => Some(Transport::RtpLatm),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_types() {
+        assert_eq!(AOT_PS, to_audio_object_type(AudioObjectType::PS));
+        assert_eq!(
+            Some(AudioObjectType::ER_AAC_ELD),
+            from_audio_object_type(AOT_ER_AAC_ELD)
+        );
+        assert_eq!(
+            Some(AudioObjectType::AAC_LC),
+            from_audio_object_type(AOT_MP2_AAC_LC)
+        );
+        assert_eq!(None, from_audio_object_type(AOT_NONE));
+        assert_eq!(None, from_audio_object_type(143));
+        assert_eq!(
+            Some(Transport::RtpLatm),
+            from_transport_type(TT_MP4_LATM_MCP0)
+        );
+        assert_eq!(None, from_transport_type(TT_MP4_LOAS));
+    }
+}
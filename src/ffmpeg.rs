@@ -0,0 +1,104 @@
+//! Mappings between audio object types and the AAC profile values used by FFmpeg (the
+//! `profile` field of `AVCodecContext` / `AVCodecParameters`).
+//!
+//! FFmpeg's names for the constants were `FF_PROFILE_AAC_*` until FFmpeg 6.1, and are
+//! `AV_PROFILE_AAC_*` since.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::ffmpeg::*;
+//! assert_eq!(Some(FF_PROFILE_AAC_HE_V2), to_profile(AudioObjectType::PS));
+//! assert_eq!(Some(AudioObjectType::ER_AAC_ELD), from_profile(FF_PROFILE_AAC_ELD));
+//! ```
+
+use crate::asc::AudioSpecificConfig;
+use crate::AudioObjectType;
+
+/// The profile value for AAC Main
+pub const FF_PROFILE_AAC_MAIN: i32 = 0;
+/// The profile value for AAC LC
+pub const FF_PROFILE_AAC_LOW: i32 = 1;
+/// The profile value for AAC SSR
+pub const FF_PROFILE_AAC_SSR: i32 = 2;
+/// The profile value for AAC LTP
+pub const FF_PROFILE_AAC_LTP: i32 = 3;
+/// The profile value for HE-AAC (AAC LC + SBR)
+pub const FF_PROFILE_AAC_HE: i32 = 4;
+/// The profile value for HE-AAC v2 (AAC LC + SBR + PS)
+pub const FF_PROFILE_AAC_HE_V2: i32 = 28;
+/// The profile value for AAC LD
+pub const FF_PROFILE_AAC_LD: i32 = 22;
+/// The profile value for AAC ELD
+pub const FF_PROFILE_AAC_ELD: i32 = 38;
+/// The profile value for xHE-AAC (USAC)
+pub const FF_PROFILE_AAC_USAC: i32 = 41;
+/// The profile value for MPEG-2 AAC LC
+pub const FF_PROFILE_MPEG2_AAC_LOW: i32 = 128;
+/// The profile value for MPEG-2 AAC LC + SBR
+pub const FF_PROFILE_MPEG2_AAC_HE: i32 = 131;
+
+/// The FFmpeg profile for the given audio object type, or `None` if FFmpeg defines no profile
+/// for it.
+pub fn to_profile(audio_object_type: AudioObjectType) -> Option<i32> {
+    Some(match audio_object_type {
+        AudioObjectType::AAC_MAIN => FF_PROFILE_AAC_MAIN,
+        AudioObjectType::AAC_LC => FF_PROFILE_AAC_LOW,
+        AudioObjectType::AAC_SSR => FF_PROFILE_AAC_SSR,
+        AudioObjectType::AAC_LTP => FF_PROFILE_AAC_LTP,
+        AudioObjectType::SBR => FF_PROFILE_AAC_HE,
+        AudioObjectType::PS => FF_PROFILE_AAC_HE_V2,
+        AudioObjectType::ER_AAC_LD => FF_PROFILE_AAC_LD,
+        AudioObjectType::ER_AAC_ELD => FF_PROFILE_AAC_ELD,
+        AudioObjectType::USAC => FF_PROFILE_AAC_USAC,
+        _ => return None,
+    })
+}
+
+/// The audio object type for the given FFmpeg profile, or `None` if the value is not an AAC
+/// profile.  The MPEG-2 profiles map to their MPEG-4 equivalents.
+pub fn from_profile(profile: i32) -> Option<AudioObjectType> {
+    Some(match profile {
+        FF_PROFILE_AAC_MAIN => AudioObjectType::AAC_MAIN,
+        FF_PROFILE_AAC_LOW | FF_PROFILE_MPEG2_AAC_LOW => AudioObjectType::AAC_LC,
+        FF_PROFILE_AAC_SSR => AudioObjectType::AAC_SSR,
+        FF_PROFILE_AAC_LTP => AudioObjectType::AAC_LTP,
+        FF_PROFILE_AAC_HE | FF_PROFILE_MPEG2_AAC_HE => AudioObjectType::SBR,
+        FF_PROFILE_AAC_HE_V2 => AudioObjectType::PS,
+        FF_PROFILE_AAC_LD => AudioObjectType::ER_AAC_LD,
+        FF_PROFILE_AAC_ELD => AudioObjectType::ER_AAC_ELD,
+        FF_PROFILE_AAC_USAC => AudioObjectType::USAC,
+        _ => return None,
+    })
+}
+
+/// The FFmpeg profile for the given configuration, which is HE-AAC or HE-AAC v2 when the
+/// presence of SBR or PS is signalled (as FFmpeg reports for such streams).
+///
+/// ```rust
+/// # use mpeg4_audio_const::asc::*;
+/// # use mpeg4_audio_const::ffmpeg::*;
+/// let asc = AudioSpecificConfig::from_bytes(&[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80]).unwrap();
+/// assert_eq!(Some(FF_PROFILE_AAC_HE_V2), profile(&asc));
+/// ```
+pub fn profile(asc: &AudioSpecificConfig) -> Option<i32> {
+    to_profile(asc.signalled_object_type())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn roundtrip() {
+        for aot in 0..=95 {
+            if let Ok(aot) = AudioObjectType::try_from(aot) {
+                if let Some(profile) = to_profile(aot) {
+                    assert_eq!(Some(aot), from_profile(profile));
+                }
+            }
+        }
+        assert_eq!(None, from_profile(-99));
+        assert_eq!(None, to_profile(AudioObjectType::ALS));
+    }
+}
@@ -0,0 +1,167 @@
+//! Support for the AAC audio tags of the FLV file format, as also used by RTMP.
+//!
+//! An AAC stream starts with a _sequence header_ tag, whose body is the `AudioSpecificConfig`,
+//! followed by tags each holding one raw access unit,
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::flv;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! let tag_body = flv::sequence_header(&asc);
+//! assert_eq!(vec![0xaf, 0x00, 0x12, 0x10], tag_body);
+//! assert_eq!(Ok(asc), flv::parse_sequence_header(&tag_body));
+//! ```
+//!
+//! The `SoundRate`, `SoundSize` and `SoundType` fields of the `AudioTagHeader` can't describe
+//! most AAC configurations, so for AAC the FLV specification requires them always to be set to
+//! 44 kHz, 16-bit and stereo, and the real values are instead taken from the
+//! `AudioSpecificConfig`.
+
+use crate::asc::{AscError, AudioSpecificConfig};
+use crate::bits::{BitReader, EndOfData};
+use crate::trace::TraceEntry;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The `SoundFormat` value identifying AAC
+pub const SOUND_FORMAT_AAC: u8 = 10;
+/// The `SoundRate` value that AAC tags must use (signalling 44 kHz)
+pub const SOUND_RATE_AAC: u8 = 3;
+/// The complete `AudioTagHeader` byte for AAC: `SoundFormat` 10, `SoundRate` 3 (44 kHz),
+/// `SoundSize` 1 (16-bit) and `SoundType` 1 (stereo)
+pub const AAC_AUDIO_TAG_HEADER: u8 = SOUND_FORMAT_AAC << 4 | SOUND_RATE_AAC << 2 | 0b11;
+
+/// The `AACPacketType` of a tag holding the `AudioSpecificConfig`
+pub const AAC_PACKET_TYPE_SEQUENCE_HEADER: u8 = 0;
+/// The `AACPacketType` of a tag holding a raw access unit
+pub const AAC_PACKET_TYPE_RAW: u8 = 1;
+
+/// Errors that may be produced while parsing an FLV audio tag body
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum FlvError {
+    /// The tag body was too short to hold the `AudioTagHeader` and `AACPacketType`, ending
+    /// while reading the given field.
+    NotEnoughData(TraceEntry),
+    /// The `SoundFormat` was not AAC.
+    NotAac(u8),
+    /// The `AACPacketType` was not that of a sequence header.
+    NotSequenceHeader(u8),
+    /// The `SoundRate`, `SoundSize` or `SoundType` fields did not have the values required for
+    /// AAC (reported only by [`validate_audio_tag_header()`](fn.validate_audio_tag_header.html)).
+    BadAudioTagHeader(u8),
+    /// The sequence header did not hold a valid `AudioSpecificConfig`.
+    Asc(AscError),
+}
+
+impl fmt::Display for FlvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlvError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            FlvError::NotAac(v) => write!(f, "SoundFormat {} is not AAC", v),
+            FlvError::NotSequenceHeader(v) => {
+                write!(f, "AACPacketType {} is not a sequence header", v)
+            }
+            FlvError::BadAudioTagHeader(v) => {
+                write!(f, "audio tag header {:#04x} not valid for AAC", v)
+            }
+            FlvError::Asc(_) => f.write_str("invalid AudioSpecificConfig"),
+        }
+    }
+}
+
+impl core::error::Error for FlvError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            FlvError::Asc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EndOfData> for FlvError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        FlvError::NotEnoughData(at)
+    }
+}
+
+/// Produces the body of the sequence header tag for the given configuration.
+pub fn sequence_header(asc: &AudioSpecificConfig) -> Vec<u8> {
+    let mut buf = vec![AAC_AUDIO_TAG_HEADER, AAC_PACKET_TYPE_SEQUENCE_HEADER];
+    buf.extend_from_slice(&asc.to_bytes());
+    buf
+}
+
+/// Parses the `AudioSpecificConfig` from the body of a sequence header tag.
+///
+/// Since some encoders set them incorrectly, the `SoundRate`, `SoundSize` and `SoundType` fields
+/// are not checked (see [`validate_audio_tag_header()`](fn.validate_audio_tag_header.html)).
+pub fn parse_sequence_header(tag_body: &[u8]) -> Result<AudioSpecificConfig, FlvError> {
+    let mut r = BitReader::new(tag_body);
+    let sound_format = r.named("SoundFormat").read_u8(4)?;
+    if sound_format != SOUND_FORMAT_AAC {
+        return Err(FlvError::NotAac(sound_format));
+    }
+    r.named("SoundRate").skip(2)?;
+    r.named("SoundSize").skip(1)?;
+    r.named("SoundType").skip(1)?;
+    let packet_type = r.named("AACPacketType").read_u8(8)?;
+    if packet_type != AAC_PACKET_TYPE_SEQUENCE_HEADER {
+        return Err(FlvError::NotSequenceHeader(packet_type));
+    }
+    AudioSpecificConfig::from_bytes(&tag_body[2..]).map_err(FlvError::Asc)
+}
+
+/// Checks that the given `AudioTagHeader` byte is AAC, with the fixed `SoundRate`, `SoundSize`
+/// and `SoundType` values that the FLV specification requires.
+///
+/// ```rust
+/// # use mpeg4_audio_const::flv::*;
+/// assert_eq!(Ok(()), validate_audio_tag_header(0xaf));
+/// // mono, 22 kHz
+/// assert_eq!(Err(FlvError::BadAudioTagHeader(0xaa)), validate_audio_tag_header(0xaa));
+/// ```
+pub fn validate_audio_tag_header(header: u8) -> Result<(), FlvError> {
+    if header >> 4 != SOUND_FORMAT_AAC {
+        Err(FlvError::NotAac(header >> 4))
+    } else if header != AAC_AUDIO_TAG_HEADER {
+        Err(FlvError::BadAudioTagHeader(header))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(FlvError::NotEnoughData(TraceEntry {
+                bit_offset: 8,
+                bit_length: 8,
+                field: "AACPacketType",
+                value: None,
+            })),
+            parse_sequence_header(&[0xaf])
+        );
+        // MP3
+        assert_eq!(
+            Err(FlvError::NotAac(2)),
+            parse_sequence_header(&[0x2f, 0x00, 0x12, 0x10])
+        );
+        assert_eq!(
+            Err(FlvError::NotSequenceHeader(AAC_PACKET_TYPE_RAW)),
+            parse_sequence_header(&[0xaf, 0x01, 0x21, 0x00])
+        );
+        assert!(matches!(
+            parse_sequence_header(&[0xaf, 0x00, 0x12]),
+            Err(FlvError::Asc(AscError::NotEnoughData(_)))
+        ));
+        // a mono header is tolerated
+        assert!(parse_sequence_header(&[0xae, 0x00, 0x11, 0x88]).is_ok());
+    }
+}
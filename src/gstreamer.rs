@@ -0,0 +1,99 @@
+//! GStreamer caps strings describing MPEG-4 audio, for applications passing data parsed by this
+//! crate into GStreamer pipelines (e.g. via `appsrc`).
+//!
+//! The caps are produced as strings in GStreamer's serialization syntax, so this module does
+//! not depend on the GStreamer libraries; the result can be passed to `gst::Caps::from_str()`.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::gstreamer::*;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! assert_eq!(
+//!     "audio/mpeg, mpegversion=(int)4, stream-format=(string)raw, rate=(int)44100, \
+//!      channels=(int)2, codec_data=(buffer)1210",
+//!     caps(&asc, StreamFormat::Raw)
+//! );
+//! ```
+
+use crate::asc::AudioSpecificConfig;
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+
+/// The framing of the buffers described by the caps, given in the `stream-format` field
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum StreamFormat {
+    /// Raw AAC frames (e.g. as demuxed from MP4), with the `AudioSpecificConfig` given in the
+    /// `codec_data` field
+    Raw,
+    /// ADTS frames
+    Adts,
+    /// LATM in the LOAS transport (`AudioSyncStream`)
+    Loas,
+}
+
+impl StreamFormat {
+    /// The value of the `stream-format` field.
+    pub fn name(self) -> &'static str {
+        match self {
+            StreamFormat::Raw => "raw",
+            StreamFormat::Adts => "adts",
+            StreamFormat::Loas => "loas",
+        }
+    }
+}
+
+/// The caps for the given configuration in the given stream format.
+///
+/// The `rate` and `channels` fields describe the decoded output, so take account of any SBR
+/// or PS extension, and are omitted if unknown.  The `codec_data` field is only included for
+/// the `Raw` stream format, since the other formats carry their configuration in-band.
+///
+/// ```rust
+/// # use mpeg4_audio_const::asc::*;
+/// # use mpeg4_audio_const::gstreamer::*;
+/// // HE-AAC v2: 24kHz mono core, producing 48kHz stereo
+/// let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+/// assert_eq!(
+///     "audio/mpeg, mpegversion=(int)4, stream-format=(string)adts, rate=(int)48000, \
+///      channels=(int)2",
+///     caps(&asc, StreamFormat::Adts)
+/// );
+/// ```
+pub fn caps(asc: &AudioSpecificConfig, stream_format: StreamFormat) -> String {
+    let mut caps = format!(
+        "audio/mpeg, mpegversion=(int)4, stream-format=(string){}",
+        stream_format.name()
+    );
+    if let Some(rate) = asc.output_sample_rate() {
+        write!(caps, ", rate=(int){}", rate).unwrap();
+    }
+    if let Some(channels) = asc.channel_count() {
+        write!(caps, ", channels=(int){}", channels).unwrap();
+    }
+    if stream_format == StreamFormat::Raw {
+        caps.push_str(", codec_data=(buffer)");
+        for b in asc.to_bytes() {
+            write!(caps, "{:02x}", b).unwrap();
+        }
+    }
+    caps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pce_loas() {
+        // AAC LC, 48kHz, channelConfiguration 0, PCE describing stereo
+        let asc =
+            AudioSpecificConfig::from_bytes(&[0x11, 0x80, 0x04, 0xc4, 0x00, 0x00, 0x20, 0x00])
+                .unwrap();
+        assert_eq!(
+            "audio/mpeg, mpegversion=(int)4, stream-format=(string)loas, rate=(int)48000, \
+             channels=(int)2",
+            caps(&asc, StreamFormat::Loas)
+        );
+    }
+}
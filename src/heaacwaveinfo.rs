@@ -0,0 +1,228 @@
+//! Support for the `HEAACWAVEINFO` structure, which follows the `WAVEFORMATEX` of a
+//! `WAVE_FORMAT_MPEG_HEAAC` format description, as used by Windows Media Foundation (where the
+//! structure, together with the `AudioSpecificConfig` following it, is the
+//! `MF_MT_USER_DATA` attribute of an AAC media type).
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::heaacwaveinfo::*;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! let info = HeAacWaveInfo::new(PayloadType::Raw, &asc);
+//! assert_eq!(AudioProfileLevelIndication::AAC_L2, info.audio_profile_level_indication);
+//! let user_data = info.to_bytes();
+//! assert_eq!(
+//!     vec![0x00, 0x00, 0x29, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x12, 0x10],
+//!     user_data
+//! );
+//! assert_eq!(Ok(info), HeAacWaveInfo::from_bytes(&user_data));
+//! ```
+
+use crate::asc::{AscError, AudioSpecificConfig};
+use crate::trace::TraceEntry;
+use crate::AudioProfileLevelIndication;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The `wFormatTag` value of a `WAVEFORMATEX` followed by `HEAACWAVEINFO`
+pub const WAVE_FORMAT_MPEG_HEAAC: u16 = 0x1610;
+
+/// The length in bytes of the `HEAACWAVEINFO` fields following the `WAVEFORMATEX`, and so
+/// preceding any `AudioSpecificConfig`
+pub const HEAACWAVEINFO_LENGTH: usize = 12;
+
+/// The names and lengths in bytes of the `HEAACWAVEINFO` fields following the `WAVEFORMATEX`
+const FIELDS: [(&str, usize); 5] = [
+    ("wPayloadType", 2),
+    ("wAudioProfileLevelIndication", 2),
+    ("wStructType", 2),
+    ("wReserved1", 2),
+    ("dwReserved2", 4),
+];
+
+/// Errors that may be produced while parsing `HEAACWAVEINFO` data
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum HeAacWaveInfoError {
+    /// The data was shorter than the fixed fields of the structure, ending within the given
+    /// field.
+    NotEnoughData(TraceEntry),
+    /// The `wPayloadType` field held an undefined value.
+    BadPayloadType(u16),
+    /// The `wAudioProfileLevelIndication` field held a value too large to be an audio profile
+    /// and level indication.
+    BadAudioProfileLevelIndication(u16),
+    /// The `wStructType` field was not `0` (the only value defined).
+    BadStructType(u16),
+    /// The data following the structure was not a valid `AudioSpecificConfig`.
+    Asc(AscError),
+}
+
+impl fmt::Display for HeAacWaveInfoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeAacWaveInfoError::NotEnoughData(at) => {
+                write!(f, "not enough data, reading {}", at)
+            }
+            HeAacWaveInfoError::BadPayloadType(v) => write!(f, "undefined wPayloadType {}", v),
+            HeAacWaveInfoError::BadAudioProfileLevelIndication(v) => {
+                write!(f, "wAudioProfileLevelIndication {} out of range", v)
+            }
+            HeAacWaveInfoError::BadStructType(v) => write!(f, "undefined wStructType {}", v),
+            HeAacWaveInfoError::Asc(_) => f.write_str("invalid AudioSpecificConfig"),
+        }
+    }
+}
+
+impl core::error::Error for HeAacWaveInfoError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            HeAacWaveInfoError::Asc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The `wPayloadType` value, giving the framing of the stream
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PayloadType {
+    /// `0`: raw access units, requiring an `AudioSpecificConfig` to decode
+    Raw,
+    /// `1`: ADTS frames
+    Adts,
+    /// `2`: an ADIF header followed by raw access units
+    Adif,
+    /// `3`: LATM within the LOAS `AudioSyncStream`
+    Loas,
+}
+
+impl PayloadType {
+    fn from_u16(v: u16) -> Option<PayloadType> {
+        match v {
+            0 => Some(PayloadType::Raw),
+            1 => Some(PayloadType::Adts),
+            2 => Some(PayloadType::Adif),
+            3 => Some(PayloadType::Loas),
+            _ => None,
+        }
+    }
+}
+
+/// The fields of the `HEAACWAVEINFO` structure that carry information (the rest are reserved),
+/// and the `AudioSpecificConfig` that may follow it
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct HeAacWaveInfo {
+    pub payload_type: PayloadType,
+    /// The profile and level, which may be `NOT_SPECIFIED` (`0xfe`)
+    pub audio_profile_level_indication: AudioProfileLevelIndication,
+    /// The configuration following the structure, which Media Foundation requires for
+    /// `PayloadType::Raw` streams, and permits for the others
+    pub audio_specific_config: Option<AudioSpecificConfig>,
+}
+
+impl HeAacWaveInfo {
+    /// Describes a stream with the given framing and configuration, taking the profile and
+    /// level from
+    /// [`AudioSpecificConfig::audio_profile_level_indication()`](../asc/struct.AudioSpecificConfig.html#method.audio_profile_level_indication).
+    pub fn new(payload_type: PayloadType, asc: &AudioSpecificConfig) -> HeAacWaveInfo {
+        HeAacWaveInfo {
+            payload_type,
+            audio_profile_level_indication: asc.audio_profile_level_indication(),
+            audio_specific_config: Some(asc.clone()),
+        }
+    }
+
+    /// Parses the structure from the bytes following the `WAVEFORMATEX` (i.e. the
+    /// `cbSize` bytes of extra format information).
+    pub fn from_bytes(buf: &[u8]) -> Result<HeAacWaveInfo, HeAacWaveInfoError> {
+        let mut offset = 0;
+        for &(field, length) in FIELDS.iter() {
+            if buf.len() < offset + length {
+                return Err(HeAacWaveInfoError::NotEnoughData(TraceEntry {
+                    bit_offset: offset * 8,
+                    bit_length: length * 8,
+                    field,
+                    value: None,
+                }));
+            }
+            offset += length;
+        }
+        let word = |i: usize| u16::from_le_bytes([buf[i], buf[i + 1]]);
+        let payload_type = PayloadType::from_u16(word(0))
+            .ok_or_else(|| HeAacWaveInfoError::BadPayloadType(word(0)))?;
+        let apli = word(2);
+        if apli > 0xff {
+            return Err(HeAacWaveInfoError::BadAudioProfileLevelIndication(apli));
+        }
+        if word(4) != 0 {
+            return Err(HeAacWaveInfoError::BadStructType(word(4)));
+        }
+        let rest = &buf[HEAACWAVEINFO_LENGTH..];
+        let audio_specific_config = if rest.is_empty() {
+            None
+        } else {
+            Some(AudioSpecificConfig::from_bytes(rest).map_err(HeAacWaveInfoError::Asc)?)
+        };
+        Ok(HeAacWaveInfo {
+            payload_type,
+            audio_profile_level_indication: AudioProfileLevelIndication::from(apli as u8),
+            audio_specific_config,
+        })
+    }
+
+    /// Serializes the structure and any `AudioSpecificConfig`, with the reserved fields set to
+    /// zero.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(HEAACWAVEINFO_LENGTH + 2);
+        buf.extend_from_slice(&(self.payload_type as u16).to_le_bytes());
+        buf.extend_from_slice(
+            &u16::from(u8::from(self.audio_profile_level_indication)).to_le_bytes(),
+        );
+        // wStructType, wReserved1 and dwReserved2
+        buf.extend_from_slice(&[0; 8]);
+        if let Some(ref asc) = self.audio_specific_config {
+            buf.extend_from_slice(&asc.to_bytes());
+        }
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Err(HeAacWaveInfoError::NotEnoughData(TraceEntry {
+                bit_offset: 64,
+                bit_length: 32,
+                field: "dwReserved2",
+                value: None,
+            })),
+            HeAacWaveInfo::from_bytes(&[0; 11])
+        );
+        let mut buf = [0x01, 0x00, 0xfe, 0x00, 0, 0, 0, 0, 0, 0, 0, 0];
+        let info = HeAacWaveInfo::from_bytes(&buf).unwrap();
+        assert_eq!(PayloadType::Adts, info.payload_type);
+        assert_eq!(None, info.audio_specific_config);
+        assert_eq!(&buf[..], &info.to_bytes()[..]);
+        buf[0] = 4;
+        assert_eq!(
+            Err(HeAacWaveInfoError::BadPayloadType(4)),
+            HeAacWaveInfo::from_bytes(&buf)
+        );
+        buf[0] = 0;
+        buf[3] = 1;
+        assert_eq!(
+            Err(HeAacWaveInfoError::BadAudioProfileLevelIndication(0x1fe)),
+            HeAacWaveInfo::from_bytes(&buf)
+        );
+        buf[3] = 0;
+        buf[4] = 1;
+        assert_eq!(
+            Err(HeAacWaveInfoError::BadStructType(1)),
+            HeAacWaveInfo::from_bytes(&buf)
+        );
+    }
+}
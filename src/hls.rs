@@ -0,0 +1,145 @@
+//! Helpers for describing MPEG-4 audio renditions in HLS playlists.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::hls;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x11, 0xb0]).unwrap();
+//! assert_eq!("mp4a.40.2", hls::codecs(&asc));
+//! assert_eq!(Some("6".to_string()), hls::channels(&asc));
+//! ```
+
+use crate::asc::AudioSpecificConfig;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Rendition-specific usage identifiers, given in the third parameter of the `CHANNELS`
+/// attribute
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ChannelUsage {
+    /// The audio is binaural (i.e. intended for headphones)
+    Binaural,
+    /// The audio is pre-processed content that should not be further spatialized
+    Immersive,
+    /// The audio is a downmix derived from content with more channels
+    Downmix,
+}
+
+impl fmt::Display for ChannelUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChannelUsage::Binaural => "BINAURAL",
+            ChannelUsage::Immersive => "IMMERSIVE",
+            ChannelUsage::Downmix => "DOWNMIX",
+        })
+    }
+}
+
+/// The value of an `EXT-X-MEDIA` tag's `CHANNELS` attribute.
+///
+/// The attribute is a `/`-separated list of parameters: the channel count (or, for
+/// object-based audio, the object count), then an optional comma-separated list of audio
+/// object coding identifiers (e.g. `JOC`), then an optional comma-separated list of usage
+/// identifiers.  When usage identifiers are given without any object coding identifiers, the
+/// second parameter is written as `-`.
+///
+/// ```rust
+/// # use mpeg4_audio_const::hls::*;
+/// let channels = HlsChannels {
+///     count: 2,
+///     object_coding: vec![],
+///     usage: vec![ChannelUsage::Binaural],
+/// };
+/// assert_eq!("2/-/BINAURAL", channels.to_string());
+/// let joc = HlsChannels {
+///     count: 16,
+///     object_coding: vec!["JOC".to_string()],
+///     usage: vec![],
+/// };
+/// assert_eq!("16/JOC", joc.to_string());
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct HlsChannels {
+    pub count: u32,
+    /// Audio object coding identifiers, made up of the characters `A`-`Z`, `0`-`9` and `-`
+    pub object_coding: Vec<String>,
+    pub usage: Vec<ChannelUsage>,
+}
+
+impl HlsChannels {
+    /// The `CHANNELS` value for the given configuration: simply the number of output channels,
+    /// since MPEG-4 audio renditions are channel-based.  Returns `None` if the channel count
+    /// can't be determined (i.e. for a reserved `channelConfiguration` value, or a
+    /// `channelConfiguration` of `0` without a parsed program config element).
+    pub fn from_asc(asc: &AudioSpecificConfig) -> Option<HlsChannels> {
+        asc.channel_count().map(|count| HlsChannels {
+            count,
+            object_coding: vec![],
+            usage: vec![],
+        })
+    }
+}
+
+impl fmt::Display for HlsChannels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.count)?;
+        if self.object_coding.is_empty() && self.usage.is_empty() {
+            return Ok(());
+        }
+        if self.object_coding.is_empty() {
+            f.write_str("/-")?;
+        } else {
+            write!(f, "/{}", self.object_coding.join(","))?;
+        }
+        for (i, usage) in self.usage.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { "/" } else { "," }, usage)?;
+        }
+        Ok(())
+    }
+}
+
+/// The value for the `CODECS` attribute of an `EXT-X-STREAM-INF` tag describing the given
+/// configuration, e.g. `"mp4a.40.2"`.
+pub fn codecs(asc: &AudioSpecificConfig) -> String {
+    asc.codec_string()
+}
+
+/// The value for the `CHANNELS` attribute of an `EXT-X-MEDIA` tag describing the given
+/// configuration, e.g. `"2"`, or `None` if the channel count can't be determined.
+pub fn channels(asc: &AudioSpecificConfig) -> Option<String> {
+    HlsChannels::from_asc(asc).map(|c| c.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parametric_stereo_is_stereo() {
+        // HE-AAC v2, mono core
+        let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+        assert_eq!("mp4a.40.29", codecs(&asc));
+        assert_eq!(Some("2".to_string()), channels(&asc));
+    }
+
+    #[test]
+    fn pce_channel_count() {
+        // AAC LC, 48kHz, channelConfiguration 0, PCE describing stereo
+        let asc =
+            AudioSpecificConfig::from_bytes(&[0x11, 0x80, 0x04, 0xc4, 0x00, 0x00, 0x20, 0x00])
+                .unwrap();
+        assert_eq!(Some("2".to_string()), channels(&asc));
+    }
+
+    #[test]
+    fn multiple_usages() {
+        let channels = HlsChannels {
+            count: 12,
+            object_coding: vec!["JOC".to_string()],
+            usage: vec![ChannelUsage::Immersive, ChannelUsage::Downmix],
+        };
+        assert_eq!("12/JOC/IMMERSIVE,DOWNMIX", channels.to_string());
+    }
+}
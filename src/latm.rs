@@ -0,0 +1,740 @@
+//! The `StreamMuxConfig` of the Low-overhead MPEG-4 Audio Transport Multiplex (LATM), which
+//! describes the programs and layers multiplexed into a LATM stream, along with their
+//! `AudioSpecificConfig`s.
+//!
+//! A `StreamMuxConfig` is carried either in-band within an `AudioMuxElement`, or out-of-band
+//! (e.g. as the `config` parameter of an RFC 6416 `MP4A-LATM` SDP description; see
+//! [`sdp::Mp4aLatmFmtp`](../sdp/struct.Mp4aLatmFmtp.html)).
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::latm::*;
+//! let config = StreamMuxConfig::from_bytes(&[0x40, 0x00, 0x24, 0x20, 0x3f, 0xc0]).unwrap();
+//! let asc = config.audio_specific_config().unwrap();
+//! assert_eq!(AudioObjectType::AAC_LC, asc.audio_object_type);
+//! assert_eq!(ChannelConfiguration::STEREO, asc.channel_configuration);
+//! ```
+
+use crate::asc::{AscError, AudioSpecificConfig, RawBits};
+use crate::bits::{BitReader, BitWriter, EndOfData};
+use crate::bitstream::{read_counted, write_bits, FromBitstream, ToBitstream};
+use crate::describe::{yes_no, Describe, Describer, Description};
+use crate::options::{ParseOptions, Parsed, Violation};
+use crate::trace::{AscVisitor, TraceEntry};
+use crate::AudioObjectType;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Errors that may be produced while parsing a `StreamMuxConfig`
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum LatmError {
+    /// The data ended before the end of the structure, while reading the given element.
+    NotEnoughData(TraceEntry),
+    /// The `audioMuxVersionA` field was set, signalling a syntax not yet defined by the spec.
+    UnsupportedVersion(TraceEntry),
+    /// The structure signalled more layers than permitted by the
+    /// [`Limits`](../options/struct.Limits.html) in use, the given field taking the total past
+    /// the limit.
+    TooManyLayers(TraceEntry),
+    /// The length of an `AudioSpecificConfig` exceeded the `max_explicit_length` of the
+    /// [`Limits`](../options/struct.Limits.html) in use, ending with the given field.
+    LengthLimitExceeded(TraceEntry),
+    /// The structure did not conform to the spec, when parsed with
+    /// [`Strictness::Strict`](../options/enum.Strictness.html).
+    Violation(Violation),
+    /// An `AudioSpecificConfig` within the structure could not be parsed.
+    Asc(AscError),
+}
+
+impl fmt::Display for LatmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LatmError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            LatmError::UnsupportedVersion(at) => write!(f, "unsupported version, {}", at),
+            LatmError::TooManyLayers(at) => write!(f, "too many layers, reading {}", at),
+            LatmError::LengthLimitExceeded(at) => {
+                write!(f, "length limit exceeded, reading {}", at)
+            }
+            LatmError::Violation(v) => v.fmt(f),
+            LatmError::Asc(_) => f.write_str("invalid AudioSpecificConfig"),
+        }
+    }
+}
+
+impl core::error::Error for LatmError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            LatmError::Asc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EndOfData> for LatmError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        LatmError::NotEnoughData(at)
+    }
+}
+impl From<Violation> for LatmError {
+    fn from(v: Violation) -> Self {
+        LatmError::Violation(v)
+    }
+}
+impl From<AscError> for LatmError {
+    fn from(e: AscError) -> Self {
+        match e {
+            AscError::NotEnoughData(at) => LatmError::NotEnoughData(at),
+            e => LatmError::Asc(e),
+        }
+    }
+}
+
+/// How the length of a layer's payload within each `PayloadMux` is given, as determined by
+/// the `frameLengthType`
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FrameLength {
+    /// `frameLengthType` `0`: the payload length is given in the `PayloadLengthInfo`
+    Variable {
+        latm_buffer_fullness: u8,
+        /// Present for a CELP or HVXC core layer beneath a scalable layer, when
+        /// `allStreamsSameTimeFraming` is not set
+        core_frame_offset: Option<u8>,
+    },
+    /// `frameLengthType` `1`: the payload has a fixed length, given by the 9-bit
+    /// `frameLength` field
+    Fixed(u16),
+    /// `frameLengthType` `3`, `4` or `5`: CELP payloads, with the length given by a table
+    /// index
+    Celp {
+        frame_length_type: u8,
+        table_index: u8,
+    },
+    /// `frameLengthType` `6` or `7`: HVXC payloads, with the length given by a table index
+    Hvxc {
+        frame_length_type: u8,
+        table_index: bool,
+    },
+    /// `frameLengthType` `2`, which is reserved, and has no further fields
+    Reserved,
+}
+
+impl FrameLength {
+    /// The value of the 3-bit `frameLengthType` field.
+    pub fn frame_length_type(&self) -> u8 {
+        match *self {
+            FrameLength::Variable { .. } => 0,
+            FrameLength::Fixed(_) => 1,
+            FrameLength::Reserved => 2,
+            FrameLength::Celp {
+                frame_length_type, ..
+            } => frame_length_type,
+            FrameLength::Hvxc {
+                frame_length_type, ..
+            } => frame_length_type,
+        }
+    }
+}
+
+/// A layer of a program multiplexed in a LATM stream
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Layer {
+    /// The configuration of this layer, or `None` if `useSameConfig` was set, indicating that
+    /// the configuration of the preceding layer applies
+    pub config: Option<AudioSpecificConfig>,
+    pub frame_length: FrameLength,
+}
+
+/// A parsed `StreamMuxConfig`
+///
+/// When `audio_mux_version` is `0`, the `AudioSpecificConfig`s are not preceded by their
+/// length, so can only be located within the structure for those object types whose specific
+/// config this crate interprets.
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StreamMuxConfig {
+    /// The `audioMuxVersion`, `0` or `1`
+    pub audio_mux_version: u8,
+    /// Present when `audio_mux_version` is `1`
+    pub tara_buffer_fullness: Option<u32>,
+    pub all_streams_same_time_framing: bool,
+    /// The 6-bit `numSubFrames` field, which is one less than the number of `PayloadMux`
+    /// elements in each `AudioMuxElement`
+    pub num_sub_frames: u8,
+    /// The layers of each program (there must be from 1 to 16 programs, each having from 1 to
+    /// 8 layers)
+    pub programs: Vec<Vec<Layer>>,
+    /// The length of the other data, in bits, present when `otherDataPresent` is set
+    pub other_data_len_bits: Option<u32>,
+    /// Present when `crcCheckPresent` is set
+    pub crc_check_sum: Option<u8>,
+}
+
+impl StreamMuxConfig {
+    /// A multi-line, human-readable description of this configuration (including the
+    /// `AudioSpecificConfig` of each layer), similar to that given by media inspection tools,
+    /// for diagnostic output.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// # use mpeg4_audio_const::latm::*;
+    /// let config = StreamMuxConfig::new(AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap());
+    /// let description = config.describe().to_string();
+    /// assert!(description.contains("\n  Program 0\n    Layer 0\n"));
+    /// assert!(description.contains("\n      AudioSpecificConfig\n        Object type: 2"));
+    /// ```
+    pub fn describe(&self) -> impl fmt::Display + '_ {
+        Description(self)
+    }
+
+    /// A configuration for a single program having a single layer, with the given
+    /// configuration, using `audioMuxVersion` `0` and carrying one variable-length
+    /// `PayloadMux` per `AudioMuxElement`, as is typical for AAC.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::asc::*;
+    /// # use mpeg4_audio_const::latm::*;
+    /// let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+    /// let config = StreamMuxConfig::new(asc);
+    /// assert_eq!(vec![0x40, 0x00, 0x24, 0x20, 0x3f, 0xc0], config.to_bytes());
+    /// ```
+    pub fn new(config: AudioSpecificConfig) -> StreamMuxConfig {
+        StreamMuxConfig {
+            audio_mux_version: 0,
+            tara_buffer_fullness: None,
+            all_streams_same_time_framing: true,
+            num_sub_frames: 0,
+            programs: vec![vec![Layer {
+                config: Some(config),
+                frame_length: FrameLength::Variable {
+                    latm_buffer_fullness: 0xff,
+                    core_frame_offset: None,
+                },
+            }]],
+            other_data_len_bits: None,
+            crc_check_sum: None,
+        }
+    }
+
+    /// Parses a `StreamMuxConfig` from the start of the given buffer.
+    pub fn from_bytes(buf: &[u8]) -> Result<StreamMuxConfig, LatmError> {
+        Self::read(&mut BitReader::new(buf))
+    }
+
+    /// Parses a `StreamMuxConfig` as for [`from_bytes()`](#method.from_bytes), checking for
+    /// reserved values and other violations of the spec (including within the
+    /// `AudioSpecificConfig`s) as the given options require.
+    pub fn from_bytes_with_options(
+        buf: &[u8],
+        options: &ParseOptions,
+    ) -> Result<Parsed<StreamMuxConfig>, LatmError> {
+        options.parse(buf, Self::read)
+    }
+
+    /// Parses a `StreamMuxConfig`, passing each field (including those of the
+    /// `AudioSpecificConfig`s) to the given visitor rather than returning the parsed structure.
+    /// If the visitor stops the parse early, `Ok` is returned.
+    pub fn parse_with(buf: &[u8], visitor: &mut impl AscVisitor) -> Result<(), LatmError> {
+        let mut r = BitReader::visiting(buf, visitor);
+        match Self::read(&mut r) {
+            Err(_) if r.stopped() => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    pub(crate) fn read(r: &mut BitReader<'_>) -> Result<StreamMuxConfig, LatmError> {
+        let audio_mux_version = r.named("audioMuxVersion").read_u8(1)?;
+        if audio_mux_version == 1 && r.named("audioMuxVersionA").read_bool()? {
+            return Err(LatmError::UnsupportedVersion(r.last_read()));
+        }
+        let tara_buffer_fullness = if audio_mux_version == 1 {
+            Some(read_latm_value(r)?)
+        } else {
+            None
+        };
+        let all_streams_same_time_framing = r.named("allStreamsSameTimeFraming").read_bool()?;
+        let num_sub_frames = r.named("numSubFrames").read_u8(6)?;
+        let num_program = r.named("numProgram").read_u8(4)?;
+        let mut programs = Vec::with_capacity(usize::from(num_program) + 1);
+        let mut audio_object_type = None;
+        let mut total_layers = 0;
+        for prog in 0..=num_program {
+            let num_layer = r.named("numLayer").read_u8(3)?;
+            total_layers += usize::from(num_layer) + 1;
+            if total_layers > r.limits().max_latm_layers {
+                return Err(LatmError::TooManyLayers(r.last_read()));
+            }
+            let mut layers = Vec::with_capacity(usize::from(num_layer) + 1);
+            let mut prev_audio_object_type = None;
+            for lay in 0..=num_layer {
+                let use_same_config = if prog == 0 && lay == 0 {
+                    false
+                } else {
+                    r.named("useSameConfig").read_bool()?
+                };
+                let config = if use_same_config {
+                    None
+                } else if audio_mux_version == 0 {
+                    Some(AudioSpecificConfig::read(r)?)
+                } else {
+                    let asc_len = read_latm_value(r)? as usize;
+                    if asc_len > r.limits().max_explicit_length {
+                        return Err(LatmError::LengthLimitExceeded(r.last_read()));
+                    }
+                    let start = r.position();
+                    let data = r.named("AudioSpecificConfig").read_bits(asc_len)?;
+                    Some(r.nested(&data, asc_len, start, AudioSpecificConfig::read)?)
+                };
+                if let Some(ref asc) = config {
+                    audio_object_type = Some(asc.audio_object_type);
+                }
+                let frame_length = match r.named("frameLengthType").read_u8(3)? {
+                    0 => {
+                        let latm_buffer_fullness = r.named("latmBufferFullness").read_u8(8)?;
+                        let core_frame_offset = if !all_streams_same_time_framing
+                            && has_core_frame_offset(audio_object_type, prev_audio_object_type)
+                        {
+                            Some(r.named("coreFrameOffset").read_u8(6)?)
+                        } else {
+                            None
+                        };
+                        FrameLength::Variable {
+                            latm_buffer_fullness,
+                            core_frame_offset,
+                        }
+                    }
+                    1 => FrameLength::Fixed(r.named("frameLength").read_u16(9)?),
+                    2 => {
+                        r.violation(Violation::ReservedValue);
+                        FrameLength::Reserved
+                    }
+                    frame_length_type @ 3..=5 => FrameLength::Celp {
+                        frame_length_type,
+                        table_index: r.named("CELPframeLengthTableIndex").read_u8(6)?,
+                    },
+                    frame_length_type => FrameLength::Hvxc {
+                        frame_length_type,
+                        table_index: r.named("HVXCframeLengthTableIndex").read_bool()?,
+                    },
+                };
+                prev_audio_object_type = audio_object_type;
+                layers.push(Layer {
+                    config,
+                    frame_length,
+                });
+            }
+            programs.push(layers);
+        }
+        let other_data_len_bits = if r.named("otherDataPresent").read_bool()? {
+            Some(if audio_mux_version == 1 {
+                read_latm_value(r)?
+            } else {
+                let mut len = 0u32;
+                loop {
+                    let esc = r.named("otherDataLenEsc").read_bool()?;
+                    len = (len << 8) + r.named("otherDataLenTmp").read(8)?;
+                    if !esc {
+                        break;
+                    }
+                }
+                len
+            })
+        } else {
+            None
+        };
+        let crc_check_sum = if r.named("crcCheckPresent").read_bool()? {
+            Some(r.named("crcCheckSum").read_u8(8)?)
+        } else {
+            None
+        };
+        Ok(StreamMuxConfig {
+            audio_mux_version,
+            tara_buffer_fullness,
+            all_streams_same_time_framing,
+            num_sub_frames,
+            programs,
+            other_data_len_bits,
+            crc_check_sum,
+        })
+    }
+
+    /// Serializes this configuration.
+    ///
+    /// As with
+    /// [`AudioSpecificConfig::to_bytes()`](../asc/struct.AudioSpecificConfig.html#method.to_bytes),
+    /// fields are written as given, so optional fields should be present or absent as required
+    /// by the syntax.  Lengths and other variable-width values are written in the fewest bytes
+    /// possible, and any fill bits following an `AudioSpecificConfig` in the parsed input are
+    /// not reproduced (see [`Verbatim`](../bitstream/struct.Verbatim.html) for reproducing the
+    /// input exactly).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = BitWriter::new();
+        self.write(&mut w);
+        w.into_bytes()
+    }
+
+    pub(crate) fn write(&self, w: &mut BitWriter) {
+        w.write(1, u32::from(self.audio_mux_version));
+        if self.audio_mux_version == 1 {
+            // audioMuxVersionA
+            w.write_bool(false);
+        }
+        if let Some(fullness) = self.tara_buffer_fullness {
+            write_latm_value(w, fullness);
+        }
+        w.write_bool(self.all_streams_same_time_framing);
+        w.write(6, u32::from(self.num_sub_frames));
+        w.write(4, self.programs.len().saturating_sub(1) as u32);
+        for (prog, layers) in self.programs.iter().enumerate() {
+            w.write(3, layers.len().saturating_sub(1) as u32);
+            for (lay, layer) in layers.iter().enumerate() {
+                if prog != 0 || lay != 0 {
+                    w.write_bool(layer.config.is_none());
+                }
+                if let Some(ref asc) = layer.config {
+                    if self.audio_mux_version == 0 {
+                        asc.write(w);
+                    } else {
+                        let mut asc_w = BitWriter::new();
+                        asc.write(&mut asc_w);
+                        let asc_len = asc_w.bit_len();
+                        write_latm_value(w, asc_len as u32);
+                        w.write_bits(&asc_w.into_bytes(), asc_len);
+                    }
+                }
+                w.write(3, u32::from(layer.frame_length.frame_length_type()));
+                match layer.frame_length {
+                    FrameLength::Variable {
+                        latm_buffer_fullness,
+                        core_frame_offset,
+                    } => {
+                        w.write(8, u32::from(latm_buffer_fullness));
+                        if let Some(offset) = core_frame_offset {
+                            w.write(6, u32::from(offset));
+                        }
+                    }
+                    FrameLength::Fixed(frame_length) => w.write(9, u32::from(frame_length)),
+                    FrameLength::Celp { table_index, .. } => w.write(6, u32::from(table_index)),
+                    FrameLength::Hvxc { table_index, .. } => w.write_bool(table_index),
+                    FrameLength::Reserved => (),
+                }
+            }
+        }
+        w.write_bool(self.other_data_len_bits.is_some());
+        if let Some(len) = self.other_data_len_bits {
+            if self.audio_mux_version == 1 {
+                write_latm_value(w, len);
+            } else {
+                let bytes = len.to_be_bytes();
+                let first = bytes.iter().position(|b| *b != 0).unwrap_or(3);
+                for (i, b) in bytes.iter().enumerate().skip(first) {
+                    w.write_bool(i != 3);
+                    w.write(8, u32::from(*b));
+                }
+            }
+        }
+        w.write_bool(self.crc_check_sum.is_some());
+        if let Some(crc) = self.crc_check_sum {
+            w.write(8, u32::from(crc));
+        }
+    }
+
+    /// The configuration of the first layer of the first program (for typical streams, the
+    /// only layer).
+    pub fn audio_specific_config(&self) -> Option<&AudioSpecificConfig> {
+        self.programs.first()?.first()?.config.as_ref()
+    }
+}
+
+impl FromBitstream for StreamMuxConfig {
+    type Context = ();
+    type Error = LatmError;
+
+    fn from_bitstream_with_length(data: &[u8], _: ()) -> Result<(Self, usize), LatmError> {
+        read_counted(data, Self::read)
+    }
+}
+
+impl ToBitstream for StreamMuxConfig {
+    fn to_bitstream(&self) -> RawBits {
+        write_bits(|w| self.write(w))
+    }
+}
+
+fn has_core_frame_offset(
+    audio_object_type: Option<AudioObjectType>,
+    prev_audio_object_type: Option<AudioObjectType>,
+) -> bool {
+    let scalable = matches!(
+        audio_object_type,
+        Some(AudioObjectType::AAC_SCALABLE) | Some(AudioObjectType::ER_AAC_SCALABLE)
+    );
+    let celp_core = matches!(
+        prev_audio_object_type,
+        Some(AudioObjectType::CELP) | Some(AudioObjectType::ER_CELP)
+    );
+    scalable && celp_core
+}
+
+fn read_latm_value(r: &mut BitReader<'_>) -> Result<u32, EndOfData> {
+    let bytes_for_value = r.named("bytesForValue").read_u8(2)?;
+    let mut value = 0;
+    for _ in 0..=bytes_for_value {
+        value = (value << 8) | r.named("valueTmp").read(8)?;
+    }
+    Ok(value)
+}
+
+fn write_latm_value(w: &mut BitWriter, value: u32) {
+    let bytes = value.to_be_bytes();
+    // at most 4 bytes can be signalled
+    let first = bytes.iter().position(|b| *b != 0).unwrap_or(3);
+    w.write(2, (3 - first) as u32);
+    for b in &bytes[first..] {
+        w.write(8, u32::from(*b));
+    }
+}
+
+impl Describe for StreamMuxConfig {
+    fn describe_to(&self, d: &mut Describer<'_, '_>) -> fmt::Result {
+        d.section("StreamMuxConfig", |d| {
+            d.field("Audio mux version", self.audio_mux_version)?;
+            if let Some(fullness) = self.tara_buffer_fullness {
+                d.field("Tara buffer fullness", fullness)?;
+            }
+            d.field(
+                "All streams same time framing",
+                yes_no(self.all_streams_same_time_framing),
+            )?;
+            d.field("Sub-frames", u32::from(self.num_sub_frames) + 1)?;
+            for (prog, layers) in self.programs.iter().enumerate() {
+                d.section(format_args!("Program {}", prog), |d| {
+                    for (lay, layer) in layers.iter().enumerate() {
+                        d.section(format_args!("Layer {}", lay), |d| {
+                            match layer.frame_length {
+                                FrameLength::Variable {
+                                    latm_buffer_fullness,
+                                    ..
+                                } => d.field(
+                                    "Frame length",
+                                    format_args!(
+                                        "variable (buffer fullness {:#04x})",
+                                        latm_buffer_fullness
+                                    ),
+                                )?,
+                                FrameLength::Fixed(len) => {
+                                    d.field("Frame length", format_args!("fixed, {} bytes", len))?
+                                }
+                                ref other => {
+                                    d.field("Frame length type", other.frame_length_type())?
+                                }
+                            }
+                            match layer.config {
+                                Some(ref config) => config.describe_to(d),
+                                None => d.field("AudioSpecificConfig", "same as previous layer"),
+                            }
+                        })?;
+                    }
+                    Ok(())
+                })?;
+            }
+            if let Some(bits) = self.other_data_len_bits {
+                d.field("Other data", format_args!("{} bits", bits))?;
+            }
+            if let Some(crc) = self.crc_check_sum {
+                d.field("CRC", format_args!("{:#04x}", crc))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::Limits;
+
+    #[test]
+    fn version_1_roundtrip() {
+        let asc = AudioSpecificConfig::from_bytes(&[0x11, 0x90]).unwrap();
+        let mut config = StreamMuxConfig::new(asc.clone());
+        config.audio_mux_version = 1;
+        config.tara_buffer_fullness = Some(0xff);
+        config.other_data_len_bits = Some(0x1234);
+        config.crc_check_sum = Some(0xaa);
+        let buf = config.to_bytes();
+        let parsed = StreamMuxConfig::from_bytes(&buf).unwrap();
+        assert_eq!(config, parsed);
+        assert_eq!(Some(&asc), parsed.audio_specific_config());
+    }
+
+    #[test]
+    fn strictness() {
+        // reserved channelConfiguration 9, within a version 1 StreamMuxConfig
+        let asc = AudioSpecificConfig::from_bytes(&[0x11, 0xc8]).unwrap();
+        let mut config = StreamMuxConfig::new(asc);
+        config.audio_mux_version = 1;
+        config.tara_buffer_fullness = Some(0xff);
+        let buf = config.to_bytes();
+        let parsed =
+            StreamMuxConfig::from_bytes_with_options(&buf, &ParseOptions::PERMISSIVE).unwrap();
+        assert_eq!(config, parsed.value);
+        assert_eq!(
+            vec![Violation::ReservedValue(TraceEntry {
+                // the offset within the StreamMuxConfig, not the AudioSpecificConfig
+                bit_offset: 45,
+                bit_length: 4,
+                field: "channelConfiguration",
+                value: Some(9),
+            })],
+            parsed.warnings
+        );
+        assert!(matches!(
+            StreamMuxConfig::from_bytes_with_options(&buf, &ParseOptions::STRICT),
+            Err(LatmError::Violation(_))
+        ));
+    }
+
+    #[test]
+    fn limits() {
+        let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+        let mut config = StreamMuxConfig::new(asc);
+        config.audio_mux_version = 1;
+        config.tara_buffer_fullness = Some(0xff);
+        let layer = Layer {
+            config: None,
+            ..config.programs[0][0].clone()
+        };
+        config.programs[0].push(layer);
+        let buf = config.to_bytes();
+        let limited = |limits| ParseOptions {
+            limits,
+            ..ParseOptions::PERMISSIVE
+        };
+        assert!(StreamMuxConfig::from_bytes_with_options(&buf, &limited(Limits::DEFAULT)).is_ok());
+        assert_eq!(
+            Err(LatmError::TooManyLayers(TraceEntry {
+                bit_offset: 23,
+                bit_length: 3,
+                field: "numLayer",
+                value: Some(1),
+            })),
+            StreamMuxConfig::from_bytes_with_options(
+                &buf,
+                &limited(Limits {
+                    max_latm_layers: 1,
+                    ..Limits::DEFAULT
+                })
+            )
+        );
+        assert_eq!(
+            Err(LatmError::LengthLimitExceeded(TraceEntry {
+                bit_offset: 28,
+                bit_length: 8,
+                field: "valueTmp",
+                value: Some(16),
+            })),
+            StreamMuxConfig::from_bytes_with_options(
+                &buf,
+                &limited(Limits {
+                    max_explicit_length: 15,
+                    ..Limits::DEFAULT
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn visitor() {
+        use core::ops::ControlFlow;
+
+        /// Collects the fields up to the first `channelConfiguration`.
+        struct UpToChannelConfiguration(Vec<TraceEntry>);
+
+        impl AscVisitor for UpToChannelConfiguration {
+            fn field(&mut self, entry: TraceEntry) -> ControlFlow<()> {
+                self.0.push(entry);
+                if entry.field == "channelConfiguration" {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            }
+        }
+
+        let asc = AudioSpecificConfig::from_bytes(&[0x11, 0x90]).unwrap();
+        let mut config = StreamMuxConfig::new(asc);
+        config.audio_mux_version = 1;
+        config.tara_buffer_fullness = Some(0xff);
+        let buf = config.to_bytes();
+        let mut visitor = UpToChannelConfiguration(vec![]);
+        assert_eq!(Ok(()), StreamMuxConfig::parse_with(&buf, &mut visitor));
+        // the fields of the AudioSpecificConfig are given at their offsets within the
+        // StreamMuxConfig
+        assert_eq!(
+            Some(&TraceEntry {
+                bit_offset: 45,
+                bit_length: 4,
+                field: "channelConfiguration",
+                value: Some(2),
+            }),
+            visitor.0.last()
+        );
+        let mut visitor = UpToChannelConfiguration(vec![]);
+        assert!(matches!(
+            StreamMuxConfig::parse_with(&buf[..4], &mut visitor),
+            Err(LatmError::NotEnoughData(_))
+        ));
+    }
+
+    #[test]
+    fn version_0_other_data() {
+        let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+        let mut config = StreamMuxConfig::new(asc);
+        config.other_data_len_bits = Some(0x1_0000);
+        assert_eq!(
+            config,
+            StreamMuxConfig::from_bytes(&config.to_bytes()).unwrap()
+        );
+        assert_eq!(
+            Err(LatmError::UnsupportedVersion(TraceEntry {
+                bit_offset: 1,
+                bit_length: 1,
+                field: "audioMuxVersionA",
+                value: Some(1),
+            })),
+            StreamMuxConfig::from_bytes(&[0xc0])
+        );
+        assert_eq!(
+            Err(LatmError::NotEnoughData(TraceEntry {
+                bit_offset: 31,
+                bit_length: 3,
+                field: "frameLengthType",
+                value: None,
+            })),
+            StreamMuxConfig::from_bytes(&[0x40, 0x00, 0x24, 0x20])
+        );
+    }
+}
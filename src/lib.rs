@@ -4,20 +4,239 @@
 //! Currently supported,
 //!
 //!  - [`AudioObjectType`](struct.AudioObjectType.html)
+//!  - [`SamplingFrequencyIndex`](struct.SamplingFrequencyIndex.html)
+//!  - [`ChannelConfiguration`](struct.ChannelConfiguration.html)
+//!  - [`AudioProfileLevelIndication`](struct.AudioProfileLevelIndication.html)
+//!  - [`AudioSpecificConfig`](asc/struct.AudioSpecificConfig.html) parsing and serialization
+//!  - RFC 6381 [codec strings](codec_string/index.html)
+//!  - MP4 [`esds`](esds/index.html) descriptors
+//!  - [MPEG-DASH](dash/index.html) signalling helpers, including for xHE-AAC and
+//!    `AudioChannelConfiguration` descriptors
+//!  - [HLS](hls/index.html) `CODECS` and `CHANNELS` attribute values
+//!  - [SDP](sdp/index.html) `fmtp` parameters for RTP payloads, and RFC 3640
+//!    [AU headers](rtp/index.html)
+//!  - [MPEG-TS](ts/index.html) descriptors, including the DVB `AAC_descriptor`
+//!  - [FLV / RTMP](flv/index.html) AAC sequence headers
+//!  - [Matroska](matroska/index.html) `CodecPrivate` values
+//!  - Windows Media Foundation [`HEAACWAVEINFO`](heaacwaveinfo/index.html) structures
+//!  - Windows [speaker position masks](channel_mask/index.html) for `WAVEFORMATEXTENSIBLE`
+//!  - [MIME types](mime/index.html) for each transport format
+//!  - [LATM](latm/index.html) `StreamMuxConfig` parsing and serialization
+//!  - [ADTS](adts/index.html) frame headers, stream duration / bitrate estimation and seek indexing
+//!  - [Program config elements](pce/index.html), including detection of in-band PCEs and
+//!    best-effort channel layout inference for
+//!    [`raw_data_block()`](raw_data_block/index.html) payloads
+//!  - [SBR headers](sbr/index.html), including those found in fill elements
+//!
+//! Optional features provide interoperability helpers,
+//!
+//!  - `arbitrary`: `Arbitrary` for the value types and for `AudioSpecificConfig`, `AdtsHeader`
+//!    and `StreamMuxConfig`, generating only valid values, for use in fuzz targets
+//!  - `borsh`: `BorshSerialize` and `BorshDeserialize` for the value types, as their numeric
+//!    values (implies `alloc`)
+//!  - `bytemuck`: `CheckedBitPattern` and `NoUninit` for `AudioObjectType`,
+//!    `SamplingFrequencyIndex` and `ChannelConfiguration` (so that casts from bytes are
+//!    validated), and `Pod` for `AudioProfileLevelIndication`
+//!  - `adts-reader`: [conversions](adts_reader/index.html) to and from the types of the
+//!    adts-reader crate
+//!  - `mpeg2ts-reader`: [AAC stream and descriptor](mpeg2ts_reader/index.html) support for
+//!    the mpeg2ts-reader crate
+//!  - `names` (enabled by default): the names and descriptions of the value types'
+//!    constants, as used by their `Debug` and `Display` implementations and by
+//!    `AudioObjectType`'s `FromStr` implementation; without this feature, values are formatted
+//!    numerically, saving the space taken by the strings in size-constrained firmware
+//!  - `capi`: a [C API](capi/index.html) for `AudioObjectType` validation, and
+//!    `AudioSpecificConfig` and ADTS header parsing, for use via cbindgen-generated headers
+//!  - `clap`: clap `ValueEnum` for `AudioObjectType` and `AudioProfileLevelIndication`,
+//!    accepting kebab-case constant names (e.g. `aac-lc`, or `he-aac` and `he-aac-v2` for
+//!    `SBR` and `PS`) as command-line arguments
+//!  - `diesel`: diesel `ToSql` and `FromSql` for `AudioObjectType` and `ChannelConfiguration`,
+//!    as `SmallInt` values validated on load
+//!  - `defmt`: `defmt::Format` for the value types, error types and parsed structures, for
+//!    logging on embedded targets
+//!  - `gstreamer`: [GStreamer caps](gstreamer/index.html) strings
+//!  - `ffmpeg`: [FFmpeg AAC profile](ffmpeg/index.html) mappings
+//!  - `fdk-aac`: [fdk-aac enum value](fdk_aac/index.html) mappings
+//!  - `audio-toolbox`: [Apple AudioToolbox format ID and channel layout tag](audio_toolbox/index.html)
+//!    mappings
+//!  - `android`: [Android MediaCodec AAC profile](android/index.html) mappings
+//!  - `openmax`: [OpenMAX IL AAC profile and stream format](openmax/index.html) mappings
+//!  - `proptest`: [proptest strategies](proptest/index.html) generating valid values
+//!  - `quickcheck`: quickcheck `Arbitrary` for the value types, generating valid values
+//!  - `python`: a [PyO3 module](python/index.html) exposing the value types, and
+//!    `AudioSpecificConfig` and `AdtsHeader` parsing
+//!  - `rand`: rand `Distribution` support (via `StandardUniform`) generating defined
+//!    `AudioObjectType`, `SamplingFrequencyIndex` and `ChannelConfiguration` values, and small
+//!    AAC `AudioSpecificConfig`s
+//!  - `rkyv`: rkyv `Archive`, `Serialize` and `Deserialize` for the value types and the parsed
+//!    structures, with archived value types validated as by their `TryFrom<u8>`
+//!    implementations
+//!  - `serde`: `Serialize` and `Deserialize` for `AudioObjectType`, `SamplingFrequencyIndex`,
+//!    `ChannelConfiguration` and `AudioProfileLevelIndication`, as their numeric values (or
+//!    optionally [as names](serde_names/index.html)), and for the parsed structures such as
+//!    `AudioSpecificConfig`, `AdtsHeader` and `StreamMuxConfig`
+//!  - `schemars`: `JsonSchema` for the same types (implies `serde`), describing their
+//!    serialized form
+//!  - `sqlx`: sqlx `Type`, `Encode` and `Decode` for `AudioObjectType` and
+//!    `ChannelConfiguration`, as `SMALLINT` values validated on load
+//!  - `symphonia`: [Symphonia codec parameters](symphonia/index.html)
+//!  - `wasm`: [wasm-bindgen bindings](wasm/index.html) for `AudioSpecificConfig` parsing,
+//!    codec strings and ADTS probing from JavaScript
+//!  - `zerocopy`: `IntoBytes`, `Immutable`, `KnownLayout` and `Unaligned` for the value types,
+//!    and `FromBytes` for `AudioProfileLevelIndication`; the other value types can't accept
+//!    every byte value, so are read with `TryFrom<u8>` (or the `bytemuck` feature's checked
+//!    casts)
+//!
+//! The `ffmpeg`, `fdk-aac`, `audio-toolbox`, `android` and `openmax` mappings define the
+//! foreign constants' values themselves rather than taking them from bindings, so these
+//! features add no dependency on the libraries or SDKs concerned.
+//!
+//! The crate is `no_std` when the default `std` feature is disabled.  Without `std`, the
+//! `AdtsHeader` reading functions taking `std::io::Read` are unavailable, and without `alloc`
+//! (which `std` implies) only the value types, ADTS header parsing, SBR header parsing, the
+//! speaker mask conversions and the allocation-free
+//! [`FixedAudioSpecificConfig`](asc/struct.FixedAudioSpecificConfig.html) remain, since the
+//! other structures hold variable-length data.
+//! All of the crate's error types implement `core::error::Error` (and so
+//! `std::error::Error`) with or without `std`, with the error of an enclosed structure given
+//! as the `source()`.
 
-use std::convert::TryFrom;
-use std::fmt;
+// the test harness needs std anyway, so tests are free to use it
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::convert::TryFrom;
+use core::fmt;
+
+pub mod adts;
+#[cfg(feature = "adts-reader")]
+pub mod adts_reader;
+#[cfg(feature = "android")]
+pub mod android;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+pub mod asc;
+#[cfg(feature = "audio-toolbox")]
+pub mod audio_toolbox;
+mod bits;
+#[cfg(feature = "alloc")]
+pub mod bitstream;
+#[cfg(feature = "borsh")]
+mod borsh_impls;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_impls;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod channel_mask;
+#[cfg(feature = "clap")]
+mod clap_impls;
+#[cfg(feature = "alloc")]
+pub mod codec_string;
+#[cfg(feature = "alloc")]
+pub mod dash;
+mod describe;
+#[cfg(feature = "alloc")]
+pub mod esds;
+#[cfg(feature = "fdk-aac")]
+pub mod fdk_aac;
+#[cfg(feature = "ffmpeg")]
+pub mod ffmpeg;
+#[cfg(feature = "alloc")]
+pub mod flv;
+#[cfg(feature = "gstreamer")]
+pub mod gstreamer;
+#[cfg(feature = "alloc")]
+pub mod heaacwaveinfo;
+#[cfg(feature = "alloc")]
+pub mod hls;
+#[cfg(feature = "alloc")]
+pub mod latm;
+#[cfg(feature = "alloc")]
+pub mod matroska;
+#[cfg(feature = "alloc")]
+pub mod mime;
+#[cfg(feature = "mpeg2ts-reader")]
+pub mod mpeg2ts_reader;
+#[cfg(feature = "openmax")]
+pub mod openmax;
+pub mod options;
+#[cfg(feature = "alloc")]
+pub mod pce;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impls;
+#[cfg(feature = "rand")]
+mod rand_impls;
+#[cfg(feature = "alloc")]
+pub mod raw_data_block;
+#[cfg(feature = "rkyv")]
+mod rkyv_impls;
+#[cfg(feature = "alloc")]
+pub mod rtp;
+pub mod sbr;
+#[cfg(feature = "schemars")]
+mod schemars_impls;
+#[cfg(feature = "alloc")]
+pub mod sdp;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "serde")]
+pub mod serde_names;
+#[cfg(any(feature = "sqlx", feature = "diesel"))]
+mod sql_impls;
+#[cfg(feature = "symphonia")]
+pub mod symphonia;
+pub mod trace;
+#[cfg(feature = "alloc")]
+pub mod ts;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// Represents an error converting a `u8` into an `AudioObjectType`
 #[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum AudioObjectTypeError {
     /// Tried to convert the 'escape value', `31`, into an `AudioObjectType` (this is not a legitimate
     /// AOT value but instead is used as part of encoding the field value.
     EscapeValue,
     /// Only values 95 and under can be legitimate Audio Object Types.
     TooLarge(u8),
+    /// The value is reserved in the spec (only returned by
+    /// [`AudioObjectType::try_from_defined()`](struct.AudioObjectType.html#method.try_from_defined)).
+    Reserved(u8),
+    /// A value given as a wider integer type is outside the range of a `u8`.
+    OutOfRange,
+}
+
+impl fmt::Display for AudioObjectTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioObjectTypeError::EscapeValue => write!(
+                f,
+                "audio object type value {} is the escape value",
+                AOT_ESCAPE_VALUE
+            ),
+            AudioObjectTypeError::TooLarge(v) => {
+                write!(f, "audio object type value {} is greater than 95", v)
+            }
+            AudioObjectTypeError::Reserved(v) => {
+                write!(f, "audio object type value {} is reserved", v)
+            }
+            AudioObjectTypeError::OutOfRange => {
+                f.write_str("audio object type value is outside the range of a u8")
+            }
+        }
+    }
 }
 
+impl core::error::Error for AudioObjectTypeError {}
+
 /// Represents an
 /// [audio object type](https://en.wikipedia.org/wiki/MPEG-4_Part_3#MPEG-4_Audio_Object_Types)
 /// indicator value.
@@ -26,16 +245,19 @@ pub enum AudioObjectTypeError {
 ///
 /// ```rust
 /// # use mpeg4_audio_const::*;
-/// # use std::convert::TryFrom;
+/// # use core::convert::TryFrom;
 /// assert_eq!(AudioObjectType::AAC_LC, AudioObjectType::try_from(2).unwrap());
-/// assert_eq!(2u8, AudioObjectType::AAC_LC.into());
+/// let value: u8 = AudioObjectType::AAC_LC.into();
+/// assert_eq!(2, value);
+/// assert!(value == AudioObjectType::AAC_LC);
 /// ```
 ///
 /// and will accept values that are 'reserved' in the spec,
 ///
 /// ```rust
 /// # use mpeg4_audio_const::*;
-/// # use std::convert::TryFrom;
+/// # use core::convert::TryFrom;
+/// # #[cfg(feature = "names")]
 /// assert_eq!("RESERVED(95)", format!("{:?}", AudioObjectType::try_from(95).unwrap()));
 /// ```
 ///
@@ -46,34 +268,350 @@ pub enum AudioObjectTypeError {
 ///
 /// ```rust
 /// # use mpeg4_audio_const::*;
-/// # use std::convert::TryFrom;
+/// # use core::convert::TryFrom;
 /// assert_eq!(Err(AudioObjectTypeError::EscapeValue), AudioObjectType::try_from(31));
 /// assert_eq!(Err(AudioObjectTypeError::TooLarge(97)), AudioObjectType::try_from(97));
 /// ```
-#[derive(Eq, PartialEq, Copy, Clone)]
+///
+/// This is a wrapper around `u8` rather than a fieldless `enum` so that values reserved by the
+/// current edition of the spec (which may be assigned by later editions, and which do appear in
+/// real-world streams) survive parsing and re-serialization unchanged.  For exhaustive
+/// `match`ing over the defined values, use [`kind()`](#method.kind) to obtain an
+/// [`AudioObjectTypeKind`](enum.AudioObjectTypeKind.html).
+///
+/// Values are ordered by their numeric value, and the default is
+/// [`NULL`](#associatedconstant.NULL).
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Default)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(frozen, eq, from_py_object, module = "mpeg4_audio_const")
+)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::SmallInt)
+)]
 pub struct AudioObjectType(u8);
 
 /// This value, `31`, is not used as an _audio object type_, but is instead used in the encoding of
 /// any _audio object type_ value greater than or equal to `32`.
 pub const AOT_ESCAPE_VALUE: u8 = 0b_11111;
 
+/// The largest _audio object type_ value that can be encoded, `95` (the escape value followed by
+/// the largest value of [`AOT_EXT_FIELD_BITS`](constant.AOT_EXT_FIELD_BITS.html) bits).
+pub const AOT_VALUE_MAX: u8 = 32 + (1 << AOT_EXT_FIELD_BITS) - 1;
+
+/// The width of the `audioObjectType` field, `5` bits.
+pub const AOT_FIELD_BITS: u32 = 5;
+
+/// The width of the `audioObjectTypeExt` field, `6` bits, which follows the `audioObjectType`
+/// field when that holds [`AOT_ESCAPE_VALUE`](constant.AOT_ESCAPE_VALUE.html), and gives the
+/// value less `32`.
+pub const AOT_EXT_FIELD_BITS: u32 = 6;
+
 impl From<AudioObjectType> for u8 {
     fn from(v: AudioObjectType) -> Self {
         v.0
     }
 }
+impl AudioObjectType {
+    /// A `const` equivalent of `TryFrom<u8>`, giving `None` for the values that it rejects, for
+    /// use where a `const` value is needed (e.g. in lookup tables).
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// const HE_AAC: Option<AudioObjectType> = AudioObjectType::from_u8_const(5);
+    /// assert_eq!(Some(AudioObjectType::SBR), HE_AAC);
+    /// assert_eq!(None, AudioObjectType::from_u8_const(AOT_ESCAPE_VALUE));
+    /// ```
+    pub const fn from_u8_const(value: u8) -> Option<AudioObjectType> {
+        match value {
+            AOT_ESCAPE_VALUE => None,
+            v if v > AOT_VALUE_MAX => None,
+            _ => Some(AudioObjectType(value)),
+        }
+    }
+
+    /// A `const` equivalent of `u8::from()`.
+    pub const fn to_u8_const(self) -> u8 {
+        self.0
+    }
+
+    /// `true` if this value is encoded using the escape value, i.e. is `32` or greater.
+    pub const fn is_escape_coded(self) -> bool {
+        self.0 > AOT_ESCAPE_VALUE
+    }
+
+    /// The number of bits used to encode this value in an `AudioSpecificConfig`, being either
+    /// [`AOT_FIELD_BITS`](constant.AOT_FIELD_BITS.html) or, for values encoded using the escape
+    /// value, that plus [`AOT_EXT_FIELD_BITS`](constant.AOT_EXT_FIELD_BITS.html).
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(5, AudioObjectType::AAC_LC.encoded_bit_length());
+    /// assert_eq!(11, AudioObjectType::USAC.encoded_bit_length());
+    /// ```
+    pub const fn encoded_bit_length(self) -> u32 {
+        if self.is_escape_coded() {
+            AOT_FIELD_BITS + AOT_EXT_FIELD_BITS
+        } else {
+            AOT_FIELD_BITS
+        }
+    }
+
+    /// Iterates over every value that the spec defines, as listed in
+    /// [`ALL`](#associatedconstant.ALL).
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert!(AudioObjectType::iter_defined().any(|aot| aot == AudioObjectType::USAC));
+    /// ```
+    pub fn iter_defined() -> impl Iterator<Item = AudioObjectType> {
+        AudioObjectType::ALL.iter().copied()
+    }
+
+    /// `true` if the spec defines this value (i.e. it has a constant).
+    pub const fn is_defined(self) -> bool {
+        !matches!(self.kind(), AudioObjectTypeKind::Reserved(_))
+    }
+
+    /// `true` if this value is reserved in the spec, so that no constant is defined for it.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// # use core::convert::TryFrom;
+    /// assert!(!AudioObjectType::AAC_LC.is_reserved());
+    /// assert!(AudioObjectType::try_from(18).unwrap().is_reserved());
+    /// ```
+    pub const fn is_reserved(self) -> bool {
+        !self.is_defined()
+    }
+
+    /// A stricter alternative to `TryFrom<u8>`, additionally rejecting the values which the spec
+    /// reserves, for use when validating data that must use only the defined object types.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Ok(AudioObjectType::AAC_LC), AudioObjectType::try_from_defined(2));
+    /// assert_eq!(Err(AudioObjectTypeError::Reserved(18)), AudioObjectType::try_from_defined(18));
+    /// assert_eq!(Err(AudioObjectTypeError::EscapeValue), AudioObjectType::try_from_defined(31));
+    /// ```
+    pub fn try_from_defined(value: u8) -> Result<AudioObjectType, AudioObjectTypeError> {
+        let aot = AudioObjectType::try_from(value)?;
+        if aot.is_reserved() {
+            Err(AudioObjectTypeError::Reserved(value))
+        } else {
+            Ok(aot)
+        }
+    }
+
+    /// Creates a value without the check performed by `TryFrom<u8>`, for static tables and
+    /// performance-critical code where the value is known to be valid.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// // SAFETY: 2 is neither the escape value nor too large
+    /// const AAC_LC: AudioObjectType = unsafe { AudioObjectType::new_unchecked(2) };
+    /// assert_eq!(AudioObjectType::AAC_LC, AAC_LC);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The value must be one that `TryFrom<u8>` accepts, i.e. not the escape value
+    /// [`AOT_ESCAPE_VALUE`](constant.AOT_ESCAPE_VALUE.html) and no greater than `95`.  Other
+    /// code (including `unsafe` code, such as zero-copy casts) may rely on this.  The
+    /// condition is checked by a debug assertion.
+    pub const unsafe fn new_unchecked(value: u8) -> AudioObjectType {
+        debug_assert!(value != AOT_ESCAPE_VALUE && value <= AOT_VALUE_MAX);
+        AudioObjectType(value)
+    }
+
+    /// The RFC 6381 codec string identifying this object type within the MPEG-4 Audio
+    /// _object type indication_ `0x40`, as used in HLS `CODECS` attributes, DASH `codecs`
+    /// attributes and MSE `isTypeSupported()` checks.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!("mp4a.40.2", AudioObjectType::AAC_LC.codec_string());
+    /// assert_eq!("mp4a.40.42", AudioObjectType::USAC.codec_string());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn codec_string(&self) -> alloc::string::String {
+        alloc::format!("mp4a.40.{}", self.0)
+    }
+}
+
 impl TryFrom<u8> for AudioObjectType {
     type Error = AudioObjectTypeError;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             AOT_ESCAPE_VALUE => Err(AudioObjectTypeError::EscapeValue),
-            96..=255 => Err(AudioObjectTypeError::TooLarge(value)),
+            v if v > AOT_VALUE_MAX => Err(AudioObjectTypeError::TooLarge(value)),
             _ => Ok(AudioObjectType(value)),
         }
     }
 }
 
+/// `true` if `name` is the given constant name, ignoring case and treating `-` as `_`.
+#[cfg(feature = "names")]
+fn const_name_matches(name: &str, const_name: &str) -> bool {
+    name.len() == const_name.len()
+        && name
+            .bytes()
+            .zip(const_name.bytes())
+            .all(|(a, b)| a.to_ascii_uppercase() == b || (a == b'-' && b == b'_'))
+}
+
+/// Implements the traits and methods that every value type shares, so that their coverage
+/// doesn't drift apart:
+///
+///  - comparison with raw `u8` field values in both directions, so that e.g.
+///    `field == AudioObjectType::AAC_LC` needs no conversion
+///  - conversions to and from the wider integer types in which container formats and foreign
+///    APIs often give the values, with the same checks as the conversion from `u8` (supporting
+///    `i32` also keeps e.g. `AudioObjectType::try_from(2)` compiling, since an integer literal
+///    that can't otherwise be inferred is an `i32`)
+///  - `Debug` and `defmt::Format` giving the constant name from the type's `name()`, e.g.
+///    `AAC_LC(2)`, and the crate-internal `from_const_name()` lookup.  Without the `names`
+///    feature, the values are formatted as just their numeric value (e.g.
+///    `AudioObjectType(2)`), so that the constant names need not be stored.
+///
+/// Each type is given with the error for values outside the range of a `u8`.
+///
+/// The `@display` form implements `Display` from the type's `label()`, e.g. `AAC LC`, preceded
+/// by the numeric value in the alternate form, and `Reserved (n)` for reserved values.  The
+/// `@from_str` form implements `FromStr` accepting the constant names and the labels, ignoring
+/// case, plus whatever the optional function given after the error accepts.  Both take the
+/// attributes (docs and `cfg`) for the implementation.
+macro_rules! implement_value_type {
+    ( $( $t:ident : $err:ty = $out_of_range:expr ),* $(,)? ) => {
+        $(
+            impl PartialEq<u8> for $t {
+                fn eq(&self, other: &u8) -> bool {
+                    self.0 == *other
+                }
+            }
+
+            impl PartialEq<$t> for u8 {
+                fn eq(&self, other: &$t) -> bool {
+                    *self == other.0
+                }
+            }
+
+            implement_value_type!(@wider $t $err, $out_of_range, u16, u32, usize, i32);
+
+            #[cfg(feature = "names")]
+            impl $t {
+                /// The value whose constant has the given name, ignoring case and treating `-`
+                /// as `_`.
+                pub(crate) fn from_const_name(name: &str) -> Option<$t> {
+                    (0..=u8::MAX)
+                        .filter_map(|v| $t::try_from(v).ok())
+                        .find(|v| v.name().is_some_and(|c| const_name_matches(name, c)))
+                }
+            }
+
+            #[cfg(feature = "names")]
+            impl fmt::Debug for $t {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    if f.alternate() {
+                        return fmt::Display::fmt(self, f);
+                    }
+                    match self.name() {
+                        Some(name) => write!(f, "{}({})", name, self.0),
+                        None => write!(f, "RESERVED({})", self.0),
+                    }
+                }
+            }
+
+            #[cfg(not(feature = "names"))]
+            impl fmt::Debug for $t {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}({})", stringify!($t), self.0)
+                }
+            }
+
+            #[cfg(all(feature = "defmt", feature = "names"))]
+            impl defmt::Format for $t {
+                fn format(&self, f: defmt::Formatter<'_>) {
+                    match self.name() {
+                        Some(name) => defmt::write!(f, "{=str}({=u8})", name, self.0),
+                        None => defmt::write!(f, "RESERVED({=u8})", self.0),
+                    }
+                }
+            }
+
+            #[cfg(all(feature = "defmt", not(feature = "names")))]
+            impl defmt::Format for $t {
+                fn format(&self, f: defmt::Formatter<'_>) {
+                    defmt::write!(f, "{=str}({=u8})", stringify!($t), self.0)
+                }
+            }
+        )*
+    };
+    (@wider $t:ident $err:ty, $out_of_range:expr, $( $w:ty ),* ) => {
+        $(
+            impl TryFrom<$w> for $t {
+                type Error = $err;
+
+                fn try_from(value: $w) -> Result<Self, Self::Error> {
+                    match u8::try_from(value) {
+                        Ok(value) => $t::try_from(value).map_err(Into::into),
+                        Err(_) => Err($out_of_range),
+                    }
+                }
+            }
+
+            impl From<$t> for $w {
+                fn from(v: $t) -> Self {
+                    <$w>::from(v.0)
+                }
+            }
+        )*
+    };
+    (@display $( #[$attr:meta] )* $t:ident ) => {
+        $( #[$attr] )*
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.label() {
+                    Some(label) if f.alternate() => write!(f, "{} ({})", self.0, label),
+                    Some(label) => f.write_str(label),
+                    None => write!(f, "Reserved ({})", self.0),
+                }
+            }
+        }
+    };
+    (@from_str $( #[$attr:meta] )* $t:ident : $err:ident $( , $alias:path )? ) => {
+        $( #[$attr] )*
+        #[cfg(feature = "names")]
+        impl core::str::FromStr for $t {
+            type Err = $err;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $t::from_const_name(s)
+                    .or_else(|| {
+                        (0..=u8::MAX)
+                            .filter_map(|v| $t::try_from(v).ok())
+                            .find(|v| v.label().is_some_and(|label| label.eq_ignore_ascii_case(s)))
+                    })
+                    $( .or_else(|| $alias(s)) )?
+                    .ok_or($err)
+            }
+        }
+    };
+}
+
 macro_rules! implement_aot {
     (
         $( $tag:literal $id:ident $desc:literal ),* ,
@@ -86,13 +624,132 @@ macro_rules! implement_aot {
             )*
         }
 
-        impl fmt::Debug for AudioObjectType {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        impl AudioObjectType {
+            /// Every value that the spec defines (i.e. that has a constant), in ascending order.
+            ///
+            /// ```rust
+            /// # use mpeg4_audio_const::*;
+            /// assert_eq!(Some(&AudioObjectType::NULL), AudioObjectType::ALL.first());
+            /// assert!(AudioObjectType::ALL.iter().all(|aot| aot.is_defined()));
+            /// ```
+            pub const ALL: &'static [AudioObjectType] = &[
+                $( AudioObjectType($tag) ),*
+            ];
+        }
+
+        #[cfg(feature = "names")]
+        impl AudioObjectType {
+            /// The name of the constant for this value, or `None` if the value is reserved.
+            ///
+            /// ```rust
+            /// # use mpeg4_audio_const::*;
+            /// # use core::convert::TryFrom;
+            /// assert_eq!(Some("ER_AAC_ELD"), AudioObjectType::ER_AAC_ELD.name());
+            /// assert_eq!(None, AudioObjectType::try_from(95).unwrap().name());
+            /// ```
+            pub const fn name(self) -> Option<&'static str> {
+                match AOT_NAMES[self.0 as usize] {
+                    Some((name, _)) => Some(name),
+                    None => None,
+                }
+            }
+
+            /// The name of this object type as given in the spec (as also produced by the
+            /// `Display` implementation), or `None` if the value is reserved.
+            ///
+            /// ```rust
+            /// # use mpeg4_audio_const::*;
+            /// assert_eq!(Some("ER AAC ELD"), AudioObjectType::ER_AAC_ELD.description());
+            /// ```
+            pub const fn description(self) -> Option<&'static str> {
+                match AOT_NAMES[self.0 as usize] {
+                    Some((_, description)) => Some(description),
+                    None => None,
+                }
+            }
+        }
+
+        #[cfg(feature = "names")]
+        impl AudioObjectType {
+            /// The text that `Display` gives for this value, or `None` if the value is reserved.
+            fn label(self) -> Option<&'static str> {
+                self.description()
+            }
+        }
+
+        /// The constant name and spec name of each audio object type, indexed by value (or
+        /// `None` for reserved values and the escape value), for use in `const` contexts and
+        /// static lookup tables.
+        ///
+        /// ```rust
+        /// # use mpeg4_audio_const::*;
+        /// const LC: Option<(&str, &str)> = AOT_NAMES[2];
+        /// assert_eq!(Some(("AAC_LC", "AAC LC")), LC);
+        /// ```
+        #[cfg(feature = "names")]
+        pub const AOT_NAMES: [Option<(&str, &str)>; AOT_VALUE_MAX as usize + 1] = {
+            let mut names = [None; AOT_VALUE_MAX as usize + 1];
+            $( names[$tag] = Some((stringify!($id), $desc)); )*
+            names
+        };
+
+        /// An enumeration of the audio object types, having one variant per
+        /// [`AudioObjectType`](struct.AudioObjectType.html) constant plus `Reserved` for the
+        /// remaining values, so that code can `match` exhaustively over the defined types.
+        ///
+        /// ```rust
+        /// # use mpeg4_audio_const::*;
+        /// # use core::convert::TryFrom;
+        /// fn is_he_aac(aot: AudioObjectType) -> bool {
+        ///     match aot.kind() {
+        ///         AudioObjectTypeKind::SBR | AudioObjectTypeKind::PS => true,
+        ///         _ => false,
+        ///     }
+        /// }
+        /// assert!(is_he_aac(AudioObjectType::PS));
+        /// assert_eq!(AudioObjectTypeKind::Reserved(95), AudioObjectType::try_from(95).unwrap().kind());
+        /// assert_eq!(Ok(AudioObjectType::AAC_LC), AudioObjectType::try_from(AudioObjectTypeKind::AAC_LC));
+        /// ```
+        #[allow(non_camel_case_types)]
+        #[derive(Eq, PartialEq, Copy, Clone, Debug)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        pub enum AudioObjectTypeKind {
+            $(
+                #[doc=$desc]
+                $id,
+            )*
+            /// A value that the spec reserves.  Converting back to an `AudioObjectType` fails for
+            /// the values that `TryFrom<u8>` rejects.
+            Reserved(u8),
+        }
+
+        impl AudioObjectType {
+            /// The [`AudioObjectTypeKind`](enum.AudioObjectTypeKind.html) variant for this value.
+            pub const fn kind(self) -> AudioObjectTypeKind {
                 match self.0 {
                     $(
-                        $tag => write!(f, "{}({})", stringify!($id), $tag)
+                        $tag => AudioObjectTypeKind::$id
+                    ),* ,
+                    _ => AudioObjectTypeKind::Reserved(self.0),
+                }
+            }
+        }
+
+        impl From<AudioObjectType> for AudioObjectTypeKind {
+            fn from(v: AudioObjectType) -> Self {
+                v.kind()
+            }
+        }
+
+        impl TryFrom<AudioObjectTypeKind> for AudioObjectType {
+            type Error = AudioObjectTypeError;
+
+            fn try_from(kind: AudioObjectTypeKind) -> Result<Self, Self::Error> {
+                match kind {
+                    $(
+                        AudioObjectTypeKind::$id => Ok(AudioObjectType($tag))
                     ),* ,
-                    _ => write!(f, "RESERVED({})", self.0),
+                    AudioObjectTypeKind::Reserved(v) => AudioObjectType::try_from(v),
                 }
             }
         }
@@ -102,14 +759,14 @@ macro_rules! implement_aot {
 implement_aot! {
     0 NULL "Null",
     1 AAC_MAIN "AAC main",
+    2 AAC_LC "AAC LC",
     3 AAC_SSR "AAC SSR",
     4 AAC_LTP "AAC LTP",
+    5 SBR "SBR",
+    6 AAC_SCALABLE "AAC Scalable",
     7 TWIN_VQ "TwinVQ",
     8 CELP "CELP",
     9 HVXC "HVXC",
-    5 SBR "SBR",
-    6 AAC_SCALABLE "AAC Scalable",
-    2 AAC_LC "AAC LC",
     12 TTSI "TTSI",
     13 MAIN_SYNTHETIC "Main synthetic",
     14 WAVETABLE_SYNTHESIS "Wavetable synthesis",
@@ -130,6 +787,7 @@ implement_aot! {
     30 MPEG_SURROUND "MPEG Surround",
     // 31 - 'escape' - deliberately skipped
     32 LAYER1 "Layer-1",
+    33 LAYER2 "Layer-2",
     34 LAYER3 "Layer-3",
     35 DST "DST",
     36 ALS "ALS",
@@ -145,16 +803,1843 @@ implement_aot! {
     46 AUDIO_SYNC "Audio synchronization tool",
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::convert::TryFrom;
+implement_value_type! {
+    @display
+    /// Formats the name of the object type as given in the spec, e.g. `AAC LC`, or
+    /// `Reserved (n)` for reserved values (see
+    /// [`with_labels()`](struct.AudioObjectType.html#method.with_labels) for naming them).
+    /// The alternate form (`{:#}`, which `{:#?}` also gives) is preceded by the numeric
+    /// value.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!("ER AAC ELD", AudioObjectType::ER_AAC_ELD.to_string());
+    /// assert_eq!("2 (AAC LC)", format!("{:#}", AudioObjectType::AAC_LC));
+    /// ```
+    #[cfg(feature = "names")]
+    AudioObjectType
+}
 
-    #[test]
-    fn escape_value() {
-        assert_eq!(
-            Err(AudioObjectTypeError::EscapeValue),
-            AudioObjectType::try_from(AOT_ESCAPE_VALUE)
-        );
+#[cfg(feature = "names")]
+impl AudioObjectType {
+    /// Formats this value using the given names for reserved values which an application uses
+    /// experimentally (e.g. in a private deployment), in place of `RESERVED` (for `Debug`) and
+    /// `Reserved` (for `Display`).  Labels given for defined values are ignored.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// # use core::convert::TryFrom;
+    /// const LABELS: &[(u8, &str)] = &[(93, "ACME_LOSSLESS")];
+    /// let aot = AudioObjectType::try_from(93).unwrap();
+    /// assert_eq!("ACME_LOSSLESS(93)", format!("{:?}", aot.with_labels(LABELS)));
+    /// assert_eq!("RESERVED(93)", format!("{:?}", aot));
+    /// assert_eq!(None, aot.name());
+    /// ```
+    pub fn with_labels<'a>(self, labels: &'a [(u8, &'a str)]) -> WithLabels<'a> {
+        WithLabels { aot: self, labels }
+    }
+}
+
+/// An [`AudioObjectType`](struct.AudioObjectType.html) formatted with an application's labels
+/// for reserved values, as returned by
+/// [`with_labels()`](struct.AudioObjectType.html#method.with_labels)
+#[cfg(feature = "names")]
+#[derive(Clone, Copy)]
+pub struct WithLabels<'a> {
+    aot: AudioObjectType,
+    labels: &'a [(u8, &'a str)],
+}
+
+#[cfg(feature = "names")]
+impl WithLabels<'_> {
+    /// The label given for the value, if it is reserved.
+    fn label(&self) -> Option<&str> {
+        if self.aot.is_defined() {
+            return None;
+        }
+        self.labels
+            .iter()
+            .find(|(v, _)| *v == self.aot.0)
+            .map(|(_, label)| *label)
+    }
+}
+
+#[cfg(feature = "names")]
+impl fmt::Debug for WithLabels<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.label() {
+            Some(_) if f.alternate() => fmt::Display::fmt(self, f),
+            Some(label) => write!(f, "{}({})", label, self.aot.0),
+            None => fmt::Debug::fmt(&self.aot, f),
+        }
+    }
+}
+
+#[cfg(feature = "names")]
+impl fmt::Display for WithLabels<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.label() {
+            Some(label) if f.alternate() => write!(f, "{} ({})", self.aot.0, label),
+            Some(label) => f.write_str(label),
+            None => fmt::Display::fmt(&self.aot, f),
+        }
+    }
+}
+
+/// Matches an [`AudioObjectType`](struct.AudioObjectType.html) against every defined object
+/// type, by the names of their constants, plus a required `Reserved(..)` arm binding the value
+/// of any other object type.
+///
+/// The expansion is a `match` over the value's [`kind()`](struct.AudioObjectType.html#method.kind),
+/// so unlike a `match` with a wildcard arm, it fails to compile when a new version of this crate
+/// adds a constant, prompting the dispatch to be updated.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// fn decoder(aot: AudioObjectType) -> Option<&'static str> {
+///     match_aot!(aot, {
+///         AAC_MAIN | AAC_LC | AAC_SSR | AAC_LTP | SBR | PS | AAC_SCALABLE | ER_AAC_LC |
+///         ER_AAC_LTP | ER_AAC_SCALABLE | ER_AAC_LD | ER_AAC_ELD => Some("aac"),
+///         USAC => Some("usac"),
+///         NULL | TWIN_VQ | CELP | HVXC | TTSI | MAIN_SYNTHETIC | WAVETABLE_SYNTHESIS |
+///         GENERAL_MIDI | ALGORITHMIC_SYNTHESIS_AND_AUDIO_FX | ER_TWIN_VQ | ER_BSAC | ER_CELP |
+///         ER_HVXC | ER_HILN | ER_PARAMETRIC | SSC | MPEG_SURROUND | LAYER1 | LAYER2 | LAYER3 |
+///         DST | ALS | SLS | SLS_NON_CORE | SMR_SIMPLE | SMR_MAIN | SAOC | LD_MPEG_SURROUND |
+///         SAOC_DE | AUDIO_SYNC => None,
+///         Reserved(_) => None,
+///     })
+/// }
+/// assert_eq!(Some("aac"), decoder(AudioObjectType::PS));
+/// ```
+///
+/// Leaving out any object type is an error,
+///
+/// ```compile_fail
+/// # use mpeg4_audio_const::*;
+/// let is_lc = match_aot!(AudioObjectType::AAC_LC, {
+///     AAC_LC => true,
+///     Reserved(_) => false,
+/// });
+/// ```
+#[macro_export]
+macro_rules! match_aot {
+    ( $aot:expr, { $( $( $id:ident $( ( $v:pat ) )? )|+ => $body:expr ),* $(,)? } ) => {
+        match $crate::AudioObjectType::kind($aot) {
+            $(
+                $( $crate::AudioObjectTypeKind::$id $( ( $v ) )? )|+ => $body
+            ),*
+        }
+    };
+}
+
+/// A broad classification of the audio object types, as given by
+/// [`AudioObjectType::family()`](struct.AudioObjectType.html#method.family).
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AudioObjectTypeFamily {
+    /// The general audio coders: the AAC types (including the error resilient and low delay
+    /// variants), BSAC and TwinVQ
+    GeneralAudio,
+    /// The bandwidth extension and parametric stereo tools of HE-AAC, `SBR` and `PS`
+    Extension,
+    /// The speech coders, CELP and HVXC, and the text-to-speech interface
+    Speech,
+    /// The parametric coders, HILN and SSC
+    Parametric,
+    /// Structured audio synthesis, MIDI and symbolic music representation
+    Synthesis,
+    /// The lossless coders, ALS, SLS and DST
+    Lossless,
+    /// The spatial audio tools, MPEG Surround and SAOC
+    Spatial,
+    /// Unified Speech and Audio Coding
+    Unified,
+    /// MPEG-1/2 Layer-1, -2 and -3 audio carried in MPEG-4
+    MpegLayer,
+    /// The `NULL` object type and the audio synchronization tool
+    Other,
+}
+
+impl AudioObjectType {
+    /// The family to which this object type belongs, or `None` if the value is reserved.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Some(AudioObjectTypeFamily::GeneralAudio), AudioObjectType::ER_AAC_LD.family());
+    /// assert_eq!(Some(AudioObjectTypeFamily::Lossless), AudioObjectType::ALS.family());
+    /// ```
+    pub const fn family(self) -> Option<AudioObjectTypeFamily> {
+        use AudioObjectTypeFamily::*;
+        Some(match self {
+            AudioObjectType::AAC_MAIN
+            | AudioObjectType::AAC_LC
+            | AudioObjectType::AAC_SSR
+            | AudioObjectType::AAC_LTP
+            | AudioObjectType::AAC_SCALABLE
+            | AudioObjectType::TWIN_VQ
+            | AudioObjectType::ER_AAC_LC
+            | AudioObjectType::ER_AAC_LTP
+            | AudioObjectType::ER_AAC_SCALABLE
+            | AudioObjectType::ER_TWIN_VQ
+            | AudioObjectType::ER_BSAC
+            | AudioObjectType::ER_AAC_LD
+            | AudioObjectType::ER_AAC_ELD => GeneralAudio,
+            AudioObjectType::SBR | AudioObjectType::PS => Extension,
+            AudioObjectType::CELP
+            | AudioObjectType::HVXC
+            | AudioObjectType::TTSI
+            | AudioObjectType::ER_CELP
+            | AudioObjectType::ER_HVXC => Speech,
+            AudioObjectType::ER_HILN | AudioObjectType::ER_PARAMETRIC | AudioObjectType::SSC => {
+                Parametric
+            }
+            AudioObjectType::MAIN_SYNTHETIC
+            | AudioObjectType::WAVETABLE_SYNTHESIS
+            | AudioObjectType::GENERAL_MIDI
+            | AudioObjectType::ALGORITHMIC_SYNTHESIS_AND_AUDIO_FX
+            | AudioObjectType::SMR_SIMPLE
+            | AudioObjectType::SMR_MAIN => Synthesis,
+            AudioObjectType::DST
+            | AudioObjectType::ALS
+            | AudioObjectType::SLS
+            | AudioObjectType::SLS_NON_CORE => Lossless,
+            AudioObjectType::MPEG_SURROUND
+            | AudioObjectType::SAOC
+            | AudioObjectType::LD_MPEG_SURROUND
+            | AudioObjectType::SAOC_DE => Spatial,
+            AudioObjectType::USAC => Unified,
+            AudioObjectType::LAYER1 | AudioObjectType::LAYER2 | AudioObjectType::LAYER3 => {
+                MpegLayer
+            }
+            AudioObjectType::NULL | AudioObjectType::AUDIO_SYNC => Other,
+            _ => return None,
+        })
+    }
+
+    /// `true` for the AAC object types, including the error resilient and low delay variants
+    /// and BSAC, and for the HE-AAC tools `SBR` and `PS`.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert!(AudioObjectType::PS.is_aac_family());
+    /// assert!(!AudioObjectType::TWIN_VQ.is_aac_family());
+    /// ```
+    pub const fn is_aac_family(self) -> bool {
+        matches!(self.0, 1..=6 | 17 | 19 | 20 | 22 | 23 | 29 | 39)
+    }
+
+    /// `true` for the error resilient (`ER_`) object types.
+    pub const fn is_error_resilient(self) -> bool {
+        matches!(self.0, 17 | 19..=27 | 39)
+    }
+
+    /// `true` for the object types designed for low delay communication applications, AAC LD,
+    /// AAC ELD and Low Delay MPEG Surround.
+    pub const fn is_low_delay(self) -> bool {
+        matches!(
+            self,
+            AudioObjectType::ER_AAC_LD
+                | AudioObjectType::ER_AAC_ELD
+                | AudioObjectType::LD_MPEG_SURROUND
+        )
+    }
+}
+
+/// A coding tool whose use is permitted only by some object types, as queried by
+/// [`AudioObjectType::supports_tool()`](struct.AudioObjectType.html#method.supports_tool).
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CodingTool {
+    /// Long term prediction
+    Ltp,
+    /// Temporal noise shaping
+    Tns,
+    /// Perceptual noise substitution
+    Pns,
+    /// Spectral band replication (including the low delay SBR of AAC ELD, and the SBR of USAC)
+    Sbr,
+    /// Parametric stereo
+    Ps,
+    /// Frames of 960 rather than 1024 samples (signalled by `frameLengthFlag`)
+    Frame960,
+}
+
+impl AudioObjectType {
+    /// `true` if this object type permits use of the given coding tool, per the object type
+    /// definitions of the spec, so that streams using a tool which their signalled object type
+    /// does not permit can be flagged.
+    ///
+    /// The tools of the `SBR` and `PS` object types are reported only for those types (and for
+    /// the types incorporating SBR themselves), so when SBR or PS is signalled implicitly, the
+    /// object type of the extension should be checked rather than that of the core.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert!(AudioObjectType::AAC_LTP.supports_tool(CodingTool::Ltp));
+    /// assert!(!AudioObjectType::AAC_LC.supports_tool(CodingTool::Ltp));
+    /// assert!(AudioObjectType::PS.supports_tool(CodingTool::Ps));
+    /// ```
+    pub const fn supports_tool(self, tool: CodingTool) -> bool {
+        match tool {
+            CodingTool::Ltp => matches!(self.0, 4 | 6 | 19 | 20 | 23),
+            CodingTool::Tns => matches!(self.0, 1..=4 | 6 | 17 | 19 | 20 | 22 | 23 | 39 | 42),
+            CodingTool::Pns => matches!(self.0, 1..=4 | 6 | 17 | 19 | 20 | 22 | 23),
+            CodingTool::Sbr => matches!(self.0, 5 | 29 | 39 | 42),
+            CodingTool::Ps => matches!(self.0, 29),
+            CodingTool::Frame960 => matches!(self.0, 1 | 2 | 4 | 6 | 7 | 17 | 19..=22),
+        }
+    }
+}
+
+/// An edition of ISO/IEC 14496-3, as returned by
+/// [`AudioObjectType::introduced_in()`](struct.AudioObjectType.html#method.introduced_in).
+///
+/// Values defined by an amendment are attributed to the first edition consolidating that
+/// amendment.  Editions are ordered by publication.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpecEdition {
+    /// The first edition, ISO/IEC 14496-3:1999 ('version 1')
+    Edition1999,
+    /// The second edition, ISO/IEC 14496-3:2001, adding the error resilient object types
+    /// ('version 2')
+    Edition2001,
+    /// The third edition, ISO/IEC 14496-3:2005, adding SBR, PS, SSC, the MPEG-1/2 layers and DST
+    Edition2005,
+    /// The fourth edition, ISO/IEC 14496-3:2009, adding MPEG Surround, ALS, SLS, ER AAC ELD and
+    /// SMR
+    Edition2009,
+    /// The fifth edition, ISO/IEC 14496-3:2019, adding USAC, SAOC, Low Delay MPEG Surround,
+    /// SAOC-DE and the audio synchronization tool
+    Edition2019,
+}
+
+impl AudioObjectType {
+    /// The edition of the spec which defined this value, or `None` if the value is reserved.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Some(SpecEdition::Edition1999), AudioObjectType::AAC_LC.introduced_in());
+    /// assert_eq!(Some(SpecEdition::Edition2005), AudioObjectType::SBR.introduced_in());
+    /// ```
+    pub const fn introduced_in(self) -> Option<SpecEdition> {
+        Some(match self.0 {
+            0..=4 | 6..=9 | 12..=16 => SpecEdition::Edition1999,
+            17 | 19..=27 => SpecEdition::Edition2001,
+            5 | 28 | 29 | 32..=35 => SpecEdition::Edition2005,
+            30 | 36..=41 => SpecEdition::Edition2009,
+            42..=46 => SpecEdition::Edition2019,
+            _ => return None,
+        })
+    }
+
+    /// `true` if this value is defined by the given edition of the spec, for validating content
+    /// against a particular edition.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert!(!AudioObjectType::USAC.is_defined_in(SpecEdition::Edition2009));
+    /// assert!(AudioObjectType::USAC.is_defined_in(SpecEdition::Edition2019));
+    /// ```
+    pub fn is_defined_in(self, edition: SpecEdition) -> bool {
+        self.introduced_in()
+            .is_some_and(|introduced| introduced <= edition)
+    }
+}
+
+/// Commonly used names for object types, besides their constant and spec names
+#[cfg(feature = "names")]
+const AOT_ALIASES: &[(AudioObjectType, &str)] = &[
+    (AudioObjectType::AAC_LC, "LC-AAC"),
+    (AudioObjectType::SBR, "HE-AAC"),
+    (AudioObjectType::SBR, "HE-AACv1"),
+    (AudioObjectType::SBR, "aacPlus"),
+    (AudioObjectType::PS, "HE-AACv2"),
+    (AudioObjectType::PS, "aacPlus v2"),
+    (AudioObjectType::PS, "eAAC+"),
+    (AudioObjectType::ER_AAC_LD, "AAC-LD"),
+    (AudioObjectType::ER_AAC_ELD, "AAC-ELD"),
+    (AudioObjectType::USAC, "xHE-AAC"),
+];
+
+/// Represents an error parsing an `AudioObjectType` from a string which is not one of its
+/// names, giving any object types whose names are similar to the string.
+#[cfg(feature = "names")]
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParseAudioObjectTypeError {
+    near_misses: [Option<AudioObjectType>; 3],
+}
+
+#[cfg(feature = "names")]
+impl fmt::Display for ParseAudioObjectTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown audio object type name")?;
+        for (i, aot) in self.near_misses().enumerate() {
+            let separator = if i == 0 { " (did you mean " } else { ", " };
+            write!(f, "{}{}", separator, aot.name().unwrap())?;
+        }
+        if self.near_misses[0].is_some() {
+            f.write_str("?)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "names")]
+impl core::error::Error for ParseAudioObjectTypeError {}
+
+#[cfg(feature = "names")]
+impl ParseAudioObjectTypeError {
+    /// Up to three object types having a name close to the string that failed to parse, closest
+    /// first (e.g. as suggestions for a misspelled configuration value).
+    pub fn near_misses(&self) -> impl Iterator<Item = AudioObjectType> + '_ {
+        self.near_misses.iter().flatten().copied()
+    }
+}
+
+/// Copies the given name into the buffer lowercased and without spaces, `-` or `_`, giving
+/// `None` if it is too long to be the name of any object type.
+#[cfg(feature = "names")]
+fn normalize_aot_name<'a>(name: &str, buf: &'a mut [u8; 64]) -> Option<&'a [u8]> {
+    let mut len = 0;
+    for b in name.bytes().filter(|b| !matches!(b, b' ' | b'-' | b'_')) {
+        *buf.get_mut(len)? = b.to_ascii_lowercase();
+        len += 1;
+    }
+    Some(&buf[..len])
+}
+
+/// The Levenshtein distance between two names of at most 64 bytes.
+#[cfg(feature = "names")]
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut row = [0usize; 65];
+    for (j, v) in row.iter_mut().enumerate().take(b.len() + 1) {
+        *v = j;
+    }
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Parses the constant name (e.g. `AAC_LC`), the spec name (e.g. `AAC LC`) or a common alias
+/// (e.g. `HE-AAC` for `SBR`, or `xHE-AAC` for `USAC`) of a defined object type, ignoring case,
+/// spaces, `-` and `_`.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// assert_eq!(Ok(AudioObjectType::AAC_LC), "aac-lc".parse());
+/// assert_eq!(Ok(AudioObjectType::ER_AAC_ELD), "ER AAC ELD".parse());
+/// assert_eq!(Ok(AudioObjectType::PS), "HE-AAC v2".parse());
+/// ```
+///
+/// On failure, the error suggests similarly named object types,
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// let err = "AAC-LTD".parse::<AudioObjectType>().unwrap_err();
+/// assert_eq!(Some(AudioObjectType::AAC_LTP), err.near_misses().next());
+/// ```
+#[cfg(feature = "names")]
+impl core::str::FromStr for AudioObjectType {
+    type Err = ParseAudioObjectTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(aot) = AudioObjectType::from_const_name(s) {
+            return Ok(aot);
+        }
+        let mut near_misses: [(usize, Option<AudioObjectType>); 3] = [(usize::MAX, None); 3];
+        let mut buf = [0; 64];
+        if let Some(input) = normalize_aot_name(s, &mut buf) {
+            let candidates = AudioObjectType::iter_defined()
+                .flat_map(|aot| {
+                    let names = aot.name().into_iter().chain(aot.description());
+                    names.map(move |name| (aot, name))
+                })
+                .chain(AOT_ALIASES.iter().copied());
+            for (aot, name) in candidates {
+                let mut name_buf = [0; 64];
+                let name = normalize_aot_name(name, &mut name_buf).unwrap();
+                if name == input {
+                    return Ok(aot);
+                }
+                let distance = edit_distance(input, name);
+                if distance > 2 || distance * 3 > input.len() {
+                    continue;
+                }
+                let worst = near_misses[2].0;
+                match near_misses.iter_mut().find(|(_, v)| *v == Some(aot)) {
+                    Some(existing) => existing.0 = existing.0.min(distance),
+                    None if distance < worst => near_misses[2] = (distance, Some(aot)),
+                    None => continue,
+                }
+                near_misses.sort_unstable_by_key(|(distance, _)| *distance);
+            }
+        }
+        Err(ParseAudioObjectTypeError {
+            near_misses: [near_misses[0].1, near_misses[1].1, near_misses[2].1],
+        })
+    }
+}
+
+/// Represents an error converting a `u8` into a `SamplingFrequencyIndex`
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum SamplingFrequencyIndexError {
+    /// Tried to convert the 'escape value', `15`, into a `SamplingFrequencyIndex` (this value
+    /// signals that an explicit 24-bit frequency value follows, rather than being an index itself).
+    EscapeValue,
+    /// Only values 15 and under can be represented by the 4-bit field.
+    TooLarge(u8),
+    /// A value given as a wider integer type is outside the range of a `u8`.
+    OutOfRange,
+}
+
+impl fmt::Display for SamplingFrequencyIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SamplingFrequencyIndexError::EscapeValue => write!(
+                f,
+                "sampling frequency index value {} is the escape value",
+                SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE
+            ),
+            SamplingFrequencyIndexError::TooLarge(v) => {
+                write!(f, "sampling frequency index value {} is greater than 15", v)
+            }
+            SamplingFrequencyIndexError::OutOfRange => {
+                f.write_str("sampling frequency index value is outside the range of a u8")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SamplingFrequencyIndexError {}
+
+/// Represents a _sampling frequency index_ value, as used in the `AudioSpecificConfig` and ADTS
+/// headers to signal one of a table of common sampling rates.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use core::convert::TryFrom;
+/// let sfi = SamplingFrequencyIndex::try_from(4).unwrap();
+/// assert_eq!(SamplingFrequencyIndex::FREQ_44100, sfi);
+/// assert_eq!(Some(44100), sfi.frequency());
+/// ```
+///
+/// Values `13` and `14` are reserved in the spec, and are accepted, but have no frequency,
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use core::convert::TryFrom;
+/// let sfi = SamplingFrequencyIndex::try_from(13).unwrap();
+/// # #[cfg(feature = "names")]
+/// assert_eq!("RESERVED(13)", format!("{:?}", sfi));
+/// assert_eq!(None, sfi.frequency());
+/// ```
+///
+/// The escape value `15` (see
+/// [`SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE`](constant.SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE.html))
+/// is disallowed.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use core::convert::TryFrom;
+/// assert_eq!(Err(SamplingFrequencyIndexError::EscapeValue), SamplingFrequencyIndex::try_from(15));
+/// assert_eq!(Err(SamplingFrequencyIndexError::TooLarge(16)), SamplingFrequencyIndex::try_from(16));
+/// ```
+///
+/// Values are ordered by their index value (so that higher frequencies sort first).  There is no
+/// `Default`, since no index means 'unspecified' and picking any one frequency would silently
+/// give a plausible but wrong value for a stream whose frequency was never set.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(frozen, eq, from_py_object, module = "mpeg4_audio_const")
+)]
+pub struct SamplingFrequencyIndex(u8);
+
+/// This value, `15`, is not used as a _sampling frequency index_, but instead indicates that an
+/// explicit 24-bit sampling frequency value follows.
+pub const SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE: u8 = 0b_1111;
+
+impl From<SamplingFrequencyIndex> for u8 {
+    fn from(v: SamplingFrequencyIndex) -> Self {
+        v.0
+    }
+}
+impl TryFrom<u8> for SamplingFrequencyIndex {
+    type Error = SamplingFrequencyIndexError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE => Err(SamplingFrequencyIndexError::EscapeValue),
+            16..=255 => Err(SamplingFrequencyIndexError::TooLarge(value)),
+            _ => Ok(SamplingFrequencyIndex(value)),
+        }
+    }
+}
+impl SamplingFrequencyIndex {
+    /// A `const` equivalent of `TryFrom<u8>`, giving `None` for the values that it rejects.
+    ///
+    /// Together with the `const` [`frequency()`](#method.frequency) method, this allows
+    /// lookup tables to be built at compile time,
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// const RATES: [Option<u32>; 16] = {
+    ///     let mut rates = [None; 16];
+    ///     let mut i = 0;
+    ///     while i < rates.len() {
+    ///         if let Some(index) = SamplingFrequencyIndex::from_u8_const(i as u8) {
+    ///             rates[i] = index.frequency();
+    ///         }
+    ///         i += 1;
+    ///     }
+    ///     rates
+    /// };
+    /// assert_eq!(Some(48000), RATES[3]);
+    /// assert_eq!(None, RATES[15]);
+    /// ```
+    pub const fn from_u8_const(value: u8) -> Option<SamplingFrequencyIndex> {
+        match value {
+            SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE | 16..=255 => None,
+            _ => Some(SamplingFrequencyIndex(value)),
+        }
+    }
+
+    /// Creates a value without the check performed by `TryFrom<u8>`.
+    ///
+    /// # Safety
+    ///
+    /// The value must be one that `TryFrom<u8>` accepts, i.e. less than the escape value
+    /// [`SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE`](constant.SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE.html).
+    /// The condition is checked by a debug assertion.
+    pub const unsafe fn new_unchecked(value: u8) -> SamplingFrequencyIndex {
+        debug_assert!(value < SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE);
+        SamplingFrequencyIndex(value)
+    }
+
+    /// A `const` equivalent of `u8::from()`.
+    pub const fn to_u8_const(self) -> u8 {
+        self.0
+    }
+
+    /// The index whose frequency is exactly the given frequency in Hz, or `None` if the
+    /// frequency must instead be signalled explicitly.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Some(SamplingFrequencyIndex::FREQ_48000), SamplingFrequencyIndex::exact(48000));
+    /// assert_eq!(None, SamplingFrequencyIndex::exact(44000));
+    /// ```
+    pub const fn exact(hz: u32) -> Option<SamplingFrequencyIndex> {
+        let mut i = 0;
+        while i < SAMPLING_FREQUENCIES.len() {
+            if let Some(frequency) = SAMPLING_FREQUENCIES[i] {
+                if frequency == hz {
+                    return Some(SamplingFrequencyIndex(i as u8));
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// The index for the given frequency in Hz according to the sampling frequency mapping of
+    /// the spec (ISO/IEC 14496-3, table 4.82), which decoders use to choose the tables for
+    /// frequencies signalled explicitly, and so encoders should use for nonstandard rates.
+    ///
+    /// Note that the mapping never gives `FREQ_7350`.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(SamplingFrequencyIndex::FREQ_44100, SamplingFrequencyIndex::nearest(44000));
+    /// assert_eq!(SamplingFrequencyIndex::FREQ_8000, SamplingFrequencyIndex::nearest(7350));
+    /// ```
+    pub const fn nearest(hz: u32) -> SamplingFrequencyIndex {
+        SamplingFrequencyIndex(match hz {
+            92017.. => 0,
+            75132..=92016 => 1,
+            55426..=75131 => 2,
+            46009..=55425 => 3,
+            37566..=46008 => 4,
+            27713..=37565 => 5,
+            23004..=27712 => 6,
+            18783..=23003 => 7,
+            13856..=18782 => 8,
+            11502..=13855 => 9,
+            9391..=11501 => 10,
+            _ => 11,
+        })
+    }
+}
+
+macro_rules! implement_sampling_frequency_index {
+    (
+        $( $tag:literal $id:ident $freq:literal ),* ,
+    ) => {
+
+        impl SamplingFrequencyIndex {
+            $(
+                #[doc=concat!(stringify!($freq), " Hz")]
+                pub const $id: SamplingFrequencyIndex = SamplingFrequencyIndex($tag);
+            )*
+
+            /// The sampling frequency in Hz, or `None` if this index value is reserved.
+            pub const fn frequency(self) -> Option<u32> {
+                SAMPLING_FREQUENCIES[self.0 as usize]
+            }
+        }
+
+        /// The sampling frequency in Hz for each sampling frequency index, indexed by value (or
+        /// `None` for reserved values), for use in `const` contexts and static lookup tables.
+        ///
+        /// ```rust
+        /// # use mpeg4_audio_const::*;
+        /// assert_eq!(Some(44100), SAMPLING_FREQUENCIES[4]);
+        /// ```
+        pub const SAMPLING_FREQUENCIES: [Option<u32>; SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE as usize] = {
+            let mut frequencies = [None; SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE as usize];
+            $( frequencies[$tag] = Some($freq); )*
+            frequencies
+        };
+
+        impl SamplingFrequencyIndex {
+            /// The text that `Display` gives for this value, or `None` if the value is reserved.
+            fn label(self) -> Option<&'static str> {
+                match self.0 {
+                    $(
+                        $tag => Some(concat!(stringify!($freq), " Hz"))
+                    ),* ,
+                    _ => None,
+                }
+            }
+        }
+
+        #[cfg(feature = "names")]
+        impl SamplingFrequencyIndex {
+            /// The constant name of each index, indexed by value (or `None` for reserved values)
+            const NAMES: [Option<&'static str>; SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE as usize] = {
+                let mut names = [None; SAMPLING_FREQUENCY_INDEX_ESCAPE_VALUE as usize];
+                $( names[$tag] = Some(stringify!($id)); )*
+                names
+            };
+
+            /// The name of the constant for this value, or `None` if the value is reserved.
+            ///
+            /// ```rust
+            /// # use mpeg4_audio_const::*;
+            /// assert_eq!(Some("FREQ_44100"), SamplingFrequencyIndex::FREQ_44100.name());
+            /// ```
+            pub const fn name(self) -> Option<&'static str> {
+                SamplingFrequencyIndex::NAMES[self.0 as usize]
+            }
+        }
+    }
+}
+
+implement_sampling_frequency_index! {
+    0 FREQ_96000 96000,
+    1 FREQ_88200 88200,
+    2 FREQ_64000 64000,
+    3 FREQ_48000 48000,
+    4 FREQ_44100 44100,
+    5 FREQ_32000 32000,
+    6 FREQ_24000 24000,
+    7 FREQ_22050 22050,
+    8 FREQ_16000 16000,
+    9 FREQ_12000 12000,
+    10 FREQ_11025 11025,
+    11 FREQ_8000 8000,
+    12 FREQ_7350 7350,
+    // 13, 14 - reserved
+    // 15 - 'escape' - deliberately skipped
+}
+
+implement_value_type! {
+    @display
+    /// Formats the frequency, e.g. `44100 Hz`, or `Reserved (n)` for reserved values.  The
+    /// alternate form (`{:#}`, which `{:#?}` also gives) is preceded by the index value.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!("44100 Hz", SamplingFrequencyIndex::FREQ_44100.to_string());
+    /// assert_eq!("4 (44100 Hz)", format!("{:#}", SamplingFrequencyIndex::FREQ_44100));
+    /// ```
+    SamplingFrequencyIndex
+}
+
+#[cfg(feature = "names")]
+impl SamplingFrequencyIndex {
+    /// The index whose frequency is exactly the given number of Hz, with or without the `Hz`
+    /// suffix that `Display` gives.
+    fn from_frequency_str(s: &str) -> Option<SamplingFrequencyIndex> {
+        let hz = s
+            .strip_suffix("Hz")
+            .or_else(|| s.strip_suffix("hz"))
+            .unwrap_or(s)
+            .trim_end();
+        SamplingFrequencyIndex::exact(hz.parse().ok()?)
+    }
+}
+
+implement_value_type! {
+    @from_str
+    /// Parses a frequency in Hz, with or without the `Hz` suffix produced by the `Display`
+    /// implementation, giving the index only if the frequency is exactly that of an index (see
+    /// [`nearest()`](#method.nearest) for other frequencies).  The constant names are also
+    /// accepted.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Ok(SamplingFrequencyIndex::FREQ_44100), "44100 Hz".parse());
+    /// assert_eq!(Ok(SamplingFrequencyIndex::FREQ_48000), "48000".parse());
+    /// assert_eq!(Ok(SamplingFrequencyIndex::FREQ_8000), "freq_8000".parse());
+    /// assert!("44000".parse::<SamplingFrequencyIndex>().is_err());
+    /// ```
+    SamplingFrequencyIndex: ParseSamplingFrequencyIndexError,
+        SamplingFrequencyIndex::from_frequency_str
+}
+
+/// Represents an error converting a `u8` into a `ChannelConfiguration`
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ChannelConfigurationError {
+    /// Only values 15 and under can be represented by the 4-bit field.
+    TooLarge(u8),
+    /// A value given as a wider integer type is outside the range of a `u8`.
+    OutOfRange,
+}
+
+impl fmt::Display for ChannelConfigurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChannelConfigurationError::TooLarge(v) => {
+                write!(f, "channel configuration value {} is greater than 15", v)
+            }
+            ChannelConfigurationError::OutOfRange => {
+                f.write_str("channel configuration value is outside the range of a u8")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ChannelConfigurationError {}
+
+/// Represents a _channel configuration_ value, as used in the `AudioSpecificConfig` and ADTS
+/// headers to signal one of a table of standard speaker layouts.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use core::convert::TryFrom;
+/// let conf = ChannelConfiguration::try_from(6).unwrap();
+/// assert_eq!(ChannelConfiguration::FIVE_ONE, conf);
+/// assert_eq!(Some(6), conf.channel_count());
+/// ```
+///
+/// The value `0` signals that the layout is instead given by a _program config element_, and so
+/// has no fixed channel count,
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// assert_eq!(None, ChannelConfiguration::AOT_SPECIFIC_CONFIG.channel_count());
+/// ```
+///
+/// This value is also the default, since it asserts no particular layout (as the
+/// [`AudioProfileLevelIndication`](struct.AudioProfileLevelIndication.html) default of
+/// `NOT_SPECIFIED` asserts no profile), so a default value can't be mistaken for a real
+/// layout.  [`SamplingFrequencyIndex`](struct.SamplingFrequencyIndex.html) has no default
+/// because it has no such value.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Default)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(frozen, eq, from_py_object, module = "mpeg4_audio_const")
+)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::SmallInt)
+)]
+pub struct ChannelConfiguration(u8);
+
+impl From<ChannelConfiguration> for u8 {
+    fn from(v: ChannelConfiguration) -> Self {
+        v.0
+    }
+}
+impl TryFrom<u8> for ChannelConfiguration {
+    type Error = ChannelConfigurationError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            16..=255 => Err(ChannelConfigurationError::TooLarge(value)),
+            _ => Ok(ChannelConfiguration(value)),
+        }
+    }
+}
+impl ChannelConfiguration {
+    /// A `const` equivalent of `TryFrom<u8>`, giving `None` for the values that it rejects.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// const STEREO_COUNT: Option<u32> = match ChannelConfiguration::from_u8_const(2) {
+    ///     Some(conf) => conf.channel_count(),
+    ///     None => None,
+    /// };
+    /// assert_eq!(Some(2), STEREO_COUNT);
+    /// ```
+    pub const fn from_u8_const(value: u8) -> Option<ChannelConfiguration> {
+        match value {
+            16..=255 => None,
+            _ => Some(ChannelConfiguration(value)),
+        }
+    }
+
+    /// Creates a value without the check performed by `TryFrom<u8>`.
+    ///
+    /// # Safety
+    ///
+    /// The value must be one that `TryFrom<u8>` accepts, i.e. no greater than `15`.  The
+    /// condition is checked by a debug assertion.
+    pub const unsafe fn new_unchecked(value: u8) -> ChannelConfiguration {
+        debug_assert!(value <= 15);
+        ChannelConfiguration(value)
+    }
+
+    /// A `const` equivalent of `u8::from()`.
+    pub const fn to_u8_const(self) -> u8 {
+        self.0
+    }
+}
+
+macro_rules! implement_channel_configuration {
+    (
+        $( $tag:literal $id:ident $desc:literal ),* ,
+    ) => {
+
+        impl ChannelConfiguration {
+            $(
+                #[doc=$desc]
+                pub const $id: ChannelConfiguration = ChannelConfiguration($tag);
+            )*
+        }
+
+        #[cfg(feature = "names")]
+        impl ChannelConfiguration {
+            /// The constant name of each configuration, indexed by value (or `None` for reserved
+            /// values)
+            const NAMES: [Option<&'static str>; 16] = {
+                let mut names = [None; 16];
+                $( names[$tag] = Some(stringify!($id)); )*
+                names
+            };
+
+            /// The name of the constant for this value, or `None` if the value is reserved.
+            ///
+            /// ```rust
+            /// # use mpeg4_audio_const::*;
+            /// assert_eq!(Some("FIVE_ONE"), ChannelConfiguration::FIVE_ONE.name());
+            /// ```
+            pub const fn name(self) -> Option<&'static str> {
+                ChannelConfiguration::NAMES[self.0 as usize]
+            }
+        }
+    }
+}
+
+implement_channel_configuration! {
+    0 AOT_SPECIFIC_CONFIG "Channel layout is given in the audio object type specific config",
+    1 MONO "1 channel: front-center",
+    2 STEREO "2 channels: front-left, front-right",
+    3 THREE "3 channels: front-center, front-left, front-right",
+    4 FOUR "4 channels: front-center, front-left, front-right, back-center",
+    5 FIVE "5 channels: front-center, front-left, front-right, back-left, back-right",
+    6 FIVE_ONE "6 channels: front-center, front-left, front-right, back-left, back-right, LFE",
+    7 SEVEN_ONE "8 channels: front-center, front-left-center, front-right-center, front-left, front-right, back-left, back-right, LFE",
+    // 8 - 10 - reserved
+    11 SIX_ONE "7 channels: front-center, front-left, front-right, back-left, back-right, back-center, LFE",
+    12 SEVEN_ONE_BACK "8 channels: front-center, front-left, front-right, side-left, side-right, back-left, back-right, LFE",
+    13 TWENTY_TWO_TWO "24 channels: the 22.2 layout",
+    14 SEVEN_ONE_TOP_FRONT "8 channels: front-center, front-left, front-right, back-left, back-right, LFE, top-front-left, top-front-right",
+    // 15 - reserved
+}
+
+impl ChannelConfiguration {
+    /// The number of output channels implied by this configuration, or `None` if the value is
+    /// `0` (layout given elsewhere) or reserved.
+    pub const fn channel_count(self) -> Option<u32> {
+        CHANNEL_COUNTS[self.0 as usize]
+    }
+
+    /// The configuration conventionally signalled for the given number of channels, for when
+    /// only the count is known, or `None` if no configuration has that many channels.
+    ///
+    /// Of the configurations having 8 channels, `SEVEN_ONE` is chosen, being the one that
+    /// decoders most widely support.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Some(ChannelConfiguration::FIVE_ONE), ChannelConfiguration::from_channel_count(6));
+    /// assert_eq!(Some(ChannelConfiguration::SEVEN_ONE), ChannelConfiguration::from_channel_count(8));
+    /// assert_eq!(None, ChannelConfiguration::from_channel_count(9));
+    /// ```
+    pub const fn from_channel_count(count: u32) -> Option<ChannelConfiguration> {
+        match count {
+            1..=6 => Some(ChannelConfiguration(count as u8)),
+            7 => Some(ChannelConfiguration::SIX_ONE),
+            8 => Some(ChannelConfiguration::SEVEN_ONE),
+            24 => Some(ChannelConfiguration::TWENTY_TWO_TWO),
+            _ => None,
+        }
+    }
+}
+
+/// The conventional name of the layout of each channel configuration, indexed by value
+#[cfg(feature = "names")]
+const CHANNEL_LAYOUT_NAMES: [Option<&str>; 16] = [
+    Some("aot-specific"),
+    Some("mono"),
+    Some("stereo"),
+    Some("3.0"),
+    Some("4.0"),
+    Some("5.0"),
+    Some("5.1"),
+    Some("7.1"),
+    None,
+    None,
+    None,
+    Some("6.1"),
+    Some("7.1-rear"),
+    Some("22.2"),
+    Some("5.1.2"),
+    None,
+];
+
+#[cfg(feature = "names")]
+impl ChannelConfiguration {
+    /// The text that `Display` gives for this value, or `None` if the value is reserved.
+    fn label(self) -> Option<&'static str> {
+        CHANNEL_LAYOUT_NAMES[self.0 as usize]
+    }
+
+    /// The value for the alternative layout names `1.0` and `2.0`.
+    fn from_layout_alias(s: &str) -> Option<ChannelConfiguration> {
+        match s {
+            "1.0" => Some(ChannelConfiguration::MONO),
+            "2.0" => Some(ChannelConfiguration::STEREO),
+            _ => None,
+        }
+    }
+}
+
+implement_value_type! {
+    @display
+    /// Formats the conventional name of the layout, e.g. `stereo` or `5.1`, or `Reserved (n)` for
+    /// reserved values.  The alternate form (`{:#}`, which `{:#?}` also gives) is preceded by the
+    /// numeric value.
+    ///
+    /// | Value | Name           | Value | Name       |
+    /// |-------|----------------|-------|------------|
+    /// | 0     | `aot-specific` | 6     | `5.1`      |
+    /// | 1     | `mono`         | 7     | `7.1`      |
+    /// | 2     | `stereo`       | 11    | `6.1`      |
+    /// | 3     | `3.0`          | 12    | `7.1-rear` |
+    /// | 4     | `4.0`          | 13    | `22.2`     |
+    /// | 5     | `5.0`          | 14    | `5.1.2`    |
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!("5.1", ChannelConfiguration::FIVE_ONE.to_string());
+    /// assert_eq!("1 (mono)", format!("{:#}", ChannelConfiguration::MONO));
+    /// ```
+    #[cfg(feature = "names")]
+    ChannelConfiguration
+}
+
+implement_value_type! {
+    @from_str
+    /// Parses the layout names produced by the `Display` implementation, ignoring case, and also
+    /// accepting `1.0` and `2.0` for `mono` and `stereo`, and the constant names.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Ok(ChannelConfiguration::STEREO), "Stereo".parse());
+    /// assert_eq!(Ok(ChannelConfiguration::TWENTY_TWO_TWO), "22.2".parse());
+    /// assert_eq!(Ok(ChannelConfiguration::FIVE_ONE), "five-one".parse());
+    /// ```
+    ChannelConfiguration: ParseChannelConfigurationError, ChannelConfiguration::from_layout_alias
+}
+
+/// The number of output channels for each channel configuration, indexed by value (or `None` for
+/// `0` and reserved values), for use in `const` contexts and static lookup tables.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// assert_eq!(Some(6), CHANNEL_COUNTS[6]);
+/// ```
+pub const CHANNEL_COUNTS: [Option<u32>; 16] = [
+    None,
+    Some(1),
+    Some(2),
+    Some(3),
+    Some(4),
+    Some(5),
+    Some(6),
+    Some(8),
+    None,
+    None,
+    None,
+    Some(7),
+    Some(8),
+    Some(24),
+    Some(8),
+    None,
+];
+
+/// The number of each kind of channel element making up a channel layout
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub struct ElementCounts {
+    /// Single channel elements
+    pub sce: usize,
+    /// Channel pair elements
+    pub cpe: usize,
+    /// LFE channel elements
+    pub lfe: usize,
+}
+
+impl ElementCounts {
+    /// The number of output channels carried by these elements.
+    pub fn channel_count(&self) -> u32 {
+        (self.sce + self.cpe * 2 + self.lfe) as u32
+    }
+
+    /// The standard channel configuration having exactly these element counts, if any.
+    ///
+    /// Configurations `7`, `12` and `14` all consist of one SCE, three CPEs and one LFE
+    /// (differing only in the order of the elements and the speaker positions), and in this
+    /// case `7` is given, since that is the only one of the three that can be signalled in an
+    /// ADTS header.
+    pub fn channel_configuration(&self) -> Option<ChannelConfiguration> {
+        let conf = match (self.sce, self.cpe, self.lfe) {
+            (1, 0, 0) => ChannelConfiguration::MONO,
+            (0, 1, 0) => ChannelConfiguration::STEREO,
+            (1, 1, 0) => ChannelConfiguration::THREE,
+            (2, 1, 0) => ChannelConfiguration::FOUR,
+            (1, 2, 0) => ChannelConfiguration::FIVE,
+            (1, 2, 1) => ChannelConfiguration::FIVE_ONE,
+            (1, 3, 1) => ChannelConfiguration::SEVEN_ONE,
+            (2, 2, 1) => ChannelConfiguration::SIX_ONE,
+            (6, 8, 2) => ChannelConfiguration::TWENTY_TWO_TWO,
+            _ => return None,
+        };
+        Some(conf)
+    }
+}
+
+impl ChannelConfiguration {
+    /// The numbers of each kind of channel element that a `raw_data_block()` carries for this
+    /// configuration (per the channel configuration table of ISO/IEC 14496-3), or `None` if the
+    /// value is `0` (layout given by a PCE) or reserved.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(
+    ///     Some(ElementCounts { sce: 1, cpe: 2, lfe: 1 }),
+    ///     ChannelConfiguration::FIVE_ONE.element_counts()
+    /// );
+    /// assert_eq!(None, ChannelConfiguration::AOT_SPECIFIC_CONFIG.element_counts());
+    /// ```
+    pub const fn element_counts(self) -> Option<ElementCounts> {
+        let (sce, cpe, lfe) = match self {
+            ChannelConfiguration::MONO => (1, 0, 0),
+            ChannelConfiguration::STEREO => (0, 1, 0),
+            ChannelConfiguration::THREE => (1, 1, 0),
+            ChannelConfiguration::FOUR => (2, 1, 0),
+            ChannelConfiguration::FIVE => (1, 2, 0),
+            ChannelConfiguration::FIVE_ONE => (1, 2, 1),
+            ChannelConfiguration::SEVEN_ONE => (1, 3, 1),
+            ChannelConfiguration::SIX_ONE => (2, 2, 1),
+            ChannelConfiguration::SEVEN_ONE_BACK => (1, 3, 1),
+            ChannelConfiguration::TWENTY_TWO_TWO => (6, 8, 2),
+            ChannelConfiguration::SEVEN_ONE_TOP_FRONT => (1, 3, 1),
+            _ => return None,
+        };
+        Some(ElementCounts { sce, cpe, lfe })
+    }
+}
+
+/// Represents an error converting a wider integer type into an `AudioProfileLevelIndication`
+/// (every `u8` value is a valid indication, so only the range of a `u8` can be exceeded)
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum AudioProfileLevelIndicationError {
+    /// A value given as a wider integer type is outside the range of a `u8`.
+    OutOfRange,
+}
+
+impl fmt::Display for AudioProfileLevelIndicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioProfileLevelIndicationError::OutOfRange => {
+                f.write_str("audio profile and level indication value is outside the range of a u8")
+            }
+        }
+    }
+}
+
+impl core::error::Error for AudioProfileLevelIndicationError {}
+
+impl From<core::convert::Infallible> for AudioProfileLevelIndicationError {
+    fn from(e: core::convert::Infallible) -> Self {
+        match e {}
+    }
+}
+
+/// Represents an _audio profile and level indication_ value, as carried in the
+/// `InitialObjectDescriptor`, the `profile-level-id` SDP parameter and MPEG-2 TS descriptors,
+/// identifying the profile and level to which a stream conforms.
+///
+/// Every 8-bit value can be represented, including those that are reserved,
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// let apli = AudioProfileLevelIndication::from(0x29);
+/// assert_eq!(AudioProfileLevelIndication::AAC_L2, apli);
+/// # #[cfg(feature = "names")]
+/// assert_eq!("AAC_L2(41)", format!("{:?}", apli));
+/// # #[cfg(feature = "names")]
+/// assert_eq!("RESERVED(96)", format!("{:?}", AudioProfileLevelIndication::from(0x60)));
+/// ```
+///
+/// The default is [`NOT_SPECIFIED`](#associatedconstant.NOT_SPECIFIED), rather than the
+/// reserved value `0`.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+pub struct AudioProfileLevelIndication(u8);
+
+impl From<AudioProfileLevelIndication> for u8 {
+    fn from(v: AudioProfileLevelIndication) -> Self {
+        v.0
+    }
+}
+impl From<u8> for AudioProfileLevelIndication {
+    fn from(v: u8) -> Self {
+        AudioProfileLevelIndication(v)
+    }
+}
+impl Default for AudioProfileLevelIndication {
+    fn default() -> Self {
+        AudioProfileLevelIndication::NOT_SPECIFIED
+    }
+}
+
+impl AudioProfileLevelIndication {
+    /// A `const` equivalent of `From<u8>`.
+    pub const fn from_u8_const(value: u8) -> AudioProfileLevelIndication {
+        AudioProfileLevelIndication(value)
+    }
+
+    /// A `const` equivalent of `u8::from()`.
+    pub const fn to_u8_const(self) -> u8 {
+        self.0
+    }
+}
+
+macro_rules! implement_audio_profile_level_indication {
+    (
+        $( $tag:literal $id:ident $desc:literal ),* ,
+    ) => {
+
+        impl AudioProfileLevelIndication {
+            $(
+                #[doc=$desc]
+                pub const $id: AudioProfileLevelIndication = AudioProfileLevelIndication($tag);
+            )*
+        }
+
+        #[cfg(feature = "clap")]
+        impl AudioProfileLevelIndication {
+            /// Every value having a constant
+            pub(crate) const DEFINED: &'static [AudioProfileLevelIndication] = &[
+                $( AudioProfileLevelIndication($tag) ),*
+            ];
+        }
+
+        #[cfg(feature = "names")]
+        impl AudioProfileLevelIndication {
+            /// The description of this profile and level, e.g. `"AAC Profile, Level 2"`, or
+            /// `None` if the value is reserved.
+            pub const fn description(self) -> Option<&'static str> {
+                match self.0 {
+                    $(
+                        $tag => Some($desc)
+                    ),* ,
+                    _ => None,
+                }
+            }
+
+            /// The constant name of each indication, indexed by value (or `None` for reserved
+            /// values)
+            const NAMES: [Option<&'static str>; 256] = {
+                let mut names = [None; 256];
+                $( names[$tag] = Some(stringify!($id)); )*
+                names
+            };
+
+            /// The name of the constant for this value, or `None` if the value is reserved.
+            ///
+            /// ```rust
+            /// # use mpeg4_audio_const::*;
+            /// assert_eq!(Some("AAC_L2"), AudioProfileLevelIndication::AAC_L2.name());
+            /// ```
+            pub const fn name(self) -> Option<&'static str> {
+                AudioProfileLevelIndication::NAMES[self.0 as usize]
+            }
+
+            /// The text that `Display` gives for this value, or `None` if the value is reserved.
+            fn label(self) -> Option<&'static str> {
+                self.description()
+            }
+        }
+    }
+}
+
+implement_audio_profile_level_indication! {
+    // 0x00 - reserved
+    0x01 MAIN_L1 "Main Audio Profile, Level 1",
+    0x02 MAIN_L2 "Main Audio Profile, Level 2",
+    0x03 MAIN_L3 "Main Audio Profile, Level 3",
+    0x04 MAIN_L4 "Main Audio Profile, Level 4",
+    0x05 SCALABLE_L1 "Scalable Audio Profile, Level 1",
+    0x06 SCALABLE_L2 "Scalable Audio Profile, Level 2",
+    0x07 SCALABLE_L3 "Scalable Audio Profile, Level 3",
+    0x08 SCALABLE_L4 "Scalable Audio Profile, Level 4",
+    0x09 SPEECH_L1 "Speech Audio Profile, Level 1",
+    0x0a SPEECH_L2 "Speech Audio Profile, Level 2",
+    0x0b SYNTHETIC_L1 "Synthetic Audio Profile, Level 1",
+    0x0c SYNTHETIC_L2 "Synthetic Audio Profile, Level 2",
+    0x0d SYNTHETIC_L3 "Synthetic Audio Profile, Level 3",
+    0x0e HIGH_QUALITY_L1 "High Quality Audio Profile, Level 1",
+    0x0f HIGH_QUALITY_L2 "High Quality Audio Profile, Level 2",
+    0x10 HIGH_QUALITY_L3 "High Quality Audio Profile, Level 3",
+    0x11 HIGH_QUALITY_L4 "High Quality Audio Profile, Level 4",
+    0x12 HIGH_QUALITY_L5 "High Quality Audio Profile, Level 5",
+    0x13 HIGH_QUALITY_L6 "High Quality Audio Profile, Level 6",
+    0x14 HIGH_QUALITY_L7 "High Quality Audio Profile, Level 7",
+    0x15 HIGH_QUALITY_L8 "High Quality Audio Profile, Level 8",
+    0x16 LOW_DELAY_L1 "Low Delay Audio Profile, Level 1",
+    0x17 LOW_DELAY_L2 "Low Delay Audio Profile, Level 2",
+    0x18 LOW_DELAY_L3 "Low Delay Audio Profile, Level 3",
+    0x19 LOW_DELAY_L4 "Low Delay Audio Profile, Level 4",
+    0x1a LOW_DELAY_L5 "Low Delay Audio Profile, Level 5",
+    0x1b LOW_DELAY_L6 "Low Delay Audio Profile, Level 6",
+    0x1c LOW_DELAY_L7 "Low Delay Audio Profile, Level 7",
+    0x1d LOW_DELAY_L8 "Low Delay Audio Profile, Level 8",
+    0x1e NATURAL_L1 "Natural Audio Profile, Level 1",
+    0x1f NATURAL_L2 "Natural Audio Profile, Level 2",
+    0x20 NATURAL_L3 "Natural Audio Profile, Level 3",
+    0x21 NATURAL_L4 "Natural Audio Profile, Level 4",
+    0x22 MOBILE_INTERNETWORKING_L1 "Mobile Audio Internetworking Profile, Level 1",
+    0x23 MOBILE_INTERNETWORKING_L2 "Mobile Audio Internetworking Profile, Level 2",
+    0x24 MOBILE_INTERNETWORKING_L3 "Mobile Audio Internetworking Profile, Level 3",
+    0x25 MOBILE_INTERNETWORKING_L4 "Mobile Audio Internetworking Profile, Level 4",
+    0x26 MOBILE_INTERNETWORKING_L5 "Mobile Audio Internetworking Profile, Level 5",
+    0x27 MOBILE_INTERNETWORKING_L6 "Mobile Audio Internetworking Profile, Level 6",
+    0x28 AAC_L1 "AAC Profile, Level 1",
+    0x29 AAC_L2 "AAC Profile, Level 2",
+    0x2a AAC_L4 "AAC Profile, Level 4",
+    0x2b AAC_L5 "AAC Profile, Level 5",
+    0x2c HE_AAC_L2 "High Efficiency AAC Profile, Level 2",
+    0x2d HE_AAC_L3 "High Efficiency AAC Profile, Level 3",
+    0x2e HE_AAC_L4 "High Efficiency AAC Profile, Level 4",
+    0x2f HE_AAC_L5 "High Efficiency AAC Profile, Level 5",
+    0x30 HE_AAC_V2_L2 "High Efficiency AAC v2 Profile, Level 2",
+    0x31 HE_AAC_V2_L3 "High Efficiency AAC v2 Profile, Level 3",
+    0x32 HE_AAC_V2_L4 "High Efficiency AAC v2 Profile, Level 4",
+    0x33 HE_AAC_V2_L5 "High Efficiency AAC v2 Profile, Level 5",
+    0x34 LOW_DELAY_AAC_L1 "Low Delay AAC Profile, Level 1",
+    0x35 BASELINE_MPEG_SURROUND_L1 "Baseline MPEG Surround Profile, Level 1",
+    0x36 BASELINE_MPEG_SURROUND_L2 "Baseline MPEG Surround Profile, Level 2",
+    0x37 BASELINE_MPEG_SURROUND_L3 "Baseline MPEG Surround Profile, Level 3",
+    0x38 BASELINE_MPEG_SURROUND_L4 "Baseline MPEG Surround Profile, Level 4",
+    0x39 BASELINE_MPEG_SURROUND_L5 "Baseline MPEG Surround Profile, Level 5",
+    0x3a BASELINE_MPEG_SURROUND_L6 "Baseline MPEG Surround Profile, Level 6",
+    0x3b HD_AAC_L1 "High Definition AAC Profile, Level 1",
+    0x3c ALS_SIMPLE_L1 "ALS Simple Profile, Level 1",
+    // 0x3d - 0x4f - reserved / not yet described here
+    0x50 AAC_L6 "AAC Profile, Level 6",
+    0x51 AAC_L7 "AAC Profile, Level 7",
+    0x52 HE_AAC_L6 "High Efficiency AAC Profile, Level 6",
+    0x53 HE_AAC_L7 "High Efficiency AAC Profile, Level 7",
+    0x54 HE_AAC_V2_L6 "High Efficiency AAC v2 Profile, Level 6",
+    0x55 HE_AAC_V2_L7 "High Efficiency AAC v2 Profile, Level 7",
+    // 0x56 - 0xfd - reserved / not yet described here
+    0xfe NOT_SPECIFIED "No audio profile is specified",
+    0xff NO_AUDIO_CAPABILITY "No audio capability is required",
+}
+
+implement_value_type! {
+    @display
+    /// Formats the description, e.g. `AAC Profile, Level 2`, or `Reserved (n)` for reserved
+    /// values.  The alternate form (`{:#}`, which `{:#?}` also gives) is preceded by the numeric
+    /// value.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// let apli = AudioProfileLevelIndication::AAC_L2;
+    /// assert_eq!("AAC Profile, Level 2", apli.to_string());
+    /// assert_eq!("41 (AAC Profile, Level 2)", format!("{:#}", apli));
+    /// ```
+    #[cfg(feature = "names")]
+    AudioProfileLevelIndication
+}
+
+implement_value_type! {
+    @from_str
+    /// Parses the descriptions produced by the `Display` implementation, or the constant names,
+    /// ignoring case.
+    ///
+    /// ```rust
+    /// # use mpeg4_audio_const::*;
+    /// assert_eq!(Ok(AudioProfileLevelIndication::HE_AAC_L2), "he-aac-l2".parse());
+    /// assert_eq!(
+    ///     Ok(AudioProfileLevelIndication::HE_AAC_L2),
+    ///     "High Efficiency AAC Profile, Level 2".parse()
+    /// );
+    /// ```
+    AudioProfileLevelIndication: ParseAudioProfileLevelIndicationError
+}
+
+implement_value_type! {
+    AudioObjectType: AudioObjectTypeError = AudioObjectTypeError::OutOfRange,
+    SamplingFrequencyIndex: SamplingFrequencyIndexError = SamplingFrequencyIndexError::OutOfRange,
+    ChannelConfiguration: ChannelConfigurationError = ChannelConfigurationError::OutOfRange,
+    AudioProfileLevelIndication: AudioProfileLevelIndicationError =
+        AudioProfileLevelIndicationError::OutOfRange,
+}
+
+/// Defines the errors returned by the `FromStr` implementations of the value types other than
+/// `AudioObjectType` (whose error also suggests near misses).
+macro_rules! implement_parse_error {
+    ( $( $err:ident $t:literal $msg:literal ),* $(,)? ) => {
+        $(
+            #[doc=concat!("Represents an error parsing a `", $t, "` from a string that names no value")]
+            #[derive(PartialEq, Debug)]
+            #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+            #[cfg(feature = "names")]
+            pub struct $err;
+
+            #[cfg(feature = "names")]
+            impl fmt::Display for $err {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str($msg)
+                }
+            }
+
+            #[cfg(feature = "names")]
+            impl core::error::Error for $err {}
+        )*
+    }
+}
+
+implement_parse_error! {
+    ParseSamplingFrequencyIndexError "SamplingFrequencyIndex" "unknown sampling frequency",
+    ParseChannelConfigurationError "ChannelConfiguration" "unknown channel layout name",
+    ParseAudioProfileLevelIndicationError "AudioProfileLevelIndication" "unknown audio profile and level",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn escape_value() {
+        assert_eq!(
+            Err(AudioObjectTypeError::EscapeValue),
+            AudioObjectType::try_from(AOT_ESCAPE_VALUE)
+        );
+    }
+
+    /// Checks the constants against a transcription of the spec's table of object types, so that
+    /// a value missing from (or wrongly added to) the constants is caught.
+    #[test]
+    fn complete() {
+        let mut spec = [None; AOT_VALUE_MAX as usize + 1];
+        for line in include_str!("aot_table.csv").lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ',');
+            let value: usize = fields.next().unwrap().parse().unwrap();
+            spec[value] = Some(fields.next().unwrap());
+        }
+        for (value, constant) in spec.iter().enumerate() {
+            match AudioObjectType::try_from(value) {
+                Ok(aot) => {
+                    assert_eq!(constant.is_some(), aot.is_defined(), "{:?}", aot);
+                    #[cfg(feature = "names")]
+                    assert_eq!(*constant, aot.name());
+                }
+                Err(_) => assert_eq!(None, *constant),
+            }
+        }
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn display() {
+        assert_eq!("AAC LC", format!("{}", AudioObjectType::AAC_LC));
+        assert_eq!(
+            "Reserved (95)",
+            format!("{}", AudioObjectType::try_from(95).unwrap())
+        );
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn alternate() {
+        assert_eq!("2 (AAC LC)", format!("{:#?}", AudioObjectType::AAC_LC));
+        assert_eq!("AAC_LC(2)", format!("{:?}", AudioObjectType::AAC_LC));
+        assert_eq!(
+            "Reserved (13)",
+            format!("{:#}", SamplingFrequencyIndex::try_from(13).unwrap())
+        );
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn reserved_labels() {
+        const LABELS: &[(u8, &str)] = &[(2, "NOT_LC"), (94, "EXPERIMENT")];
+        let aot = AudioObjectType::try_from(94).unwrap();
+        assert_eq!("EXPERIMENT(94)", format!("{:?}", aot.with_labels(LABELS)));
+        assert_eq!("94 (EXPERIMENT)", format!("{:#}", aot.with_labels(LABELS)));
+        assert_eq!("RESERVED(94)", format!("{:?}", aot));
+        assert_eq!(
+            "AAC_LC(2)",
+            format!("{:?}", AudioObjectType::AAC_LC.with_labels(LABELS))
+        );
+    }
+
+    #[test]
+    fn sampling_frequency_mapping() {
+        for v in 0..=12 {
+            let sfi = SamplingFrequencyIndex::try_from(v).unwrap();
+            let frequency = sfi.frequency().unwrap();
+            assert_eq!(Some(sfi), SamplingFrequencyIndex::exact(frequency));
+            if sfi != SamplingFrequencyIndex::FREQ_7350 {
+                assert_eq!(sfi, SamplingFrequencyIndex::nearest(frequency));
+            }
+        }
+        assert_eq!(
+            SamplingFrequencyIndex::FREQ_96000,
+            SamplingFrequencyIndex::nearest(192000)
+        );
+    }
+
+    #[test]
+    fn from_channel_count() {
+        for count in 0..=255 {
+            if let Some(conf) = ChannelConfiguration::from_channel_count(count) {
+                assert_eq!(Some(count), conf.channel_count());
+            }
+        }
+        assert_eq!(None, ChannelConfiguration::from_channel_count(0));
+    }
+
+    #[test]
+    fn value_type_conversions() {
+        assert_eq!(
+            Err(SamplingFrequencyIndexError::OutOfRange),
+            SamplingFrequencyIndex::try_from(300u16)
+        );
+        assert_eq!(
+            Ok(ChannelConfiguration::FIVE_ONE),
+            ChannelConfiguration::try_from(6usize)
+        );
+        assert_eq!(
+            Err(AudioProfileLevelIndicationError::OutOfRange),
+            AudioProfileLevelIndication::try_from(-1)
+        );
+        assert_eq!(
+            Ok(AudioProfileLevelIndication::AAC_L2),
+            AudioProfileLevelIndication::try_from(0x29u32)
+        );
+        assert_eq!(0x29u32, u32::from(AudioProfileLevelIndication::AAC_L2));
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn value_type_names() {
+        for v in 0..=u8::MAX {
+            let apli = AudioProfileLevelIndication::from(v);
+            if apli.description().is_some() {
+                assert_eq!(Ok(apli), apli.to_string().parse());
+            }
+            if let Ok(sfi) = SamplingFrequencyIndex::try_from(v) {
+                if sfi.frequency().is_some() {
+                    assert_eq!(Ok(sfi), sfi.to_string().parse());
+                }
+            }
+        }
+        assert_eq!(
+            Err(ParseAudioProfileLevelIndicationError),
+            "AAC Profile".parse::<AudioProfileLevelIndication>()
+        );
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn channel_layout_names() {
+        for v in 0..=15 {
+            let conf = ChannelConfiguration::try_from(v).unwrap();
+            if conf.channel_count().is_some() || v == 0 {
+                assert_eq!(Ok(conf), conf.to_string().parse());
+            }
+        }
+        assert_eq!(
+            Err(ParseChannelConfigurationError),
+            "quad".parse::<ChannelConfiguration>()
+        );
+    }
+
+    #[test]
+    fn all() {
+        assert!(AudioObjectType::ALL.windows(2).all(|w| w[0].0 < w[1].0));
+        let defined = (0..=95)
+            .filter_map(|v| AudioObjectType::try_from(v).ok())
+            .filter(|aot| aot.is_defined());
+        assert!(defined.eq(AudioObjectType::iter_defined()));
+        assert!(AudioObjectType::iter_defined()
+            .all(|aot| AudioObjectType::try_from_defined(aot.0) == Ok(aot)));
+    }
+
+    #[test]
+    fn family() {
+        for aot in AudioObjectType::iter_defined() {
+            assert!(aot.family().is_some(), "{:?}", aot);
+            #[cfg(feature = "names")]
+            assert_eq!(
+                aot.name().unwrap().starts_with("ER_"),
+                aot.is_error_resilient()
+            );
+        }
+        assert_eq!(None, AudioObjectType::try_from(18).unwrap().family());
+    }
+
+    #[test]
+    fn introduced_in() {
+        for aot in (0..=95).filter_map(|v| AudioObjectType::try_from(v).ok()) {
+            assert_eq!(aot.is_defined(), aot.introduced_in().is_some(), "{:?}", aot);
+        }
+        assert!(AudioObjectType::ER_AAC_LD.is_defined_in(SpecEdition::Edition2001));
+        assert!(!AudioObjectType::try_from(18)
+            .unwrap()
+            .is_defined_in(SpecEdition::Edition2019));
+    }
+
+    #[test]
+    fn supports_tool() {
+        assert!(AudioObjectType::ER_AAC_LD.supports_tool(CodingTool::Ltp));
+        assert!(!AudioObjectType::ER_AAC_LD.supports_tool(CodingTool::Frame960));
+        assert!(!AudioObjectType::try_from(18)
+            .unwrap()
+            .supports_tool(CodingTool::Tns));
+    }
+
+    #[test]
+    fn ordering() {
+        let mut types = [AudioObjectType::USAC, AudioObjectType::AAC_LC];
+        types.sort();
+        assert_eq!([AudioObjectType::AAC_LC, AudioObjectType::USAC], types);
+        assert_eq!(AudioObjectType::NULL, AudioObjectType::default());
+        assert_eq!(
+            ChannelConfiguration::AOT_SPECIFIC_CONFIG,
+            ChannelConfiguration::default()
+        );
+        assert_eq!(
+            AudioProfileLevelIndication::NOT_SPECIFIED,
+            AudioProfileLevelIndication::default()
+        );
+    }
+
+    #[test]
+    fn u8_eq() {
+        let field: u8 = 2;
+        assert!(field == AudioObjectType::AAC_LC);
+        assert!(AudioObjectType::AAC_LC == field);
+        assert!(SamplingFrequencyIndex::FREQ_44100 != field);
+    }
+
+    #[test]
+    fn wider_conversions() {
+        assert_eq!(Ok(AudioObjectType::USAC), AudioObjectType::try_from(42u32));
+        assert_eq!(
+            Err(AudioObjectTypeError::TooLarge(200)),
+            AudioObjectType::try_from(200u16)
+        );
+        assert_eq!(
+            Err(AudioObjectTypeError::OutOfRange),
+            AudioObjectType::try_from(258usize)
+        );
+        assert_eq!(
+            Err(AudioObjectTypeError::OutOfRange),
+            AudioObjectType::try_from(-1)
+        );
+        assert_eq!(2usize, usize::from(AudioObjectType::AAC_LC));
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            "audio object type value 31 is the escape value",
+            AudioObjectTypeError::EscapeValue.to_string()
+        );
+        #[cfg(feature = "names")]
+        assert_eq!(
+            "unknown audio object type name (did you mean AAC_LTP?)",
+            "AAC-LTPP"
+                .parse::<AudioObjectType>()
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn encoded_bit_length() {
+        assert_eq!(95, AOT_VALUE_MAX);
+        assert!(!AudioObjectType::MPEG_SURROUND.is_escape_coded());
+        assert!(AudioObjectType::LAYER1.is_escape_coded());
+        assert_eq!(11, AudioObjectType::LAYER1.encoded_bit_length());
+    }
+
+    #[cfg(feature = "names")]
+    #[test]
+    fn from_str() {
+        assert_eq!(Ok(AudioObjectType::SBR), "aacPlus".parse());
+        assert_eq!(Ok(AudioObjectType::USAC), "XHE_AAC".parse());
+        assert_eq!(Ok(AudioObjectType::LAYER1), "layer-1".parse());
+        let err = "ER-AAC-LX".parse::<AudioObjectType>().unwrap_err();
+        assert_eq!(Some(AudioObjectType::ER_AAC_LC), err.near_misses().next());
+        assert_eq!(3, err.near_misses().count());
+        let err = "flac".parse::<AudioObjectType>().unwrap_err();
+        assert_eq!(0, err.near_misses().count());
+        let long = "x".repeat(100);
+        assert!(long.parse::<AudioObjectType>().is_err());
+    }
+
+    #[test]
+    fn kind() {
+        for v in (0..=95).filter(|v| *v != AOT_ESCAPE_VALUE) {
+            let aot = AudioObjectType::try_from(v).unwrap();
+            assert_eq!(Ok(aot), AudioObjectType::try_from(aot.kind()));
+        }
+        assert_eq!(
+            Err(AudioObjectTypeError::EscapeValue),
+            AudioObjectType::try_from(AudioObjectTypeKind::Reserved(AOT_ESCAPE_VALUE))
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic]
+    fn new_unchecked_escape_value() {
+        let _ = unsafe { AudioObjectType::new_unchecked(AOT_ESCAPE_VALUE) };
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn zerocopy() {
+        use zerocopy::{FromBytes, IntoBytes};
+        let values = [AudioObjectType::AAC_LC, AudioObjectType::SBR];
+        assert_eq!(&[2, 5], values.as_bytes());
+        assert_eq!(
+            Ok(AudioProfileLevelIndication::AAC_L2),
+            AudioProfileLevelIndication::read_from_bytes(&[0x29][..])
+        );
+    }
+
+    #[test]
+    fn counts_to_configuration() {
+        let counts = ElementCounts {
+            sce: 1,
+            cpe: 2,
+            lfe: 1,
+        };
+        assert_eq!(6, counts.channel_count());
+        assert_eq!(
+            Some(ChannelConfiguration::FIVE_ONE),
+            counts.channel_configuration()
+        );
+        assert_eq!(
+            None,
+            ElementCounts {
+                sce: 0,
+                cpe: 2,
+                lfe: 0
+            }
+            .channel_configuration()
+        );
+    }
+
+    #[test]
+    fn configuration_element_counts() {
+        for v in 0..=15 {
+            let conf = ChannelConfiguration::try_from(v).unwrap();
+            let counts = conf.element_counts();
+            assert_eq!(conf.channel_count(), counts.map(|c| c.channel_count()));
+            if let Some(counts) = counts {
+                assert_eq!(
+                    conf.channel_count(),
+                    counts
+                        .channel_configuration()
+                        .and_then(|c| c.channel_count())
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,147 @@
+//! Support for AAC audio tracks in Matroska (and WebM) files.
+//!
+//! Matroska identifies AAC using the `CodecID` [`A_AAC`](constant.A_AAC.html), with the
+//! `AudioSpecificConfig` as the track's `CodecPrivate`,
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::matroska;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! let codec_private = asc.to_codec_private();
+//! assert_eq!(Ok(asc), matroska::validate_track(matroska::A_AAC, Some(&codec_private)));
+//! ```
+//!
+//! Older files may instead use one of a family of `A_AAC/...` codec IDs that encode the
+//! object type in the ID itself, and which have no `CodecPrivate`.  These are recognised by
+//! [`legacy_codec_id()`](fn.legacy_codec_id.html), but should not be produced.
+
+use crate::asc::{AscError, AudioSpecificConfig};
+use crate::AudioObjectType;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The `CodecID` for AAC of any object type, whose `CodecPrivate` is the `AudioSpecificConfig`
+pub const A_AAC: &str = "A_AAC";
+
+/// The deprecated `CodecID` values, each giving the object type and whether SBR is used
+const LEGACY_CODEC_IDS: [(&str, AudioObjectType, bool); 9] = [
+    ("A_AAC/MPEG2/MAIN", AudioObjectType::AAC_MAIN, false),
+    ("A_AAC/MPEG2/LC", AudioObjectType::AAC_LC, false),
+    ("A_AAC/MPEG2/LC/SBR", AudioObjectType::AAC_LC, true),
+    ("A_AAC/MPEG2/SSR", AudioObjectType::AAC_SSR, false),
+    ("A_AAC/MPEG4/MAIN", AudioObjectType::AAC_MAIN, false),
+    ("A_AAC/MPEG4/LC", AudioObjectType::AAC_LC, false),
+    ("A_AAC/MPEG4/LC/SBR", AudioObjectType::AAC_LC, true),
+    ("A_AAC/MPEG4/SSR", AudioObjectType::AAC_SSR, false),
+    ("A_AAC/MPEG4/LTP", AudioObjectType::AAC_LTP, false),
+];
+
+/// Problems found when validating the codec properties of a Matroska track
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum MatroskaError {
+    /// The `CodecID` was not one of the AAC family.
+    NotAac(String),
+    /// The `CodecID` was one of the legacy `A_AAC/...` values, which don't carry a
+    /// `CodecPrivate`, so no `AudioSpecificConfig` is available.
+    LegacyCodecId(String),
+    /// An `A_AAC` track had no `CodecPrivate` element.
+    MissingCodecPrivate,
+    /// The `CodecPrivate` did not hold a valid `AudioSpecificConfig`.
+    Asc(AscError),
+}
+
+impl fmt::Display for MatroskaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatroskaError::NotAac(id) => write!(f, "CodecID {:?} is not AAC", id),
+            MatroskaError::LegacyCodecId(id) => {
+                write!(f, "legacy CodecID {:?} carries no CodecPrivate", id)
+            }
+            MatroskaError::MissingCodecPrivate => f.write_str("missing CodecPrivate"),
+            MatroskaError::Asc(_) => f.write_str("invalid AudioSpecificConfig"),
+        }
+    }
+}
+
+impl core::error::Error for MatroskaError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            MatroskaError::Asc(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl AudioSpecificConfig {
+    /// Parses the `CodecPrivate` of an `A_AAC` track.
+    pub fn from_codec_private(codec_private: &[u8]) -> Result<AudioSpecificConfig, MatroskaError> {
+        AudioSpecificConfig::from_bytes(codec_private).map_err(MatroskaError::Asc)
+    }
+
+    /// Produces the `CodecPrivate` for an `A_AAC` track with this configuration.
+    pub fn to_codec_private(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+}
+
+/// The object type, and whether SBR is used, for the given legacy `A_AAC/...` codec ID, or
+/// `None` if the ID is not one of them.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::matroska::*;
+/// assert_eq!(Some((AudioObjectType::AAC_LC, true)), legacy_codec_id("A_AAC/MPEG4/LC/SBR"));
+/// assert_eq!(None, legacy_codec_id(A_AAC));
+/// ```
+pub fn legacy_codec_id(codec_id: &str) -> Option<(AudioObjectType, bool)> {
+    LEGACY_CODEC_IDS
+        .iter()
+        .find(|(id, _, _)| *id == codec_id)
+        .map(|(_, aot, sbr)| (*aot, *sbr))
+}
+
+/// Checks the `CodecID` and `CodecPrivate` of a track, returning the configuration if the
+/// track is `A_AAC` with a valid `CodecPrivate`.
+pub fn validate_track(
+    codec_id: &str,
+    codec_private: Option<&[u8]>,
+) -> Result<AudioSpecificConfig, MatroskaError> {
+    if codec_id != A_AAC {
+        return Err(if legacy_codec_id(codec_id).is_some() {
+            MatroskaError::LegacyCodecId(codec_id.to_string())
+        } else {
+            MatroskaError::NotAac(codec_id.to_string())
+        });
+    }
+    AudioSpecificConfig::from_codec_private(
+        codec_private.ok_or(MatroskaError::MissingCodecPrivate)?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate() {
+        assert_eq!(
+            Err(MatroskaError::MissingCodecPrivate),
+            validate_track(A_AAC, None)
+        );
+        assert!(matches!(
+            validate_track(A_AAC, Some(&[])),
+            Err(MatroskaError::Asc(AscError::NotEnoughData(_)))
+        ));
+        assert_eq!(
+            Err(MatroskaError::LegacyCodecId("A_AAC/MPEG2/LC".to_string())),
+            validate_track("A_AAC/MPEG2/LC", None)
+        );
+        assert_eq!(
+            Err(MatroskaError::NotAac("A_OPUS".to_string())),
+            validate_track("A_OPUS", Some(&[0x12, 0x10]))
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! MIME types (media types) for MPEG-4 audio in its various transport formats, e.g. for HTTP
+//! `Content-Type` headers.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::mime::*;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! assert_eq!("audio/aac", mime_type(&asc, Transport::Adts));
+//! assert_eq!("audio/mp4; codecs=\"mp4a.40.2\"", mime_type(&asc, Transport::IsoBmff));
+//! ```
+
+use crate::asc::AudioSpecificConfig;
+use crate::AudioObjectType;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+
+/// The MIME type for AAC in ADTS framing
+pub const AUDIO_AAC: &str = "audio/aac";
+/// The MIME type for audio in ISO Base Media File Format files (i.e. MP4 / fragmented MP4),
+/// to which a `codecs` parameter is normally added
+pub const AUDIO_MP4: &str = "audio/mp4";
+/// The MIME type for RFC 3640 RTP payloads
+pub const AUDIO_MPEG4_GENERIC: &str = "audio/mpeg4-generic";
+/// The MIME type for RFC 6416 LATM RTP payloads
+pub const AUDIO_MP4A_LATM: &str = "audio/MP4A-LATM";
+/// The MIME type for MPEG-D USAC RTP payloads
+pub const AUDIO_USAC: &str = "audio/usac";
+
+/// The container or transport in which the audio is carried
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Transport {
+    /// A stream of ADTS frames
+    Adts,
+    /// An ISO Base Media File Format file (MP4, M4A, fragmented MP4 segments)
+    IsoBmff,
+    /// RTP payloads using the RFC 3640 generic format
+    Rtp,
+    /// RTP payloads using the RFC 6416 LATM format
+    RtpLatm,
+}
+
+/// The MIME type for the given configuration carried in the given transport.
+///
+/// For `IsoBmff`, the result includes a `codecs` parameter naming the codec as given by
+/// [`AudioSpecificConfig::codec_string()`](../asc/struct.AudioSpecificConfig.html#method.codec_string).
+/// For `Rtp`, xHE-AAC (the `USAC` object type) has its own type, `audio/usac`.
+///
+/// ```rust
+/// # use mpeg4_audio_const::asc::*;
+/// # use mpeg4_audio_const::mime::*;
+/// let usac = AudioSpecificConfig::from_bytes(&[0xf9, 0x46, 0x40, 0x00]).unwrap();
+/// assert_eq!("audio/usac", mime_type(&usac, Transport::Rtp));
+/// assert_eq!("audio/mp4; codecs=\"mp4a.40.42\"", mime_type(&usac, Transport::IsoBmff));
+/// ```
+pub fn mime_type(asc: &AudioSpecificConfig, transport: Transport) -> String {
+    match transport {
+        Transport::Adts => AUDIO_AAC.to_string(),
+        Transport::IsoBmff => format!("{}; codecs=\"{}\"", AUDIO_MP4, asc.codec_string()),
+        Transport::Rtp if asc.audio_object_type == AudioObjectType::USAC => AUDIO_USAC.to_string(),
+        Transport::Rtp => AUDIO_MPEG4_GENERIC.to_string(),
+        Transport::RtpLatm => AUDIO_MP4A_LATM.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn he_aac() {
+        let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+        assert_eq!(
+            "audio/mp4; codecs=\"mp4a.40.29\"",
+            mime_type(&asc, Transport::IsoBmff)
+        );
+        assert_eq!("audio/mpeg4-generic", mime_type(&asc, Transport::Rtp));
+        assert_eq!("audio/MP4A-LATM", mime_type(&asc, Transport::RtpLatm));
+    }
+}
@@ -0,0 +1,179 @@
+//! Glue for use with the [mpeg2ts-reader](https://crates.io/crates/mpeg2ts-reader) crate,
+//! recognising the AAC-carrying `stream_type` values of a program map table, and parsing the
+//! [descriptors](../ts/index.html) that describe those streams.
+//!
+//! [`AacDescriptor`](enum.AacDescriptor.html) implements mpeg2ts-reader's `Descriptor` trait, so
+//! can be used as the type parameter of `StreamInfo::descriptors()`, while
+//! [`aac_stream_info()`](fn.aac_stream_info.html) collects everything at once,
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::mpeg2ts_reader::*;
+//! # use ::mpeg2ts_reader::psi::pmt::PmtSection;
+//! let pmt_section = [
+//!     0xe1, 0x00, 0xf0, 0x00, // PCR_PID and program_info_length
+//!     0x11, 0xe1, 0x01, 0xf0, 0x03, // LATM stream on PID 0x101
+//!     0x1c, 0x01, 0x2c, // MPEG-4_audio_descriptor
+//! ];
+//! let pmt = PmtSection::from_bytes(&pmt_section).unwrap();
+//! let stream = pmt.streams().next().unwrap();
+//! let info = aac_stream_info(&stream).unwrap();
+//! assert_eq!(AacTransport::Latm, info.transport);
+//! assert_eq!(
+//!     Some(AudioProfileLevelIndication::HE_AAC_L2),
+//!     info.mpeg4_audio.map(|d| d.profile_and_level)
+//! );
+//! ```
+
+use crate::ts::{
+    self, DvbAacDescriptor, Mpeg2AacAudioDescriptor, Mpeg4AudioDescriptor, DVB_AAC_DESCRIPTOR_TAG,
+    MPEG2_AAC_AUDIO_DESCRIPTOR_TAG, MPEG4_AUDIO_DESCRIPTOR_TAG,
+};
+use ::mpeg2ts_reader::descriptor::{Descriptor, DescriptorError, UnknownDescriptor};
+use ::mpeg2ts_reader::psi::pmt::StreamInfo;
+use ::mpeg2ts_reader::StreamType;
+
+/// The framing of an AAC elementary stream, as identified by its `stream_type`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum AacTransport {
+    /// `stream_type` `0x0f`: ADTS frames
+    Adts,
+    /// `stream_type` `0x11`: LATM within LOAS
+    Latm,
+}
+
+impl AacTransport {
+    /// The transport for the given `stream_type`, or `None` if the stream type does not carry
+    /// AAC.
+    pub fn from_stream_type(stream_type: StreamType) -> Option<AacTransport> {
+        match stream_type.0 {
+            ts::STREAM_TYPE_ADTS => Some(AacTransport::Adts),
+            ts::STREAM_TYPE_LATM => Some(AacTransport::Latm),
+            _ => None,
+        }
+    }
+
+    pub fn stream_type(self) -> StreamType {
+        match self {
+            AacTransport::Adts => StreamType(ts::STREAM_TYPE_ADTS),
+            AacTransport::Latm => StreamType(ts::STREAM_TYPE_LATM),
+        }
+    }
+}
+
+/// A descriptor relevant to AAC streams, or any other descriptor
+#[derive(Debug)]
+pub enum AacDescriptor<'buf> {
+    Mpeg4Audio(Mpeg4AudioDescriptor),
+    Mpeg2AacAudio(Mpeg2AacAudioDescriptor),
+    DvbAac(DvbAacDescriptor),
+    /// One of the descriptors above whose body could not be parsed
+    Invalid {
+        tag: u8,
+        error: ts::DescriptorError,
+    },
+    Other(UnknownDescriptor<'buf>),
+}
+
+impl<'buf> Descriptor<'buf> for AacDescriptor<'buf> {
+    fn from_bytes(buf: &'buf [u8]) -> Result<Self, DescriptorError> {
+        if buf.len() < 2 {
+            return Err(DescriptorError::BufferTooShort { buflen: buf.len() });
+        }
+        let tag = buf[0];
+        let len = usize::from(buf[1]);
+        if buf.len() < 2 + len {
+            return Err(DescriptorError::TagTooLongForBuffer {
+                taglen: len,
+                buflen: buf.len(),
+            });
+        }
+        let result = match tag {
+            MPEG4_AUDIO_DESCRIPTOR_TAG => {
+                Mpeg4AudioDescriptor::from_bytes(buf).map(AacDescriptor::Mpeg4Audio)
+            }
+            MPEG2_AAC_AUDIO_DESCRIPTOR_TAG => {
+                Mpeg2AacAudioDescriptor::from_bytes(buf).map(AacDescriptor::Mpeg2AacAudio)
+            }
+            DVB_AAC_DESCRIPTOR_TAG => DvbAacDescriptor::from_bytes(buf).map(AacDescriptor::DvbAac),
+            _ => return UnknownDescriptor::new(tag, &buf[2..2 + len]).map(AacDescriptor::Other),
+        };
+        Ok(result.unwrap_or_else(|error| AacDescriptor::Invalid { tag, error }))
+    }
+}
+
+/// The AAC-related properties of a PMT elementary stream entry
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct AacStreamInfo {
+    pub transport: AacTransport,
+    pub mpeg4_audio: Option<Mpeg4AudioDescriptor>,
+    pub mpeg2_aac_audio: Option<Mpeg2AacAudioDescriptor>,
+    pub dvb_aac: Option<DvbAacDescriptor>,
+}
+
+/// Describes the given stream, or returns `None` if its `stream_type` does not carry AAC.
+///
+/// Descriptors that are malformed are ignored, and where a descriptor is repeated, the first
+/// is used.
+pub fn aac_stream_info(stream: &StreamInfo<'_>) -> Option<AacStreamInfo> {
+    let mut info = AacStreamInfo {
+        transport: AacTransport::from_stream_type(stream.stream_type())?,
+        mpeg4_audio: None,
+        mpeg2_aac_audio: None,
+        dvb_aac: None,
+    };
+    for desc in stream.descriptors::<AacDescriptor<'_>>() {
+        match desc {
+            Ok(AacDescriptor::Mpeg4Audio(d)) => {
+                info.mpeg4_audio.get_or_insert(d);
+            }
+            Ok(AacDescriptor::Mpeg2AacAudio(d)) => {
+                info.mpeg2_aac_audio.get_or_insert(d);
+            }
+            Ok(AacDescriptor::DvbAac(d)) => {
+                info.dvb_aac.get_or_insert(d);
+            }
+            _ => (),
+        }
+    }
+    Some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AudioProfileLevelIndication;
+    use ::mpeg2ts_reader::psi::pmt::PmtSection;
+
+    #[test]
+    fn descriptors() {
+        let pmt_section = [
+            0xe1, 0x00, 0xf0, 0x00, // PCR_PID and program_info_length
+            0x0f, 0xe1, 0x01, 0xf0, 0x0e, // ADTS stream on PID 0x101
+            0x0a, 0x04, b'e', b'n', b'g', 0x00, // ISO_639_language_descriptor
+            0x2b, 0x03, 0x01, 0x10, 0x00, // MPEG-2_AAC_audio_descriptor, bad channel config
+            0x7c, 0x01, 0x28, // DVB AAC_descriptor
+            0x1b, 0xe1, 0x02, 0xf0, 0x00, // AVC stream on PID 0x102
+        ];
+        let pmt = PmtSection::from_bytes(&pmt_section).unwrap();
+        let mut streams = pmt.streams();
+        let adts = streams.next().unwrap();
+        let descs: Vec<_> = adts.descriptors::<AacDescriptor<'_>>().collect();
+        assert!(matches!(descs[0], Ok(AacDescriptor::Other(_))));
+        assert!(matches!(
+            descs[1],
+            Ok(AacDescriptor::Invalid {
+                tag: MPEG2_AAC_AUDIO_DESCRIPTOR_TAG,
+                ..
+            })
+        ));
+        let info = aac_stream_info(&adts).unwrap();
+        assert_eq!(AacTransport::Adts, info.transport);
+        assert_eq!(None, info.mpeg2_aac_audio);
+        assert_eq!(
+            Some(AudioProfileLevelIndication::AAC_L1),
+            info.dvb_aac.map(|d| d.profile_and_level)
+        );
+        assert_eq!(None, aac_stream_info(&streams.next().unwrap()));
+    }
+}
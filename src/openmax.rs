@@ -0,0 +1,164 @@
+//! Mappings to the values of the OpenMAX IL `OMX_AUDIO_AACPROFILETYPE` and
+//! `OMX_AUDIO_AACSTREAMFORMATTYPE` enums, as used in the `eAACProfile` and `eAACStreamFormat`
+//! fields of `OMX_AUDIO_PARAM_AACPROFILETYPE`.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::openmax::*;
+//! # use mpeg4_audio_const::adts::MpegVersion;
+//! assert_eq!(Some(OMX_AUDIO_AAC_OBJECT_HE), to_profile(AudioObjectType::SBR));
+//! assert_eq!(Some(AudioObjectType::ER_AAC_LD), from_profile(OMX_AUDIO_AAC_OBJECT_LD));
+//! assert_eq!(OMX_AUDIO_AAC_STREAM_FORMAT_MP2_ADTS, adts_stream_format(MpegVersion::Mpeg2));
+//! ```
+
+use crate::adts::MpegVersion;
+use crate::asc::AudioSpecificConfig;
+use crate::mime::Transport;
+use crate::AudioObjectType;
+
+/// `OMX_AUDIO_AACObjectNull`
+pub const OMX_AUDIO_AAC_OBJECT_NULL: u32 = 0;
+/// `OMX_AUDIO_AACObjectMain`
+pub const OMX_AUDIO_AAC_OBJECT_MAIN: u32 = 1;
+/// `OMX_AUDIO_AACObjectLC`
+pub const OMX_AUDIO_AAC_OBJECT_LC: u32 = 2;
+/// `OMX_AUDIO_AACObjectSSR`
+pub const OMX_AUDIO_AAC_OBJECT_SSR: u32 = 3;
+/// `OMX_AUDIO_AACObjectLTP`
+pub const OMX_AUDIO_AAC_OBJECT_LTP: u32 = 4;
+/// `OMX_AUDIO_AACObjectHE`, for HE-AAC
+pub const OMX_AUDIO_AAC_OBJECT_HE: u32 = 5;
+/// `OMX_AUDIO_AACObjectScalable`
+pub const OMX_AUDIO_AAC_OBJECT_SCALABLE: u32 = 6;
+/// `OMX_AUDIO_AACObjectERLC`
+pub const OMX_AUDIO_AAC_OBJECT_ERLC: u32 = 17;
+/// `OMX_AUDIO_AACObjectLD`
+pub const OMX_AUDIO_AAC_OBJECT_LD: u32 = 23;
+/// `OMX_AUDIO_AACObjectHE_PS`, for HE-AAC v2
+pub const OMX_AUDIO_AAC_OBJECT_HE_PS: u32 = 29;
+/// `OMX_AUDIO_AACObjectELD` (an Android extension to the Khronos enum)
+pub const OMX_AUDIO_AAC_OBJECT_ELD: u32 = 39;
+
+/// `OMX_AUDIO_AACStreamFormatMP2ADTS`: ADTS frames with the `ID` bit set (MPEG-2)
+pub const OMX_AUDIO_AAC_STREAM_FORMAT_MP2_ADTS: u32 = 0;
+/// `OMX_AUDIO_AACStreamFormatMP4ADTS`: ADTS frames with the `ID` bit clear (MPEG-4)
+pub const OMX_AUDIO_AAC_STREAM_FORMAT_MP4_ADTS: u32 = 1;
+/// `OMX_AUDIO_AACStreamFormatMP4LOAS`
+pub const OMX_AUDIO_AAC_STREAM_FORMAT_MP4_LOAS: u32 = 2;
+/// `OMX_AUDIO_AACStreamFormatMP4LATM`
+pub const OMX_AUDIO_AAC_STREAM_FORMAT_MP4_LATM: u32 = 3;
+/// `OMX_AUDIO_AACStreamFormatADIF`
+pub const OMX_AUDIO_AAC_STREAM_FORMAT_ADIF: u32 = 4;
+/// `OMX_AUDIO_AACStreamFormatMP4FF`: access units from an MP4 file
+pub const OMX_AUDIO_AAC_STREAM_FORMAT_MP4_FF: u32 = 5;
+/// `OMX_AUDIO_AACStreamFormatRAW`: raw access units
+pub const OMX_AUDIO_AAC_STREAM_FORMAT_RAW: u32 = 6;
+
+/// The `OMX_AUDIO_AACPROFILETYPE` value for the given audio object type, or `None` if OpenMAX
+/// defines no value for it.
+///
+/// OpenMAX numbers object types as the spec does, so the value is that of the object type.
+pub fn to_profile(audio_object_type: AudioObjectType) -> Option<u32> {
+    match audio_object_type {
+        AudioObjectType::AAC_MAIN
+        | AudioObjectType::AAC_LC
+        | AudioObjectType::AAC_SSR
+        | AudioObjectType::AAC_LTP
+        | AudioObjectType::SBR
+        | AudioObjectType::AAC_SCALABLE
+        | AudioObjectType::ER_AAC_LC
+        | AudioObjectType::ER_AAC_LD
+        | AudioObjectType::PS
+        | AudioObjectType::ER_AAC_ELD => Some(u32::from(u8::from(audio_object_type))),
+        _ => None,
+    }
+}
+
+/// The audio object type for the given `OMX_AUDIO_AACPROFILETYPE` value, or `None` for
+/// `OMX_AUDIO_AACObjectNull` and for values OpenMAX doesn't define.
+pub fn from_profile(value: u32) -> Option<AudioObjectType> {
+    Some(match value {
+        OMX_AUDIO_AAC_OBJECT_MAIN => AudioObjectType::AAC_MAIN,
+        OMX_AUDIO_AAC_OBJECT_LC => AudioObjectType::AAC_LC,
+        OMX_AUDIO_AAC_OBJECT_SSR => AudioObjectType::AAC_SSR,
+        OMX_AUDIO_AAC_OBJECT_LTP => AudioObjectType::AAC_LTP,
+        OMX_AUDIO_AAC_OBJECT_HE => AudioObjectType::SBR,
+        OMX_AUDIO_AAC_OBJECT_SCALABLE => AudioObjectType::AAC_SCALABLE,
+        OMX_AUDIO_AAC_OBJECT_ERLC => AudioObjectType::ER_AAC_LC,
+        OMX_AUDIO_AAC_OBJECT_LD => AudioObjectType::ER_AAC_LD,
+        OMX_AUDIO_AAC_OBJECT_HE_PS => AudioObjectType::PS,
+        OMX_AUDIO_AAC_OBJECT_ELD => AudioObjectType::ER_AAC_ELD,
+        _ => return None,
+    })
+}
+
+/// The `OMX_AUDIO_AACPROFILETYPE` value for the given configuration, which is
+/// `OMX_AUDIO_AACObjectHE` or `OMX_AUDIO_AACObjectHE_PS` when the presence of SBR or PS is
+/// signalled.
+pub fn profile(asc: &AudioSpecificConfig) -> Option<u32> {
+    to_profile(asc.signalled_object_type())
+}
+
+/// The `OMX_AUDIO_AACSTREAMFORMATTYPE` value for ADTS frames with the given `ID` bit.
+pub fn adts_stream_format(mpeg_version: MpegVersion) -> u32 {
+    match mpeg_version {
+        MpegVersion::Mpeg2 => OMX_AUDIO_AAC_STREAM_FORMAT_MP2_ADTS,
+        MpegVersion::Mpeg4 => OMX_AUDIO_AAC_STREAM_FORMAT_MP4_ADTS,
+    }
+}
+
+/// The `OMX_AUDIO_AACSTREAMFORMATTYPE` value for the given transport.
+///
+/// `Adts` maps to `OMX_AUDIO_AACStreamFormatMP4ADTS` (use
+/// [`adts_stream_format()`](fn.adts_stream_format.html) where the `ID` bit is known), RFC 3640
+/// RTP payloads carry raw access units, and RFC 6416 payloads carry LATM without LOAS.
+pub fn stream_format(transport: Transport) -> u32 {
+    match transport {
+        Transport::Adts => OMX_AUDIO_AAC_STREAM_FORMAT_MP4_ADTS,
+        Transport::IsoBmff => OMX_AUDIO_AAC_STREAM_FORMAT_MP4_FF,
+        Transport::Rtp => OMX_AUDIO_AAC_STREAM_FORMAT_RAW,
+        Transport::RtpLatm => OMX_AUDIO_AAC_STREAM_FORMAT_MP4_LATM,
+    }
+}
+
+/// The transport for the given `OMX_AUDIO_AACSTREAMFORMATTYPE` value, or `None` if there is no
+/// corresponding [`Transport`](../mime/enum.Transport.html).  Since raw access units could
+/// come from several transports, `OMX_AUDIO_AACStreamFormatRAW` maps to `Rtp`.
+pub fn from_stream_format(value: u32) -> Option<Transport> {
+    match value {
+        OMX_AUDIO_AAC_STREAM_FORMAT_MP2_ADTS | OMX_AUDIO_AAC_STREAM_FORMAT_MP4_ADTS => {
+            Some(Transport::Adts)
+        }
+        OMX_AUDIO_AAC_STREAM_FORMAT_MP4_LATM => Some(Transport::RtpLatm),
+        OMX_AUDIO_AAC_STREAM_FORMAT_MP4_FF => Some(Transport::IsoBmff),
+        OMX_AUDIO_AAC_STREAM_FORMAT_RAW => Some(Transport::Rtp),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn roundtrip() {
+        for aot in 0..=95 {
+            if let Ok(aot) = AudioObjectType::try_from(aot) {
+                if let Some(value) = to_profile(aot) {
+                    assert_eq!(Some(aot), from_profile(value));
+                }
+            }
+        }
+        assert_eq!(None, from_profile(OMX_AUDIO_AAC_OBJECT_NULL));
+        for t in &[
+            Transport::Adts,
+            Transport::IsoBmff,
+            Transport::Rtp,
+            Transport::RtpLatm,
+        ] {
+            assert_eq!(Some(*t), from_stream_format(stream_format(*t)));
+        }
+        assert_eq!(None, from_stream_format(OMX_AUDIO_AAC_STREAM_FORMAT_ADIF));
+    }
+}
@@ -0,0 +1,199 @@
+//! Options controlling how strictly the parsers check their input against the spec.
+//!
+//! By default the parsers accept reserved values and other departures from the spec wherever
+//! the data can still be interpreted, as a player must.  When ingesting content it is more
+//! useful to know about these problems, so the `from_bytes_with_options()` parsing functions
+//! either reject them (with [`Strictness::Strict`](enum.Strictness.html)) or report them
+//! alongside the parsed value.
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")] {
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::options::*;
+//! // AAC LC, using the reserved sampling frequency index 13
+//! let buf = [0x16, 0x90];
+//! let parsed = AudioSpecificConfig::from_bytes_with_options(&buf, &ParseOptions::PERMISSIVE)
+//!     .unwrap();
+//! assert_eq!(1, parsed.warnings.len());
+//! assert_eq!(
+//!     "bits 5..9 samplingFrequencyIndex=13: reserved value",
+//!     parsed.warnings[0].to_string()
+//! );
+//! assert!(AudioSpecificConfig::from_bytes_with_options(&buf, &ParseOptions::STRICT).is_err());
+//! # }
+//! ```
+//!
+//! The options also give [`Limits`](struct.Limits.html) on the size of the structures
+//! accepted, for services parsing untrusted input to bound the work done for each.
+
+#[cfg(feature = "alloc")]
+use crate::bits::BitReader;
+use crate::trace::TraceEntry;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+
+/// How the parsers respond to data that does not conform to the spec
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Strictness {
+    /// Reject the data, with an error giving the first violation found.
+    Strict,
+    /// Accept the data wherever it can be interpreted, reporting the violations as warnings.
+    #[default]
+    Permissive,
+}
+
+/// Options for the `from_bytes_with_options()` parsing functions
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParseOptions {
+    /// Whether violations of the spec are rejected or reported as warnings
+    pub strictness: Strictness,
+    /// Bounds on the size of the structures accepted
+    pub limits: Limits,
+    /// Whether data other than zero padding following an `AudioSpecificConfig` in its buffer is
+    /// a violation ([`Violation::TrailingData`](enum.Violation.html#variant.TrailingData)).
+    ///
+    /// Off in both [`STRICT`](#associatedconstant.STRICT) and
+    /// [`PERMISSIVE`](#associatedconstant.PERMISSIVE), so that by default the same buffers are
+    /// accepted as by `from_bytes()`, which ignores whatever follows the config.
+    ///
+    /// The check can only find data following a config whose end is known: a specific config
+    /// which the crate does not interpret (`SpecificConfig::Unparsed`), or an
+    /// `ErrorProtectionSpecificConfig`, is taken to run to the end of the buffer, so any data
+    /// after it is kept as part of it and never reported.
+    pub check_trailing_data: bool,
+}
+
+impl ParseOptions {
+    /// Options rejecting any violation of the spec
+    pub const STRICT: ParseOptions = ParseOptions {
+        strictness: Strictness::Strict,
+        limits: Limits::DEFAULT,
+        check_trailing_data: false,
+    };
+    /// Options accepting violations of the spec, reporting them as warnings
+    pub const PERMISSIVE: ParseOptions = ParseOptions {
+        strictness: Strictness::Permissive,
+        limits: Limits::DEFAULT,
+        check_trailing_data: false,
+    };
+
+    /// Parses the given buffer with the given function, checking for violations as these
+    /// options require.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn parse<'a, T, E: From<Violation>>(
+        &self,
+        buf: &'a [u8],
+        read: impl FnOnce(&mut BitReader<'a>) -> Result<T, E>,
+    ) -> Result<Parsed<T>, E> {
+        let mut r = BitReader::new(buf);
+        r.set_limits(self.limits);
+        r.enable_checks();
+        let value = read(&mut r)?;
+        let warnings = r.take_violations();
+        match (self.strictness, warnings.first()) {
+            (Strictness::Strict, Some(&violation)) => Err(violation.into()),
+            _ => Ok(Parsed { value, warnings }),
+        }
+    }
+}
+
+/// Bounds on the structures that the parsers accept, beyond which they fail with an error
+/// rather than continuing
+///
+/// The defaults are the most that the syntax can signal, so only tighter limits change what is
+/// accepted.
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// # use mpeg4_audio_const::latm::*;
+/// # use mpeg4_audio_const::options::*;
+/// let options = ParseOptions {
+///     limits: Limits {
+///         max_latm_layers: 1,
+///         ..Limits::DEFAULT
+///     },
+///     ..ParseOptions::STRICT
+/// };
+/// let buf = [0x40, 0x00, 0x24, 0x20, 0x3f, 0xc0];
+/// assert!(StreamMuxConfig::from_bytes_with_options(&buf, &options).is_ok());
+/// # }
+/// ```
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Limits {
+    /// The most elements (of all kinds, in total) accepted in a `program_config_element()`
+    pub max_pce_elements: usize,
+    /// The most layers (over all programs) accepted in a `StreamMuxConfig`
+    pub max_latm_layers: usize,
+    /// The greatest length, in bits, accepted for configuration data whose length is signalled
+    /// explicitly (an `AudioSpecificConfig` within a `StreamMuxConfig`, or an extension of an
+    /// `ELDSpecificConfig`)
+    pub max_explicit_length: usize,
+}
+
+impl Limits {
+    /// The most that the syntax can signal, so imposing no further limit
+    pub const DEFAULT: Limits = Limits {
+        max_pce_elements: 15 + 15 + 15 + 3 + 7 + 15,
+        max_latm_layers: 16 * 8,
+        max_explicit_length: usize::MAX,
+    };
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits::DEFAULT
+    }
+}
+
+/// A departure from the spec found while parsing, with the field concerned
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Violation {
+    /// The field held a value that the spec reserves.
+    ReservedValue(TraceEntry),
+    /// The field held a value that the spec does not permit in this context (e.g. a field
+    /// which must always be zero).
+    UnexpectedValue(TraceEntry),
+    /// Data other than zero padding to a byte boundary followed the structure (which is only
+    /// found where the extent of the structure is known; see the `ParseOptions` field
+    /// [`check_trailing_data`](struct.ParseOptions.html#structfield.check_trailing_data)).
+    TrailingData(TraceEntry),
+}
+
+impl Violation {
+    /// The field concerned.
+    pub fn field(&self) -> &TraceEntry {
+        match self {
+            Violation::ReservedValue(at)
+            | Violation::UnexpectedValue(at)
+            | Violation::TrailingData(at) => at,
+        }
+    }
+}
+
+/// Formats the violation as e.g. `bits 5..9 samplingFrequencyIndex=13: reserved value`.
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::ReservedValue(at) => write!(f, "{}: reserved value", at),
+            Violation::UnexpectedValue(at) => write!(f, "{}: value not permitted here", at),
+            Violation::TrailingData(at) => write!(f, "{}: data follows the structure", at),
+        }
+    }
+}
+
+impl core::error::Error for Violation {}
+
+/// A parsed value, along with any violations of the spec accepted while parsing it
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Parsed<T> {
+    pub value: T,
+    /// The violations found, in the order of the fields concerned (always empty when parsed
+    /// with `Strictness::Strict`)
+    pub warnings: Vec<Violation>,
+}
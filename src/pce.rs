@@ -0,0 +1,422 @@
+//! The _program config element_, which describes a channel layout in terms of the syntactic
+//! elements making up each `raw_data_block()`.
+//!
+//! A PCE may appear in the `GASpecificConfig` when `channelConfiguration` is `0`, or in-band
+//! as an element of a `raw_data_block()` (see
+//! [`raw_data_block::leading_pce()`](../raw_data_block/fn.leading_pce.html)).
+
+use crate::asc::RawBits;
+use crate::bits::{BitReader, BitWriter, EndOfData};
+use crate::bitstream::{read_counted, write_bits, FromBitstream, ToBitstream};
+use crate::trace::TraceEntry;
+use crate::{AudioObjectType, SamplingFrequencyIndex};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// Errors that may be produced while parsing a program config element
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum PceError {
+    /// The data ended before the end of the element, while reading the given syntax element.
+    NotEnoughData(TraceEntry),
+    /// The `sampling_frequency_index` field held the escape value `15`, which is not permitted
+    /// in a program config element.
+    BadSamplingFrequencyIndex(TraceEntry),
+    /// The element signalled more elements than permitted by the
+    /// [`Limits`](../options/struct.Limits.html) in use, the given field taking the total past
+    /// the limit.
+    TooManyElements(TraceEntry),
+}
+
+impl fmt::Display for PceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PceError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            PceError::BadSamplingFrequencyIndex(at) => {
+                write!(f, "escape value not permitted, {}", at)
+            }
+            PceError::TooManyElements(at) => write!(f, "too many elements, reading {}", at),
+        }
+    }
+}
+
+impl core::error::Error for PceError {}
+
+impl From<EndOfData> for PceError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        PceError::NotEnoughData(at)
+    }
+}
+
+/// A front, side or back channel element referenced by a program config element
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelElement {
+    /// `true` for a channel pair element, `false` for a single channel element
+    pub is_cpe: bool,
+    /// The `element_instance_tag` of the referenced element
+    pub tag_select: u8,
+}
+
+impl ChannelElement {
+    /// The number of audio channels carried by the referenced element.
+    pub fn channel_count(&self) -> u32 {
+        if self.is_cpe {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// A coupling channel element referenced by a program config element
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CcElement {
+    /// `true` if the coupling channel element is independently switched
+    pub is_ind_sw: bool,
+    /// The `element_instance_tag` of the referenced element
+    pub tag_select: u8,
+}
+
+/// The matrix-mixdown parameters that may be given in a program config element
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MatrixMixdown {
+    /// 2-bit index selecting the mixdown coefficient
+    pub idx: u8,
+    pub pseudo_surround_enable: bool,
+}
+
+/// A parsed `program_config_element()`
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::pce::*;
+/// let pce = ProgramConfigElement::from_bytes(&[0x04, 0xc4, 0x00, 0x00, 0x20, 0x00]).unwrap();
+/// assert_eq!(AudioObjectType::AAC_LC, pce.object_type);
+/// assert_eq!(SamplingFrequencyIndex::FREQ_48000, pce.sampling_frequency_index);
+/// assert_eq!(1, pce.front_channel_elements.len());
+/// assert_eq!(2, pce.channel_count());
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProgramConfigElement {
+    pub element_instance_tag: u8,
+    /// The audio object type, derived from the 2-bit `object_type` field by adding `1`
+    pub object_type: AudioObjectType,
+    pub sampling_frequency_index: SamplingFrequencyIndex,
+    pub front_channel_elements: Vec<ChannelElement>,
+    pub side_channel_elements: Vec<ChannelElement>,
+    pub back_channel_elements: Vec<ChannelElement>,
+    /// The `element_instance_tag` values of the LFE channel elements
+    pub lfe_channel_elements: Vec<u8>,
+    /// The `element_instance_tag` values of the associated data elements
+    pub assoc_data_elements: Vec<u8>,
+    pub valid_cc_elements: Vec<CcElement>,
+    pub mono_mixdown_element_number: Option<u8>,
+    pub stereo_mixdown_element_number: Option<u8>,
+    pub matrix_mixdown: Option<MatrixMixdown>,
+    /// The content of the `comment_field_data` bytes
+    pub comment: Vec<u8>,
+}
+
+impl ProgramConfigElement {
+    /// Parses a program config element from the start of the given buffer (which must not
+    /// include the 3-bit `id_syn_ele` that precedes the element within a `raw_data_block()`).
+    pub fn from_bytes(buf: &[u8]) -> Result<ProgramConfigElement, PceError> {
+        Self::read(&mut BitReader::new(buf))
+    }
+
+    /// Reads the element from the given reader; the `byte_alignment()` within the element is
+    /// relative to the start of the reader's data.
+    pub(crate) fn read(r: &mut BitReader<'_>) -> Result<ProgramConfigElement, PceError> {
+        let element_instance_tag = r.named("element_instance_tag").read_u8(4)?;
+        // values 0..=3 map to a-o-t values 1..=4, none of which can be rejected
+        let object_type =
+            AudioObjectType::try_from(r.named("object_type").read_u8(2)? + 1).unwrap();
+        let sfi = r.named("sampling_frequency_index").read_u8(4)?;
+        let sampling_frequency_index = SamplingFrequencyIndex::try_from(sfi)
+            .map_err(|_| PceError::BadSamplingFrequencyIndex(r.last_read()))?;
+        let mut total = 0;
+        let mut read_count = |r: &mut BitReader<'_>, name, count| {
+            let num = r.named(name).read_u8(count)?;
+            total += usize::from(num);
+            if total > r.limits().max_pce_elements {
+                return Err(PceError::TooManyElements(r.last_read()));
+            }
+            Ok(num)
+        };
+        let num_front_channel_elements = read_count(r, "num_front_channel_elements", 4)?;
+        let num_side_channel_elements = read_count(r, "num_side_channel_elements", 4)?;
+        let num_back_channel_elements = read_count(r, "num_back_channel_elements", 4)?;
+        let num_lfe_channel_elements = read_count(r, "num_lfe_channel_elements", 2)?;
+        let num_assoc_data_elements = read_count(r, "num_assoc_data_elements", 3)?;
+        let num_valid_cc_elements = read_count(r, "num_valid_cc_elements", 4)?;
+        let mono_mixdown_element_number = if r.named("mono_mixdown_present").read_bool()? {
+            Some(r.named("mono_mixdown_element_number").read_u8(4)?)
+        } else {
+            None
+        };
+        let stereo_mixdown_element_number = if r.named("stereo_mixdown_present").read_bool()? {
+            Some(r.named("stereo_mixdown_element_number").read_u8(4)?)
+        } else {
+            None
+        };
+        let matrix_mixdown = if r.named("matrix_mixdown_idx_present").read_bool()? {
+            Some(MatrixMixdown {
+                idx: r.named("matrix_mixdown_idx").read_u8(2)?,
+                pseudo_surround_enable: r.named("pseudo_surround_enable").read_bool()?,
+            })
+        } else {
+            None
+        };
+        let front_channel_elements = read_channel_elements(
+            r,
+            num_front_channel_elements,
+            ("front_element_is_cpe", "front_element_tag_select"),
+        )?;
+        let side_channel_elements = read_channel_elements(
+            r,
+            num_side_channel_elements,
+            ("side_element_is_cpe", "side_element_tag_select"),
+        )?;
+        let back_channel_elements = read_channel_elements(
+            r,
+            num_back_channel_elements,
+            ("back_element_is_cpe", "back_element_tag_select"),
+        )?;
+        let lfe_channel_elements = (0..num_lfe_channel_elements)
+            .map(|_| r.named("lfe_element_tag_select").read_u8(4))
+            .collect::<Result<_, _>>()?;
+        let assoc_data_elements = (0..num_assoc_data_elements)
+            .map(|_| r.named("assoc_data_element_tag_select").read_u8(4))
+            .collect::<Result<_, _>>()?;
+        let valid_cc_elements = (0..num_valid_cc_elements)
+            .map(|_| {
+                Ok(CcElement {
+                    is_ind_sw: r.named("cc_element_is_ind_sw").read_bool()?,
+                    tag_select: r.named("valid_cc_element_tag_select").read_u8(4)?,
+                })
+            })
+            .collect::<Result<_, EndOfData>>()?;
+        r.byte_align();
+        let comment_field_bytes = r.named("comment_field_bytes").read_u8(8)?;
+        let comment = (0..comment_field_bytes)
+            .map(|_| r.named("comment_field_data").read_u8(8))
+            .collect::<Result<_, _>>()?;
+        Ok(ProgramConfigElement {
+            element_instance_tag,
+            object_type,
+            sampling_frequency_index,
+            front_channel_elements,
+            side_channel_elements,
+            back_channel_elements,
+            lfe_channel_elements,
+            assoc_data_elements,
+            valid_cc_elements,
+            mono_mixdown_element_number,
+            stereo_mixdown_element_number,
+            matrix_mixdown,
+            comment,
+        })
+    }
+
+    /// Writes the element; the `byte_alignment()` within the element is relative to the start
+    /// of the writer's data.  Element counts and the comment length are truncated to the widths
+    /// of their fields.
+    pub(crate) fn write(&self, w: &mut BitWriter) {
+        w.write(4, u32::from(self.element_instance_tag));
+        w.write(2, u32::from(u8::from(self.object_type).wrapping_sub(1)));
+        w.write(4, u32::from(u8::from(self.sampling_frequency_index)));
+        w.write(4, self.front_channel_elements.len() as u32);
+        w.write(4, self.side_channel_elements.len() as u32);
+        w.write(4, self.back_channel_elements.len() as u32);
+        w.write(2, self.lfe_channel_elements.len() as u32);
+        w.write(3, self.assoc_data_elements.len() as u32);
+        w.write(4, self.valid_cc_elements.len() as u32);
+        for mixdown in &[
+            self.mono_mixdown_element_number,
+            self.stereo_mixdown_element_number,
+        ] {
+            w.write_bool(mixdown.is_some());
+            if let Some(number) = mixdown {
+                w.write(4, u32::from(*number));
+            }
+        }
+        w.write_bool(self.matrix_mixdown.is_some());
+        if let Some(ref matrix_mixdown) = self.matrix_mixdown {
+            w.write(2, u32::from(matrix_mixdown.idx));
+            w.write_bool(matrix_mixdown.pseudo_surround_enable);
+        }
+        for element in self
+            .front_channel_elements
+            .iter()
+            .chain(self.side_channel_elements.iter())
+            .chain(self.back_channel_elements.iter())
+        {
+            w.write_bool(element.is_cpe);
+            w.write(4, u32::from(element.tag_select));
+        }
+        for tag in self
+            .lfe_channel_elements
+            .iter()
+            .chain(self.assoc_data_elements.iter())
+        {
+            w.write(4, u32::from(*tag));
+        }
+        for cc in &self.valid_cc_elements {
+            w.write_bool(cc.is_ind_sw);
+            w.write(4, u32::from(cc.tag_select));
+        }
+        w.byte_align();
+        let comment = &self.comment[..self.comment.len().min(255)];
+        w.write(8, comment.len() as u32);
+        for b in comment {
+            w.write(8, u32::from(*b));
+        }
+    }
+
+    /// The total number of output channels described by this element (front, side and back
+    /// channels, plus LFE channels).
+    pub fn channel_count(&self) -> u32 {
+        self.front_channel_elements
+            .iter()
+            .chain(self.side_channel_elements.iter())
+            .chain(self.back_channel_elements.iter())
+            .map(ChannelElement::channel_count)
+            .sum::<u32>()
+            + self.lfe_channel_elements.len() as u32
+    }
+}
+
+impl FromBitstream for ProgramConfigElement {
+    type Context = ();
+    type Error = PceError;
+
+    fn from_bitstream_with_length(data: &[u8], _: ()) -> Result<(Self, usize), PceError> {
+        read_counted(data, Self::read)
+    }
+}
+
+impl ToBitstream for ProgramConfigElement {
+    fn to_bitstream(&self) -> RawBits {
+        write_bits(|w| self.write(w))
+    }
+}
+
+/// Reads the given number of elements, whose fields have the given names in the syntax.
+fn read_channel_elements(
+    r: &mut BitReader<'_>,
+    count: u8,
+    (is_cpe, tag_select): (&'static str, &'static str),
+) -> Result<Vec<ChannelElement>, EndOfData> {
+    (0..count)
+        .map(|_| {
+            Ok(ChannelElement {
+                is_cpe: r.named(is_cpe).read_bool()?,
+                tag_select: r.named(tag_select).read_u8(4)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::Limits;
+
+    #[test]
+    fn five_one() {
+        // tag 0, LC, 48kHz, 2 front (SCE + CPE), 0 side, 1 back CPE, 1 LFE, 0 assoc, 0 cc,
+        // no mixdowns, 1 byte comment
+        let buf = [0x04, 0xc8, 0x05, 0x00, 0x01, 0x08, 0x80, 0x01, b'x'];
+        let pce = ProgramConfigElement::from_bytes(&buf).unwrap();
+        assert_eq!(
+            vec![
+                ChannelElement {
+                    is_cpe: false,
+                    tag_select: 0
+                },
+                ChannelElement {
+                    is_cpe: true,
+                    tag_select: 0
+                },
+            ],
+            pce.front_channel_elements
+        );
+        assert_eq!(
+            vec![ChannelElement {
+                is_cpe: true,
+                tag_select: 1
+            }],
+            pce.back_channel_elements
+        );
+        assert_eq!(vec![0], pce.lfe_channel_elements);
+        assert_eq!(b"x".to_vec(), pce.comment);
+        assert_eq!(6, pce.channel_count());
+        assert_eq!(
+            Err(PceError::NotEnoughData(TraceEntry {
+                bit_offset: 64,
+                bit_length: 8,
+                field: "comment_field_data",
+                value: None,
+            })),
+            ProgramConfigElement::from_bytes(&buf[..8])
+        );
+        assert_eq!(
+            Err(PceError::BadSamplingFrequencyIndex(TraceEntry {
+                bit_offset: 6,
+                bit_length: 4,
+                field: "sampling_frequency_index",
+                value: Some(15),
+            })),
+            ProgramConfigElement::from_bytes(&[0x07, 0xc0])
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let buf = [0x04, 0xc8, 0x05, 0x00, 0x01, 0x08, 0x80, 0x01, b'x'];
+        let mut r = BitReader::new(&buf);
+        r.set_limits(Limits {
+            max_pce_elements: 2,
+            ..Limits::DEFAULT
+        });
+        assert_eq!(
+            Err(PceError::TooManyElements(TraceEntry {
+                bit_offset: 18,
+                bit_length: 4,
+                field: "num_back_channel_elements",
+                value: Some(1),
+            })),
+            ProgramConfigElement::read(&mut r)
+        );
+    }
+}
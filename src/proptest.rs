@@ -0,0 +1,220 @@
+//! [proptest](https://docs.rs/proptest) strategies generating valid values, for property tests
+//! in crates building on this one.
+//!
+//! Configuration structures have their optional fields present or absent as the syntax
+//! requires, so generated values can be expected to survive serialization and parsing
+//! unchanged:
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::proptest::*;
+//! use ::proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn asc_roundtrip(asc in any_asc()) {
+//!         prop_assert_eq!(Ok(asc.clone()), AudioSpecificConfig::from_bytes(&asc.to_bytes()));
+//!     }
+//! }
+//! # asc_roundtrip();
+//! ```
+
+use crate::adts::{AdtsHeader, MpegVersion, ADTS_HEADER_LENGTH, ADTS_HEADER_WITH_CRC_LENGTH};
+use crate::asc::{
+    AudioExtension, AudioSpecificConfig, GaExtension, GaSpecificConfig, ResilienceFlags,
+    SamplingFrequency, SpecificConfig,
+};
+use crate::latm::StreamMuxConfig;
+use crate::{AudioObjectType, ChannelConfiguration, SamplingFrequencyIndex};
+use ::proptest::prelude::*;
+use ::proptest::sample::select;
+use core::convert::TryFrom;
+
+/// The object types generated by [`any_asc()`](fn.any_asc.html)
+const GA_OBJECT_TYPES: &[AudioObjectType] = &[
+    AudioObjectType::AAC_MAIN,
+    AudioObjectType::AAC_LC,
+    AudioObjectType::AAC_SSR,
+    AudioObjectType::AAC_LTP,
+    AudioObjectType::AAC_SCALABLE,
+    AudioObjectType::TWIN_VQ,
+    AudioObjectType::ER_AAC_LC,
+    AudioObjectType::ER_AAC_LTP,
+    AudioObjectType::ER_AAC_SCALABLE,
+    AudioObjectType::ER_TWIN_VQ,
+    AudioObjectType::ER_AAC_LD,
+];
+
+/// Generates the audio object types that the spec defines (i.e. those having a constant).
+pub fn any_defined_aot() -> impl Strategy<Value = AudioObjectType> {
+    select(AudioObjectType::ALL)
+}
+
+/// Generates any audio object type value, including reserved values (but never the escape
+/// value).
+pub fn any_aot() -> impl Strategy<Value = AudioObjectType> {
+    (0..=95u8)
+        .prop_filter("escape value", |v| *v != crate::AOT_ESCAPE_VALUE)
+        .prop_map(|v| AudioObjectType::try_from(v).unwrap())
+}
+
+/// Generates any sampling frequency index, including reserved values (but never the escape
+/// value).
+pub fn any_sampling_frequency_index() -> impl Strategy<Value = SamplingFrequencyIndex> {
+    (0..=14u8).prop_map(|v| SamplingFrequencyIndex::try_from(v).unwrap())
+}
+
+/// Generates any channel configuration, including `0` and reserved values.
+pub fn any_channel_configuration() -> impl Strategy<Value = ChannelConfiguration> {
+    (0..=15u8).prop_map(|v| ChannelConfiguration::try_from(v).unwrap())
+}
+
+/// Generates a sampling frequency given either as an index or as an explicit 24-bit value.
+pub fn any_sampling_frequency() -> impl Strategy<Value = SamplingFrequency> {
+    prop_oneof![
+        any_sampling_frequency_index().prop_map(SamplingFrequency::Index),
+        (0..=0xff_ffffu32).prop_map(SamplingFrequency::Explicit),
+    ]
+}
+
+/// Generates an `AudioSpecificConfig` for one of the `GASpecificConfig` object types
+/// (excluding `ER_BSAC`), with SBR or PS signalled hierarchically for some `AAC_LC`
+/// configurations, and with no program config element or error protection config.
+pub fn any_asc() -> impl Strategy<Value = AudioSpecificConfig> {
+    (
+        select(GA_OBJECT_TYPES),
+        any_sampling_frequency(),
+        1..=15u8,
+        ::proptest::option::of((any::<bool>(), any_sampling_frequency())),
+        any::<bool>(),
+        ::proptest::option::of(0..=0x3fffu16),
+        0..=7u8,
+        any::<[bool; 3]>(),
+        0..=1u8,
+    )
+        .prop_map(
+            |(aot, sampling_frequency, cc, ext, frame_length_flag, delay, layer_nr, res, ep)| {
+                let mut channel_configuration = ChannelConfiguration::try_from(cc).unwrap();
+                let extension = match ext {
+                    Some((ps_present, sampling_frequency)) if aot == AudioObjectType::AAC_LC => {
+                        if ps_present {
+                            // parametric stereo is carried with a mono core
+                            channel_configuration = ChannelConfiguration::MONO;
+                        }
+                        Some(AudioExtension::Hierarchical {
+                            ps_present,
+                            sampling_frequency,
+                            channel_configuration: None,
+                        })
+                    }
+                    _ => None,
+                };
+                let error_resilient = u8::from(aot) >= 17;
+                let scalable =
+                    aot == AudioObjectType::AAC_SCALABLE || aot == AudioObjectType::ER_AAC_SCALABLE;
+                let resilience = ResilienceFlags {
+                    aac_section_data_resilience: res[0],
+                    aac_scalefactor_data_resilience: res[1],
+                    aac_spectral_data_resilience: res[2],
+                };
+                let ga = GaSpecificConfig {
+                    frame_length_flag,
+                    core_coder_delay: delay,
+                    program_config_element: None,
+                    layer_nr: if scalable { Some(layer_nr) } else { None },
+                    // the extension flag is set for (and only for) the error resilient types
+                    extension: if error_resilient {
+                        Some(GaExtension {
+                            bsac: None,
+                            resilience: if aot == AudioObjectType::ER_TWIN_VQ {
+                                None
+                            } else {
+                                Some(resilience)
+                            },
+                            extension_flag3: false,
+                        })
+                    } else {
+                        None
+                    },
+                };
+                AudioSpecificConfig {
+                    audio_object_type: aot,
+                    sampling_frequency,
+                    channel_configuration,
+                    extension,
+                    specific_config: SpecificConfig::Ga(ga),
+                    ep_config: if error_resilient { Some(ep) } else { None },
+                    error_protection_specific_config: None,
+                }
+            },
+        )
+}
+
+/// Generates an ADTS header whose `frame_length` is at least the length of the header.
+pub fn any_adts_header() -> impl Strategy<Value = AdtsHeader> {
+    (
+        (any::<bool>(), any::<bool>(), 1..=4u8),
+        any_sampling_frequency_index(),
+        0..=7u8,
+        any::<[bool; 5]>(),
+        0..=0x1fffu16,
+        0..=0x7ffu16,
+        0..=3u8,
+        any::<u16>(),
+    )
+        .prop_map(
+            |((mpeg2, protected, aot), sfi, cc, flags, len, fullness, blocks, crc)| {
+                let header_length = if protected {
+                    ADTS_HEADER_WITH_CRC_LENGTH
+                } else {
+                    ADTS_HEADER_LENGTH
+                };
+                AdtsHeader {
+                    mpeg_version: if mpeg2 {
+                        MpegVersion::Mpeg2
+                    } else {
+                        MpegVersion::Mpeg4
+                    },
+                    layer: 0,
+                    protected,
+                    audio_object_type: AudioObjectType::try_from(aot).unwrap(),
+                    sampling_frequency_index: sfi,
+                    private_bit: flags[0],
+                    channel_configuration: ChannelConfiguration::try_from(cc).unwrap(),
+                    original_copy: flags[1],
+                    home: flags[2],
+                    copyright_identification_bit: flags[3],
+                    copyright_identification_start: flags[4],
+                    frame_length: len.max(header_length as u16),
+                    buffer_fullness: fullness,
+                    number_of_raw_data_blocks_in_frame: blocks,
+                    crc: if protected { Some(crc) } else { None },
+                }
+            },
+        )
+}
+
+/// Generates a `StreamMuxConfig` for a single layer having a configuration from
+/// [`any_asc()`](fn.any_asc.html), as given by
+/// [`StreamMuxConfig::new()`](../latm/struct.StreamMuxConfig.html#method.new).
+pub fn any_stream_mux_config() -> impl Strategy<Value = StreamMuxConfig> {
+    any_asc().prop_map(StreamMuxConfig::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn roundtrip(asc in any_asc(), smc in any_stream_mux_config()) {
+            prop_assert_eq!(Ok(asc.clone()), AudioSpecificConfig::from_bytes(&asc.to_bytes()));
+            prop_assert_eq!(Ok(smc.clone()), StreamMuxConfig::from_bytes(&smc.to_bytes()));
+        }
+
+        #[test]
+        fn values(aot in any_defined_aot(), header in any_adts_header()) {
+            prop_assert!(aot.is_defined());
+            prop_assert!(usize::from(header.frame_length) >= header.header_length());
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! [PyO3](https://pyo3.rs) bindings, exposing the value types, and `AudioSpecificConfig` and
+//! `AdtsHeader` parsing, as a Python module named `mpeg4_audio_const`.
+//!
+//! The module is initialised by [`mpeg4_audio_const()`](fn.mpeg4_audio_const.html), so a
+//! Python extension can be built from a `cdylib` crate re-exporting it (e.g. using
+//! [maturin](https://www.maturin.rs)),
+//!
+//! ```python
+//! from mpeg4_audio_const import AudioSpecificConfig
+//!
+//! asc = AudioSpecificConfig.from_bytes(bytes([0x12, 0x10]))
+//! assert asc.codec_string == "mp4a.40.2"
+//! assert int(asc.channel_configuration) == 2
+//! ```
+//!
+//! Parse errors are raised as `ValueError`.
+
+use crate::adts::{AdtsHeader, MpegVersion};
+use crate::asc::AudioSpecificConfig;
+use crate::{AudioObjectType, ChannelConfiguration, SamplingFrequencyIndex};
+use core::convert::TryFrom;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+macro_rules! implement_value_type {
+    ( $t:ident { $( $extra:tt )* } ) => {
+        #[pymethods]
+        impl $t {
+            #[new]
+            fn py_new(value: u8) -> PyResult<Self> {
+                $t::try_from(value).map_err(|e| PyValueError::new_err(format!("{:?}", e)))
+            }
+
+            fn __int__(&self) -> u8 {
+                u8::from(*self)
+            }
+
+            fn __repr__(&self) -> String {
+                format!("{:?}", self)
+            }
+
+            $( $extra )*
+        }
+    }
+}
+
+implement_value_type!(AudioObjectType {
+    /// The RFC 6381 codec string, e.g. `mp4a.40.2`
+    #[getter(codec_string)]
+    fn py_codec_string(&self) -> String {
+        self.codec_string()
+    }
+});
+
+implement_value_type!(SamplingFrequencyIndex {
+    /// The frequency in Hz, or `None` for reserved values
+    #[getter(frequency)]
+    fn py_frequency(&self) -> Option<u32> {
+        self.frequency()
+    }
+});
+
+implement_value_type!(ChannelConfiguration {
+    /// The number of channels, or `None` when not fixed by the configuration
+    #[getter(channel_count)]
+    fn py_channel_count(&self) -> Option<u32> {
+        self.channel_count()
+    }
+});
+
+/// The Python class for `AudioSpecificConfig`, which can't itself be a class since it is
+/// generic over the storage of its uninterpreted payloads
+#[pyclass(
+    name = "AudioSpecificConfig",
+    frozen,
+    eq,
+    from_py_object,
+    module = "mpeg4_audio_const"
+)]
+#[derive(PartialEq, Clone)]
+pub struct PyAudioSpecificConfig(pub AudioSpecificConfig);
+
+#[pymethods]
+impl PyAudioSpecificConfig {
+    #[staticmethod]
+    #[pyo3(name = "from_bytes")]
+    fn py_from_bytes(data: &[u8]) -> PyResult<Self> {
+        AudioSpecificConfig::from_bytes(data)
+            .map(PyAudioSpecificConfig)
+            .map_err(|e| PyValueError::new_err(format!("invalid AudioSpecificConfig: {:?}", e)))
+    }
+
+    #[pyo3(name = "to_bytes")]
+    fn py_to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.0.to_bytes())
+    }
+
+    #[getter(audio_object_type)]
+    fn py_audio_object_type(&self) -> AudioObjectType {
+        self.0.audio_object_type
+    }
+
+    /// The core sampling rate in Hz, or `None` when given by a reserved index
+    #[getter(sampling_frequency)]
+    fn py_sampling_frequency(&self) -> Option<u32> {
+        self.0.sample_rate()
+    }
+
+    #[getter(channel_configuration)]
+    fn py_channel_configuration(&self) -> ChannelConfiguration {
+        self.0.channel_configuration
+    }
+
+    #[getter(sbr_present)]
+    fn py_sbr_present(&self) -> bool {
+        self.0.extension.as_ref().is_some_and(|e| e.sbr_present())
+    }
+
+    #[getter(ps_present)]
+    fn py_ps_present(&self) -> bool {
+        self.0.extension.as_ref().is_some_and(|e| e.ps_present())
+    }
+
+    #[getter(codec_string)]
+    fn py_codec_string(&self) -> String {
+        self.0.codec_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+#[pymethods]
+impl AdtsHeader {
+    #[staticmethod]
+    #[pyo3(name = "from_bytes")]
+    fn py_from_bytes(data: &[u8]) -> PyResult<Self> {
+        AdtsHeader::from_bytes(data)
+            .map_err(|e| PyValueError::new_err(format!("invalid ADTS header: {:?}", e)))
+    }
+
+    /// `2` or `4`
+    #[getter(mpeg_version)]
+    fn py_mpeg_version(&self) -> u8 {
+        match self.mpeg_version {
+            MpegVersion::Mpeg2 => 2,
+            MpegVersion::Mpeg4 => 4,
+        }
+    }
+
+    #[getter(protected)]
+    fn py_protected(&self) -> bool {
+        self.protected
+    }
+
+    #[getter(audio_object_type)]
+    fn py_audio_object_type(&self) -> AudioObjectType {
+        self.audio_object_type
+    }
+
+    #[getter(sampling_frequency_index)]
+    fn py_sampling_frequency_index(&self) -> SamplingFrequencyIndex {
+        self.sampling_frequency_index
+    }
+
+    #[getter(channel_configuration)]
+    fn py_channel_configuration(&self) -> ChannelConfiguration {
+        self.channel_configuration
+    }
+
+    #[getter(frame_length)]
+    fn py_frame_length(&self) -> u16 {
+        self.frame_length
+    }
+
+    #[getter(header_length)]
+    fn py_header_length(&self) -> usize {
+        self.header_length()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// Initialises the `mpeg4_audio_const` Python module.
+#[pymodule]
+pub fn mpeg4_audio_const(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<AudioObjectType>()?;
+    m.add_class::<SamplingFrequencyIndex>()?;
+    m.add_class::<ChannelConfiguration>()?;
+    m.add_class::<PyAudioSpecificConfig>()?;
+    m.add_class::<AdtsHeader>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    #[test]
+    fn module() {
+        Python::initialize();
+        Python::attach(|py| {
+            let m = PyModule::new(py, "mpeg4_audio_const").unwrap();
+            mpeg4_audio_const(&m).unwrap();
+            let locals = PyDict::new(py);
+            locals.set_item("m", m).unwrap();
+            py.run(
+                pyo3::ffi::c_str!(
+                    "
+asc = m.AudioSpecificConfig.from_bytes(bytes([0xeb, 0x09, 0x88, 0x00]))
+assert asc.audio_object_type == m.AudioObjectType(2)
+assert asc.sampling_frequency == 24000
+assert asc.ps_present
+assert asc.codec_string == 'mp4a.40.29'
+assert asc.to_bytes() == bytes([0xeb, 0x09, 0x88, 0x00])
+assert int(m.ChannelConfiguration(6)) == 6
+assert repr(m.AudioObjectType(2)) == 'AAC_LC(2)'
+try:
+    m.AudioObjectType(31)
+    assert False
+except ValueError:
+    pass
+"
+                ),
+                None,
+                Some(&locals),
+            )
+            .unwrap();
+        });
+    }
+}
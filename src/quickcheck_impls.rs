@@ -0,0 +1,65 @@
+//! quickcheck `Arbitrary` implementations for the value types, generating (and shrinking
+//! towards) only values that the types accept, so never the escape values.
+
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use core::convert::TryFrom;
+use quickcheck::{Arbitrary, Gen};
+
+macro_rules! implement_quickcheck {
+    ( $( $t:ident $max:literal ),* $(,)? ) => {
+        $(
+            impl Arbitrary for $t {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    loop {
+                        if let Ok(v) = $t::try_from(u8::arbitrary(g) % ($max + 1)) {
+                            return v;
+                        }
+                    }
+                }
+
+                fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                    Box::new(u8::from(*self).shrink().filter_map(|v| $t::try_from(v).ok()))
+                }
+            }
+        )*
+    }
+}
+
+implement_quickcheck! {
+    AudioObjectType 95,
+    SamplingFrequencyIndex 14,
+    ChannelConfiguration 15,
+}
+
+impl Arbitrary for AudioProfileLevelIndication {
+    fn arbitrary(g: &mut Gen) -> Self {
+        AudioProfileLevelIndication::from(u8::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(
+            u8::from(*self)
+                .shrink()
+                .map(AudioProfileLevelIndication::from),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AOT_ESCAPE_VALUE;
+
+    #[test]
+    fn valid() {
+        fn prop(aot: AudioObjectType, sfi: SamplingFrequencyIndex) -> bool {
+            u8::from(aot) != AOT_ESCAPE_VALUE
+                && u8::from(aot) <= 95
+                && aot.shrink().all(|v| u8::from(v) < u8::from(aot))
+                && u8::from(sfi) < 15
+        }
+        quickcheck::quickcheck(prop as fn(AudioObjectType, SamplingFrequencyIndex) -> bool);
+    }
+}
@@ -0,0 +1,108 @@
+//! rand `Distribution` implementations, generating uniformly random values from among those
+//! that the spec defines (so never reserved or escape values), for load and chaos testing.
+//!
+//! Generated `AudioSpecificConfig`s are small, plain configurations: AAC Main, LC, SSR or LTP
+//! with one of the standard channel configurations, and for AAC LC possibly with SBR (and PS
+//! for mono) signalled hierarchically at double the core sampling rate.
+
+#[cfg(feature = "alloc")]
+use crate::asc::{
+    AudioExtension, AudioSpecificConfig, GaSpecificConfig, SamplingFrequency, SpecificConfig,
+};
+use crate::{AudioObjectType, ChannelConfiguration, SamplingFrequencyIndex};
+use core::convert::TryFrom;
+use rand::distr::{Distribution, StandardUniform};
+use rand::{Rng, RngExt};
+
+impl Distribution<AudioObjectType> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> AudioObjectType {
+        let defined = AudioObjectType::ALL;
+        defined[rng.random_range(0..defined.len())]
+    }
+}
+
+impl Distribution<SamplingFrequencyIndex> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SamplingFrequencyIndex {
+        // the values with a defined frequency
+        SamplingFrequencyIndex::try_from(rng.random_range(0..=12)).unwrap()
+    }
+}
+
+impl Distribution<ChannelConfiguration> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ChannelConfiguration {
+        loop {
+            let conf = ChannelConfiguration::try_from(rng.random_range(0..=15)).unwrap();
+            if conf == ChannelConfiguration::AOT_SPECIFIC_CONFIG || conf.channel_count().is_some() {
+                return conf;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<AudioSpecificConfig> for StandardUniform {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> AudioSpecificConfig {
+        let audio_object_type = AudioObjectType::try_from(rng.random_range(1..=4)).unwrap();
+        let mut channel_configuration =
+            ChannelConfiguration::try_from(rng.random_range(1..=7)).unwrap();
+        let sbr = audio_object_type == AudioObjectType::AAC_LC && rng.random::<bool>();
+        let (index, extension) = if sbr {
+            // a core rate of 24kHz or below, doubled by SBR
+            let index = rng.random_range(6..=11);
+            let ps_present = rng.random::<bool>();
+            if ps_present {
+                channel_configuration = ChannelConfiguration::MONO;
+            }
+            let extension = AudioExtension::Hierarchical {
+                ps_present,
+                sampling_frequency: SamplingFrequency::Index(
+                    SamplingFrequencyIndex::try_from(index - 3).unwrap(),
+                ),
+                channel_configuration: None,
+            };
+            (index, Some(extension))
+        } else {
+            (rng.random_range(0..=12), None)
+        };
+        AudioSpecificConfig {
+            audio_object_type,
+            sampling_frequency: SamplingFrequency::Index(
+                SamplingFrequencyIndex::try_from(index).unwrap(),
+            ),
+            channel_configuration,
+            extension,
+            specific_config: SpecificConfig::Ga(GaSpecificConfig {
+                frame_length_flag: false,
+                core_coder_delay: None,
+                program_config_element: None,
+                layer_nr: None,
+                extension: None,
+            }),
+            ep_config: None,
+            error_protection_specific_config: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn valid() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let aot: AudioObjectType = rng.random();
+            assert!(AudioObjectType::ALL.contains(&aot));
+            let sfi: SamplingFrequencyIndex = rng.random();
+            assert!(sfi.frequency().is_some());
+            let asc: AudioSpecificConfig = rng.random();
+            assert_eq!(
+                Ok(asc.clone()),
+                AudioSpecificConfig::from_bytes(&asc.to_bytes())
+            );
+        }
+    }
+}
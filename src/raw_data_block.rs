@@ -0,0 +1,477 @@
+//! Inspection of the syntactic elements within a `raw_data_block()`, the access unit payload
+//! carried (for the AAC family of object types) in ADTS frames and LATM `PayloadMux()`
+//! structures.
+
+use crate::bits::{BitReader, EndOfData};
+use crate::pce::{PceError, ProgramConfigElement};
+use crate::sbr::{SbrError, SbrHeader};
+use crate::trace::TraceEntry;
+use crate::{ChannelConfiguration, ElementCounts};
+use core::fmt;
+
+/// `extension_type` value for fill data with no further meaning
+pub const EXT_FILL: u8 = 0b0000;
+/// `extension_type` value for fill data consisting of `0b10100101` bytes
+pub const EXT_FILL_DATA: u8 = 0b0001;
+/// `extension_type` value for a data element (e.g. ancillary data)
+pub const EXT_DATA_ELEMENT: u8 = 0b0010;
+/// `extension_type` value for dynamic range control information
+pub const EXT_DYNAMIC_RANGE: u8 = 0b1011;
+/// `extension_type` value for MPEG Surround data
+pub const EXT_SAC_DATA: u8 = 0b1100;
+/// `extension_type` value for SBR data
+pub const EXT_SBR_DATA: u8 = 0b1101;
+/// `extension_type` value for SBR data with a CRC
+pub const EXT_SBR_DATA_CRC: u8 = 0b1110;
+
+/// The `data_element_version` value signalling ancillary data
+const ANC_DATA: u8 = 0b0000;
+
+/// Errors that may be produced while inspecting the elements of a `raw_data_block()`
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum RawDataBlockError {
+    /// The data ended before the end of the element being inspected, while reading the given
+    /// syntax element.
+    NotEnoughData(TraceEntry),
+    /// The element at the given position was not of the expected type; the `id_syn_ele` field
+    /// read is also given.
+    UnexpectedElement(ElementId, TraceEntry),
+    /// An extension payload claimed more bytes than remained in its fill element; the
+    /// `extension_type` field of the payload is given.
+    BadExtensionPayloadLength(TraceEntry),
+    /// A program config element within the block could not be parsed.
+    Pce(PceError),
+}
+
+impl fmt::Display for RawDataBlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RawDataBlockError::NotEnoughData(at) => {
+                write!(f, "not enough data, reading {}", at)
+            }
+            RawDataBlockError::UnexpectedElement(id, at) => {
+                write!(f, "unexpected {:?} element, {}", id, at)
+            }
+            RawDataBlockError::BadExtensionPayloadLength(at) => {
+                write!(f, "extension payload longer than its fill element, {}", at)
+            }
+            RawDataBlockError::Pce(_) => f.write_str("invalid program config element"),
+        }
+    }
+}
+
+impl core::error::Error for RawDataBlockError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            RawDataBlockError::Pce(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EndOfData> for RawDataBlockError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        RawDataBlockError::NotEnoughData(at)
+    }
+}
+impl From<PceError> for RawDataBlockError {
+    fn from(e: PceError) -> Self {
+        RawDataBlockError::Pce(e)
+    }
+}
+impl From<SbrError> for RawDataBlockError {
+    fn from(e: SbrError) -> Self {
+        match e {
+            SbrError::NotEnoughData(at) => RawDataBlockError::NotEnoughData(at),
+        }
+    }
+}
+
+/// The 3-bit `id_syn_ele` value identifying the type of each syntactic element in a
+/// `raw_data_block()`
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum ElementId {
+    /// Single channel element
+    Sce = 0,
+    /// Channel pair element
+    Cpe = 1,
+    /// Coupling channel element
+    Cce = 2,
+    /// LFE channel element
+    Lfe = 3,
+    /// Data stream element
+    Dse = 4,
+    /// Program config element
+    Pce = 5,
+    /// Fill element
+    Fil = 6,
+    /// Terminates the `raw_data_block()`
+    End = 7,
+}
+
+impl ElementId {
+    /// Maps the low 3 bits of the given value to an element id.
+    pub fn from_bits(v: u8) -> ElementId {
+        match v & 0b111 {
+            0 => ElementId::Sce,
+            1 => ElementId::Cpe,
+            2 => ElementId::Cce,
+            3 => ElementId::Lfe,
+            4 => ElementId::Dse,
+            5 => ElementId::Pce,
+            6 => ElementId::Fil,
+            _ => ElementId::End,
+        }
+    }
+}
+
+impl From<ElementId> for u8 {
+    fn from(v: ElementId) -> Self {
+        v as u8
+    }
+}
+
+/// Parses the program config element at the very start of the given `raw_data_block()`, if
+/// the first syntactic element is a PCE.
+///
+/// Streams whose `channelConfiguration` is `0` must describe their channel layout with a PCE,
+/// and when carried in ADTS or LATM that PCE is sent in-band as the first element of the
+/// access unit.  For ADTS, the frame payload starts with the first `raw_data_block()` as long
+/// as the frame contains only one block, or the header is not `protected`.
+///
+/// ```rust
+/// # use mpeg4_audio_const::raw_data_block::*;
+/// let pce = leading_pce(&[0xa0, 0x98, 0x80, 0x00, 0x04, 0x00, 0x00, 0xe0]).unwrap();
+/// assert_eq!(2, pce.unwrap().channel_count());
+/// // starts with a single channel element, not a PCE
+/// assert_eq!(None, leading_pce(&[0x00, 0x00]).unwrap());
+/// ```
+pub fn leading_pce(raw_data_block: &[u8]) -> Result<Option<ProgramConfigElement>, PceError> {
+    let mut r = BitReader::new(raw_data_block);
+    if ElementId::from_bits(r.named("id_syn_ele").read_u8(3)?) != ElementId::Pce {
+        return Ok(None);
+    }
+    ProgramConfigElement::read(&mut r).map(Some)
+}
+
+impl ElementCounts {
+    /// Counts the front, side and back channel elements, and the LFE elements, referenced by
+    /// the given program config element.
+    pub fn from_pce(pce: &ProgramConfigElement) -> ElementCounts {
+        let elements = || {
+            pce.front_channel_elements
+                .iter()
+                .chain(pce.side_channel_elements.iter())
+                .chain(pce.back_channel_elements.iter())
+        };
+        ElementCounts {
+            sce: elements().filter(|e| !e.is_cpe).count(),
+            cpe: elements().filter(|e| e.is_cpe).count(),
+            lfe: pce.lfe_channel_elements.len(),
+        }
+    }
+}
+
+/// The result of a best-effort scan of the elements in a `raw_data_block()`, produced by
+/// [`scan_elements()`](fn.scan_elements.html)
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ElementScan {
+    /// The channel elements seen before the scan ended
+    pub counts: ElementCounts,
+    /// The program config element, if one was found
+    pub pce: Option<ProgramConfigElement>,
+    /// `true` if the scan reached the `END` element, so that `counts` covers the whole block
+    pub complete: bool,
+}
+
+impl ElementScan {
+    /// Infers the channel configuration of the access unit: from the in-band PCE if one was
+    /// found (giving `AOT_SPECIFIC_CONFIG` if the PCE layout doesn't match any standard
+    /// configuration), or otherwise from the element counts if the scan was complete.
+    pub fn channel_configuration(&self) -> Option<ChannelConfiguration> {
+        match self.pce {
+            Some(ref pce) => Some(
+                ElementCounts::from_pce(pce)
+                    .channel_configuration()
+                    .unwrap_or(ChannelConfiguration::AOT_SPECIFIC_CONFIG),
+            ),
+            None if self.complete => self.counts.channel_configuration(),
+            None => None,
+        }
+    }
+
+    /// Infers the number of output channels of the access unit, from the in-band PCE if one
+    /// was found, or otherwise from the element counts if the scan was complete.
+    pub fn channel_count(&self) -> Option<u32> {
+        match self.pce {
+            Some(ref pce) => Some(pce.channel_count()),
+            None if self.complete => Some(self.counts.channel_count()),
+            None => None,
+        }
+    }
+}
+
+/// Walks the syntactic elements of a `raw_data_block()`, counting channel elements, in order
+/// to infer the real channel layout of streams whose signalled channel configuration is `0` or
+/// wrong.
+///
+/// This is a best-effort scan: program config, data stream and fill elements are
+/// self-delimiting and can be stepped over, but the length of single channel, channel pair,
+/// coupling and LFE elements can only be found by fully decoding their spectral data, which
+/// this crate does not do.  The scan therefore ends at the first such element (having counted
+/// it), and the result will only be `complete` for blocks that contain no audio elements.  In
+/// practice, the useful result is from streams that carry their layout in an in-band PCE,
+/// which encoders place before the channel elements.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::raw_data_block::*;
+/// let scan = scan_elements(&[0xa0, 0x98, 0x80, 0x00, 0x04, 0x00, 0x00, 0x20]).unwrap();
+/// assert_eq!(Some(ChannelConfiguration::STEREO), scan.channel_configuration());
+/// assert_eq!(1, scan.counts.cpe);
+/// assert!(!scan.complete);
+/// ```
+pub fn scan_elements(raw_data_block: &[u8]) -> Result<ElementScan, RawDataBlockError> {
+    let mut r = BitReader::new(raw_data_block);
+    let mut scan = ElementScan {
+        counts: ElementCounts::default(),
+        pce: None,
+        complete: false,
+    };
+    loop {
+        match ElementId::from_bits(r.named("id_syn_ele").read_u8(3)?) {
+            ElementId::Sce => scan.counts.sce += 1,
+            ElementId::Cpe => scan.counts.cpe += 1,
+            ElementId::Lfe => scan.counts.lfe += 1,
+            ElementId::Cce => (),
+            ElementId::Dse => {
+                r.named("element_instance_tag").skip(4)?;
+                let data_byte_align_flag = r.named("data_byte_align_flag").read_bool()?;
+                let mut cnt = usize::from(r.named("count").read_u8(8)?);
+                if cnt == 255 {
+                    cnt += usize::from(r.named("esc_count").read_u8(8)?);
+                }
+                if data_byte_align_flag {
+                    r.byte_align();
+                }
+                r.named("data_stream_byte").skip(cnt * 8)?;
+                continue;
+            }
+            ElementId::Pce => {
+                scan.pce = Some(ProgramConfigElement::read(&mut r)?);
+                continue;
+            }
+            ElementId::Fil => {
+                let mut cnt = usize::from(r.named("count").read_u8(4)?);
+                if cnt == 15 {
+                    cnt = cnt + usize::from(r.named("esc_count").read_u8(8)?) - 1;
+                }
+                r.named("extension_payload").skip(cnt * 8)?;
+                continue;
+            }
+            ElementId::End => scan.complete = true,
+        }
+        return Ok(scan);
+    }
+}
+
+/// The initial fields of an `sbr_extension_data()` payload found in a fill element
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SbrExtension {
+    /// The 10-bit `bs_sbr_crc_bits` value, present for `EXT_SBR_DATA_CRC` payloads
+    pub crc: Option<u16>,
+    /// The SBR header, if this access unit carries one (encoders typically repeat the header
+    /// only periodically)
+    pub header: Option<SbrHeader>,
+}
+
+/// Locates an `EXT_SBR_DATA` (or `EXT_SBR_DATA_CRC`) extension payload within the fill element
+/// starting at the given bit offset of a `raw_data_block()`, and parses the start of the SBR
+/// data, including the `sbr_header()` if present.
+///
+/// The presence of SBR data is how _implicit_ signalling of HE-AAC is detected, where the
+/// `AudioSpecificConfig` only signals the AAC core.  The fill element carrying SBR data
+/// follows the channel element it relates to, whose length can only be found by decoding it,
+/// so the caller must supply the offset of the fill element (i.e. of its `id_syn_ele` field).
+///
+/// Returns `None` if the fill element contains no SBR data.
+///
+/// ```rust
+/// # use mpeg4_audio_const::raw_data_block::*;
+/// // a FIL element at offset 0, with a 3-byte SBR payload including a header
+/// let ext = fill_element_sbr(&[0xc7, 0xbd, 0x50, 0x00], 0).unwrap().unwrap();
+/// assert_eq!(10, ext.header.unwrap().start_freq);
+/// ```
+pub fn fill_element_sbr(
+    raw_data_block: &[u8],
+    bit_offset: usize,
+) -> Result<Option<SbrExtension>, RawDataBlockError> {
+    let mut r = BitReader::new(raw_data_block);
+    r.named("raw_data_block").skip(bit_offset)?;
+    let id = ElementId::from_bits(r.named("id_syn_ele").read_u8(3)?);
+    if id != ElementId::Fil {
+        return Err(RawDataBlockError::UnexpectedElement(id, r.last_read()));
+    }
+    let mut cnt = usize::from(r.named("count").read_u8(4)?);
+    if cnt == 15 {
+        cnt = cnt + usize::from(r.named("esc_count").read_u8(8)?) - 1;
+    }
+    while cnt > 0 {
+        let extension_type = r.named("extension_type").read_u8(4)?;
+        let extension_type_read = r.last_read();
+        let n = match extension_type {
+            EXT_SBR_DATA | EXT_SBR_DATA_CRC => {
+                let crc = if extension_type == EXT_SBR_DATA_CRC {
+                    Some(r.named("bs_sbr_crc_bits").read_u16(10)?)
+                } else {
+                    None
+                };
+                let header = if r.named("bs_header_flag").read_bool()? {
+                    Some(SbrHeader::read(&mut r)?)
+                } else {
+                    None
+                };
+                return Ok(Some(SbrExtension { crc, header }));
+            }
+            EXT_DYNAMIC_RANGE => skip_dynamic_range_info(&mut r)?,
+            EXT_DATA_ELEMENT => {
+                if r.named("data_element_version").read_u8(4)? == ANC_DATA {
+                    let mut loop_counter = 0;
+                    let mut data_element_length = 0;
+                    loop {
+                        let part = r.named("dataElementLengthPart").read_u8(8)?;
+                        data_element_length += usize::from(part);
+                        loop_counter += 1;
+                        if part != 255 {
+                            break;
+                        }
+                    }
+                    r.named("data_element_byte").skip(data_element_length * 8)?;
+                    data_element_length + loop_counter + 1
+                } else {
+                    r.named("other_bits").skip((cnt - 1) * 8)?;
+                    cnt
+                }
+            }
+            _ => {
+                r.named("extension_payload").skip(cnt * 8 - 4)?;
+                cnt
+            }
+        };
+        cnt = cnt
+            .checked_sub(n)
+            .ok_or(RawDataBlockError::BadExtensionPayloadLength(
+                extension_type_read,
+            ))?;
+    }
+    Ok(None)
+}
+
+/// Skips the remainder of a `dynamic_range_info()` following the `extension_type`, returning
+/// its total length in bytes.
+fn skip_dynamic_range_info(r: &mut BitReader<'_>) -> Result<usize, EndOfData> {
+    let mut n = 1;
+    let mut drc_num_bands = 1;
+    if r.named("pce_tag_present").read_bool()? {
+        // pce_instance_tag, drc_tag_reserved_bits
+        r.named("pce_instance_tag").skip(8)?;
+        n += 1;
+    }
+    if r.named("excluded_chns_present").read_bool()? {
+        // excluded_channels(): 7 exclude_mask bits and an additional_excluded_chns flag per byte
+        loop {
+            r.named("exclude_mask").skip(7)?;
+            n += 1;
+            if !r.named("additional_excluded_chns").read_bool()? {
+                break;
+            }
+        }
+    }
+    if r.named("drc_bands_present").read_bool()? {
+        let drc_band_incr = r.named("drc_band_incr").read_u8(4)?;
+        r.named("drc_interpolation_scheme").skip(4)?;
+        n += 1;
+        drc_num_bands += usize::from(drc_band_incr);
+        r.named("drc_band_top").skip(drc_num_bands * 8)?;
+        n += drc_num_bands;
+    }
+    if r.named("prog_ref_level_present").read_bool()? {
+        // prog_ref_level, prog_ref_level_reserved_bits
+        r.named("prog_ref_level").skip(8)?;
+        n += 1;
+    }
+    // dyn_rng_sgn, dyn_rng_ctl
+    r.named("dyn_rng_ctl").skip(drc_num_bands * 8)?;
+    n += drc_num_bands;
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_id_roundtrip() {
+        for v in 0..8 {
+            assert_eq!(v, u8::from(ElementId::from_bits(v)));
+        }
+    }
+
+    #[test]
+    fn truncated_pce() {
+        assert!(matches!(
+            leading_pce(&[0xa0, 0x98]),
+            Err(PceError::NotEnoughData(_))
+        ));
+        assert!(matches!(
+            leading_pce(&[]),
+            Err(PceError::NotEnoughData(TraceEntry {
+                field: "id_syn_ele",
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn sbr_after_other_payloads() {
+        // 5 bits of preceding data, then FIL with count 8: a 2-byte dynamic range payload, a
+        // 3-byte ancillary data element, and a 3-byte EXT_SBR_DATA_CRC payload with no header
+        let buf = [0x06, 0x8b, 0x00, 0x02, 0x00, 0x14, 0x2e, 0x00, 0x40, 0x00];
+        assert_eq!(
+            Ok(Some(SbrExtension {
+                crc: Some(1),
+                header: None
+            })),
+            fill_element_sbr(&buf, 5)
+        );
+        assert_eq!(
+            Err(RawDataBlockError::UnexpectedElement(
+                ElementId::Sce,
+                TraceEntry {
+                    bit_offset: 0,
+                    bit_length: 3,
+                    field: "id_syn_ele",
+                    value: Some(0),
+                }
+            )),
+            fill_element_sbr(&buf, 0)
+        );
+    }
+
+    #[test]
+    fn no_sbr() {
+        // FIL with count 1 and an EXT_FILL payload
+        assert_eq!(Ok(None), fill_element_sbr(&[0xc2, 0x00], 0));
+    }
+
+    #[test]
+    fn scan_skips_data_and_fill() {
+        // DSE with 1 byte of aligned data, FIL with 1 byte, END
+        let scan = scan_elements(&[0x81, 0x01, 0xaa, 0xc2, 0x01, 0xc0]).unwrap();
+        assert!(scan.complete);
+        assert_eq!(ElementCounts::default(), scan.counts);
+        assert_eq!(Some(0), scan.channel_count());
+        assert_eq!(None, scan.channel_configuration());
+    }
+}
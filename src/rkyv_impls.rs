@@ -0,0 +1,107 @@
+//! rkyv implementations for the value types.  Each is its own archived form (being a single
+//! byte), with validation of archived data rejecting the same values as the type's
+//! `TryFrom<u8>` implementation (or `From<u8>`, which accepts every value).  The parsed structures derive their implementations.
+
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use core::convert::TryFrom;
+use core::fmt;
+use rkyv::bytecheck::CheckBytes;
+use rkyv::rancor::{Fallible, Source};
+use rkyv::traits::NoUndef;
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+/// Reported when archived data holds a value that the type does not accept
+#[derive(Debug)]
+struct InvalidValue {
+    type_name: &'static str,
+    value: u8,
+}
+
+impl fmt::Display for InvalidValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {} value {}", self.type_name, self.value)
+    }
+}
+
+impl core::error::Error for InvalidValue {}
+
+macro_rules! implement_rkyv {
+    ( $( $t:ident ),* $(,)? ) => {
+        $(
+            // SAFETY: the type is a `#[repr(transparent)]` wrapper of a `u8`, so has the same
+            // representation on every target
+            unsafe impl Portable for $t {}
+            // SAFETY: as above, with no padding
+            unsafe impl NoUndef for $t {}
+
+            impl Archive for $t {
+                type Archived = $t;
+                type Resolver = ();
+
+                fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+                    out.write(*self);
+                }
+            }
+
+            impl<S: Fallible + ?Sized> Serialize<S> for $t {
+                fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                    Ok(())
+                }
+            }
+
+            impl<D: Fallible + ?Sized> Deserialize<$t, D> for $t {
+                fn deserialize(&self, _: &mut D) -> Result<$t, D::Error> {
+                    Ok(*self)
+                }
+            }
+
+            // SAFETY: the byte is only accepted if `TryFrom<u8>` accepts it
+            unsafe impl<C: Fallible + ?Sized> CheckBytes<C> for $t
+            where
+                C::Error: Source,
+            {
+                unsafe fn check_bytes(value: *const Self, _: &mut C) -> Result<(), C::Error> {
+                    let value = *value.cast::<u8>();
+                    match $t::try_from(value) {
+                        Ok(_) => Ok(()),
+                        Err(_) => Err(Source::new(InvalidValue {
+                            type_name: stringify!($t),
+                            value,
+                        })),
+                    }
+                }
+            }
+        )*
+    }
+}
+
+implement_rkyv! {
+    AudioObjectType,
+    SamplingFrequencyIndex,
+    ChannelConfiguration,
+    AudioProfileLevelIndication,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asc::AudioSpecificConfig;
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn roundtrip() {
+        let asc =
+            AudioSpecificConfig::from_bytes(&[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80]).unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&asc).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<AudioSpecificConfig>, Error>(&bytes).unwrap();
+        assert_eq!(AudioObjectType::AAC_LC, archived.audio_object_type);
+        assert_eq!(
+            asc,
+            rkyv::deserialize::<AudioSpecificConfig, Error>(archived).unwrap()
+        );
+        let bytes = rkyv::to_bytes::<Error>(&31u8).unwrap();
+        assert!(rkyv::from_bytes::<AudioObjectType, Error>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,296 @@
+//! De-packetization support for RFC 3640 `mpeg4-generic` RTP payloads.
+//!
+//! Each payload starts with an _AU-header-section_, giving the size and index of each access
+//! unit (i.e. raw AAC frame, or fragment of one) carried in the payload.  The layout of the
+//! AU headers is negotiated in the SDP `fmtp` attribute (see
+//! [`sdp::Mpeg4GenericFmtp`](../sdp/struct.Mpeg4GenericFmtp.html)).
+//!
+//! Only the `AU-size`, `AU-Index` and `AU-Index-delta` fields are supported, as used by the
+//! `AAC-hbr` and `AAC-lbr` modes; the optional CTS, DTS, RAP and stream-state fields (and the
+//! auxiliary section) are not.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::rtp::*;
+//! let lengths = AuHeaderLengths::AAC_HBR;
+//! // two AUs, of 3 and 2 bytes
+//! let payload = [0x00, 0x20, 0x00, 0x18, 0x00, 0x10, 1, 2, 3, 4, 5];
+//! let (section, data) = AuHeaderSection::parse(&payload, &lengths).unwrap();
+//! assert_eq!(vec![&[1, 2, 3][..], &[4, 5][..]], section.access_units(data));
+//! assert_eq!(Ok(payload[..6].to_vec()), section.to_bytes(&lengths));
+//! ```
+
+use crate::bits::{BitReader, BitWriter, EndOfData};
+use crate::trace::TraceEntry;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Errors that may be produced while parsing an AU-header-section
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum AuHeaderError {
+    /// The payload ended before the end of the AU-header-section, while reading the given
+    /// field.
+    NotEnoughData(TraceEntry),
+    /// The `AU-headers-length` value was not consistent with a whole number of AU headers; the
+    /// field read is given.
+    BadHeadersLength(TraceEntry),
+    /// An AU header field length greater than 32 bits was given (e.g. by the `sizeLength`
+    /// format parameter).
+    FieldTooLong(u8),
+}
+
+impl fmt::Display for AuHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuHeaderError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            AuHeaderError::BadHeadersLength(at) => {
+                write!(f, "not a whole number of AU headers, {}", at)
+            }
+            AuHeaderError::FieldTooLong(len) => {
+                write!(f, "AU header field length {} is greater than 32 bits", len)
+            }
+        }
+    }
+}
+
+impl core::error::Error for AuHeaderError {}
+
+impl From<EndOfData> for AuHeaderError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        AuHeaderError::NotEnoughData(at)
+    }
+}
+
+/// The bit lengths of the fields of each AU header, as given by the `sizeLength`,
+/// `indexLength` and `indexDeltaLength` format parameters
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct AuHeaderLengths {
+    pub size_length: u8,
+    pub index_length: u8,
+    pub index_delta_length: u8,
+}
+
+impl AuHeaderLengths {
+    /// The lengths required by the `AAC-hbr` mode
+    pub const AAC_HBR: AuHeaderLengths = AuHeaderLengths {
+        size_length: 13,
+        index_length: 3,
+        index_delta_length: 3,
+    };
+    /// The lengths required by the `AAC-lbr` mode
+    pub const AAC_LBR: AuHeaderLengths = AuHeaderLengths {
+        size_length: 6,
+        index_length: 2,
+        index_delta_length: 2,
+    };
+
+    /// The greatest length, in bits, supported for any of the fields
+    pub const MAX_FIELD_LENGTH: u8 = 32;
+
+    /// `true` if AU headers are present at all (when all lengths are zero, the payload has no
+    /// AU-header-section).
+    pub fn is_present(&self) -> bool {
+        self.size_length > 0 || self.index_length > 0 || self.index_delta_length > 0
+    }
+
+    /// Checks that no field is longer than the `u32` values of an `AuHeader` can hold.
+    fn check(&self) -> Result<(), AuHeaderError> {
+        let lengths = [self.size_length, self.index_length, self.index_delta_length];
+        match lengths.iter().find(|&&len| len > Self::MAX_FIELD_LENGTH) {
+            Some(&len) => Err(AuHeaderError::FieldTooLong(len)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A single AU header
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct AuHeader {
+    /// The `AU-size`, in bytes: for a fragment, the size of the whole access unit
+    pub size: u32,
+    /// The `AU-Index` of the first header in the section, or the `AU-Index-delta` of
+    /// subsequent headers
+    pub index: u32,
+}
+
+/// A parsed AU-header-section
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct AuHeaderSection {
+    pub headers: Vec<AuHeader>,
+}
+
+impl AuHeaderSection {
+    /// Parses the AU-header-section at the start of the given RTP payload, returning it along
+    /// with the remainder of the payload (the access units, or a fragment of one).
+    pub fn parse<'a>(
+        payload: &'a [u8],
+        lengths: &AuHeaderLengths,
+    ) -> Result<(AuHeaderSection, &'a [u8]), AuHeaderError> {
+        lengths.check()?;
+        if !lengths.is_present() {
+            return Ok((AuHeaderSection::default(), payload));
+        }
+        let mut r = BitReader::new(payload);
+        let headers_length = r.named("AU-headers-length").read_u16(16)?;
+        let total = usize::from(headers_length);
+        let first_length = usize::from(lengths.size_length) + usize::from(lengths.index_length);
+        let delta_length =
+            usize::from(lengths.size_length) + usize::from(lengths.index_delta_length);
+        let at = r.last_read();
+        let bad = || AuHeaderError::BadHeadersLength(at);
+        let rest = total.checked_sub(first_length).ok_or_else(bad)?;
+        let count = match rest.checked_div(delta_length) {
+            Some(n) if n * delta_length == rest => 1 + n,
+            None if rest == 0 => 1,
+            _ => return Err(bad()),
+        };
+        let mut headers = Vec::with_capacity(count);
+        for i in 0..count {
+            let index_length = if i == 0 {
+                lengths.index_length
+            } else {
+                lengths.index_delta_length
+            };
+            headers.push(AuHeader {
+                size: r.named("AU-size").read(u32::from(lengths.size_length))?,
+                index: r.named("AU-Index").read(u32::from(index_length))?,
+            });
+        }
+        let data_offset = (16 + total).div_ceil(8);
+        Ok((AuHeaderSection { headers }, &payload[data_offset..]))
+    }
+
+    /// Serializes the AU-header-section, including the leading `AU-headers-length` and any
+    /// padding to a byte boundary.  Field values too large for the given lengths are
+    /// truncated.
+    pub fn to_bytes(&self, lengths: &AuHeaderLengths) -> Result<Vec<u8>, AuHeaderError> {
+        lengths.check()?;
+        if !lengths.is_present() {
+            return Ok(vec![]);
+        }
+        let mut w = BitWriter::new();
+        w.write(16, 0);
+        for (i, header) in self.headers.iter().enumerate() {
+            let index_length = if i == 0 {
+                lengths.index_length
+            } else {
+                lengths.index_delta_length
+            };
+            w.write(u32::from(lengths.size_length), header.size);
+            w.write(u32::from(index_length), header.index);
+        }
+        let headers_length = (w.bit_len() - 16) as u16;
+        let mut data = w.into_bytes();
+        data[..2].copy_from_slice(&headers_length.to_be_bytes());
+        Ok(data)
+    }
+
+    /// The `AU-Index` of each access unit, derived by accumulating the `AU-Index-delta`
+    /// values, allowing interleaved access units to be put back into decoding order.
+    pub fn indices(&self) -> Vec<u32> {
+        let mut index = 0u32;
+        self.headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                index = if i == 0 {
+                    header.index
+                } else {
+                    index.wrapping_add(header.index).wrapping_add(1)
+                };
+                index
+            })
+            .collect()
+    }
+
+    /// Splits the data following the AU-header-section into access units using the `AU-size`
+    /// of each header.
+    ///
+    /// If the data ends early, the final slice is shorter than the size given in its header;
+    /// this is expected where a single access unit has been fragmented over several packets,
+    /// in which case the fragments must be reassembled until `size` bytes are available.
+    pub fn access_units<'a>(&self, data: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut rest = data;
+        let mut units = Vec::with_capacity(self.headers.len());
+        for header in &self.headers {
+            if rest.is_empty() {
+                break;
+            }
+            let len = (header.size as usize).min(rest.len());
+            units.push(&rest[..len]);
+            rest = &rest[len..];
+        }
+        units
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interleaved_lbr() {
+        // AUs with indexes 2, 4 (delta 1) and 6 (delta 1), each of 1 byte
+        let section = AuHeaderSection {
+            headers: vec![
+                AuHeader { size: 1, index: 2 },
+                AuHeader { size: 1, index: 1 },
+                AuHeader { size: 1, index: 1 },
+            ],
+        };
+        let mut payload = section.to_bytes(&AuHeaderLengths::AAC_LBR).unwrap();
+        assert_eq!(vec![0x00, 0x18, 0x06, 0x05, 0x05], payload);
+        payload.extend_from_slice(&[7, 8, 9]);
+        let (parsed, data) = AuHeaderSection::parse(&payload, &AuHeaderLengths::AAC_LBR).unwrap();
+        assert_eq!(section, parsed);
+        assert_eq!(vec![2, 4, 6], parsed.indices());
+        assert_eq!(3, parsed.access_units(data).len());
+    }
+
+    #[test]
+    fn fragment_and_errors() {
+        // one AU of 1000 bytes, of which only 2 bytes are present in this packet
+        let payload = [0x00, 0x10, 0x1f, 0x40, 1, 2];
+        let (section, data) = AuHeaderSection::parse(&payload, &AuHeaderLengths::AAC_HBR).unwrap();
+        assert_eq!(1000, section.headers[0].size);
+        assert_eq!(vec![&[1, 2][..]], section.access_units(data));
+        assert_eq!(
+            Err(AuHeaderError::BadHeadersLength(TraceEntry {
+                bit_offset: 0,
+                bit_length: 16,
+                field: "AU-headers-length",
+                value: Some(17),
+            })),
+            AuHeaderSection::parse(&[0x00, 0x11, 0, 0, 0], &AuHeaderLengths::AAC_HBR)
+        );
+        assert_eq!(
+            Err(AuHeaderError::NotEnoughData(TraceEntry {
+                bit_offset: 32,
+                bit_length: 13,
+                field: "AU-size",
+                value: None,
+            })),
+            AuHeaderSection::parse(&[0x00, 0x20, 0, 0], &AuHeaderLengths::AAC_HBR)
+        );
+    }
+
+    #[test]
+    fn field_too_long() {
+        let lengths = AuHeaderLengths {
+            size_length: 40,
+            ..AuHeaderLengths::AAC_HBR
+        };
+        assert_eq!(
+            Err(AuHeaderError::FieldTooLong(40)),
+            AuHeaderSection::parse(&[0x00, 0x2b, 0, 0, 0, 0, 0, 0], &lengths)
+        );
+        let section = AuHeaderSection {
+            headers: vec![AuHeader { size: 1, index: 0 }],
+        };
+        assert_eq!(
+            Err(AuHeaderError::FieldTooLong(40)),
+            section.to_bytes(&lengths)
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Spectral Band Replication header syntax.
+//!
+//! SBR data is carried either in `EXT_SBR_DATA` extension payloads within the fill elements of
+//! a `raw_data_block()` (see
+//! [`raw_data_block::fill_element_sbr()`](../raw_data_block/fn.fill_element_sbr.html)), or,
+//! for low-delay configurations, in the `ELDSpecificConfig`.
+
+#[cfg(feature = "alloc")]
+use crate::asc::RawBits;
+#[cfg(feature = "alloc")]
+use crate::bits::BitWriter;
+use crate::bits::{BitReader, EndOfData};
+#[cfg(feature = "alloc")]
+use crate::bitstream::{read_counted, write_bits, FromBitstream, ToBitstream};
+use crate::trace::TraceEntry;
+use core::fmt;
+
+/// Errors that may be produced while parsing an SBR header
+#[derive(PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum SbrError {
+    /// The data ended before the end of the header, while reading the given element.
+    NotEnoughData(TraceEntry),
+}
+
+impl fmt::Display for SbrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SbrError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+        }
+    }
+}
+
+impl core::error::Error for SbrError {}
+
+impl From<EndOfData> for SbrError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        SbrError::NotEnoughData(at)
+    }
+}
+
+/// Optional SBR header fields present when `bs_header_extra_1` is set
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SbrHeaderExtra1 {
+    pub freq_scale: u8,
+    pub alter_scale: bool,
+    pub noise_bands: u8,
+}
+
+/// Optional SBR header fields present when `bs_header_extra_2` is set
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SbrHeaderExtra2 {
+    pub limiter_bands: u8,
+    pub limiter_gains: u8,
+    pub interpol_freq: bool,
+    pub smoothing_mode: bool,
+}
+
+/// A parsed `sbr_header()`, with field names omitting the `bs_` prefix used in the spec.
+///
+/// When the optional `extra_1` / `extra_2` fields are absent, the decoder uses default values
+/// for them, as given in ISO/IEC 14496-3.
+///
+/// ```rust
+/// # use mpeg4_audio_const::sbr::*;
+/// let header = SbrHeader::from_bytes(&[0xd5, 0x00]).unwrap();
+/// assert!(header.amp_res);
+/// assert_eq!(10, header.start_freq);
+/// assert_eq!(10, header.stop_freq);
+/// assert_eq!(None, header.extra_1);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SbrHeader {
+    pub amp_res: bool,
+    pub start_freq: u8,
+    pub stop_freq: u8,
+    pub xover_band: u8,
+    /// The 2-bit `bs_reserved` field
+    pub reserved: u8,
+    pub extra_1: Option<SbrHeaderExtra1>,
+    pub extra_2: Option<SbrHeaderExtra2>,
+}
+
+impl SbrHeader {
+    /// Parses an SBR header from the start of the given buffer.
+    pub fn from_bytes(buf: &[u8]) -> Result<SbrHeader, SbrError> {
+        Self::read(&mut BitReader::new(buf))
+    }
+
+    pub(crate) fn read(r: &mut BitReader<'_>) -> Result<SbrHeader, SbrError> {
+        let amp_res = r.named("bs_amp_res").read_bool()?;
+        let start_freq = r.named("bs_start_freq").read_u8(4)?;
+        let stop_freq = r.named("bs_stop_freq").read_u8(4)?;
+        let xover_band = r.named("bs_xover_band").read_u8(3)?;
+        let reserved = r.named("bs_reserved").read_u8(2)?;
+        let header_extra_1 = r.named("bs_header_extra_1").read_bool()?;
+        let header_extra_2 = r.named("bs_header_extra_2").read_bool()?;
+        let extra_1 = if header_extra_1 {
+            Some(SbrHeaderExtra1 {
+                freq_scale: r.named("bs_freq_scale").read_u8(2)?,
+                alter_scale: r.named("bs_alter_scale").read_bool()?,
+                noise_bands: r.named("bs_noise_bands").read_u8(2)?,
+            })
+        } else {
+            None
+        };
+        let extra_2 = if header_extra_2 {
+            Some(SbrHeaderExtra2 {
+                limiter_bands: r.named("bs_limiter_bands").read_u8(2)?,
+                limiter_gains: r.named("bs_limiter_gains").read_u8(2)?,
+                interpol_freq: r.named("bs_interpol_freq").read_bool()?,
+                smoothing_mode: r.named("bs_smoothing_mode").read_bool()?,
+            })
+        } else {
+            None
+        };
+        Ok(SbrHeader {
+            amp_res,
+            start_freq,
+            stop_freq,
+            xover_band,
+            reserved,
+            extra_1,
+            extra_2,
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn write(&self, w: &mut BitWriter) {
+        w.write_bool(self.amp_res);
+        w.write(4, u32::from(self.start_freq));
+        w.write(4, u32::from(self.stop_freq));
+        w.write(3, u32::from(self.xover_band));
+        w.write(2, u32::from(self.reserved));
+        w.write_bool(self.extra_1.is_some());
+        w.write_bool(self.extra_2.is_some());
+        if let Some(ref extra_1) = self.extra_1 {
+            w.write(2, u32::from(extra_1.freq_scale));
+            w.write_bool(extra_1.alter_scale);
+            w.write(2, u32::from(extra_1.noise_bands));
+        }
+        if let Some(ref extra_2) = self.extra_2 {
+            w.write(2, u32::from(extra_2.limiter_bands));
+            w.write(2, u32::from(extra_2.limiter_gains));
+            w.write_bool(extra_2.interpol_freq);
+            w.write_bool(extra_2.smoothing_mode);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromBitstream for SbrHeader {
+    type Context = ();
+    type Error = SbrError;
+
+    fn from_bitstream_with_length(data: &[u8], _: ()) -> Result<(Self, usize), SbrError> {
+        read_counted(data, Self::read)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToBitstream for SbrHeader {
+    fn to_bitstream(&self) -> RawBits {
+        write_bits(|w| self.write(w))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_fields() {
+        // amp_res=0, start=5, stop=9, xover=0, reserved=0, extra_1, extra_2, freq_scale=2,
+        // alter_scale=1, noise_bands=2, limiter_bands=2, limiter_gains=2, interpol_freq=1,
+        // smoothing_mode=1
+        let header = SbrHeader::from_bytes(&[0x2c, 0x83, 0xb5, 0x60]).unwrap();
+        assert_eq!(5, header.start_freq);
+        assert_eq!(9, header.stop_freq);
+        assert_eq!(
+            Some(SbrHeaderExtra1 {
+                freq_scale: 2,
+                alter_scale: true,
+                noise_bands: 2
+            }),
+            header.extra_1
+        );
+        assert_eq!(
+            Some(SbrHeaderExtra2 {
+                limiter_bands: 2,
+                limiter_gains: 2,
+                interpol_freq: true,
+                smoothing_mode: true
+            }),
+            header.extra_2
+        );
+        assert_eq!(
+            Err(SbrError::NotEnoughData(TraceEntry {
+                bit_offset: 23,
+                bit_length: 2,
+                field: "bs_limiter_gains",
+                value: None,
+            })),
+            SbrHeader::from_bytes(&[0x2c, 0x83, 0xb5])
+        );
+    }
+}
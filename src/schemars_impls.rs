@@ -0,0 +1,70 @@
+//! `JsonSchema` implementations for the value types, describing the representation produced
+//! by their `Serialize` implementations (a number), while also admitting the constant names
+//! that deserialization accepts.
+
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+macro_rules! implement_schemars {
+    ( $( $t:ident $desc:literal ),* $(,)? ) => {
+        $(
+            impl JsonSchema for $t {
+                fn schema_name() -> Cow<'static, str> {
+                    Cow::Borrowed(stringify!($t))
+                }
+
+                fn schema_id() -> Cow<'static, str> {
+                    Cow::Borrowed(concat!("mpeg4_audio_const::", stringify!($t)))
+                }
+
+                fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                    let values: Vec<u8> = (0..=255)
+                        .filter(|v| $t::try_from(*v).is_ok())
+                        .collect();
+                    let names: Vec<&str> = values
+                        .iter()
+                        .filter_map(|v| $t::try_from(*v).ok().and_then(|v| v.name()))
+                        .collect();
+                    json_schema!({
+                        "description": $desc,
+                        "anyOf": [
+                            { "type": "integer", "enum": values },
+                            { "type": "string", "enum": names },
+                        ],
+                    })
+                }
+            }
+        )*
+    }
+}
+
+implement_schemars! {
+    AudioObjectType "An MPEG-4 audio object type",
+    SamplingFrequencyIndex "An MPEG-4 audio sampling frequency index",
+    ChannelConfiguration "An MPEG-4 audio channel configuration",
+    AudioProfileLevelIndication "An MPEG-4 audio profile and level indication",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(AudioObjectType)).unwrap();
+        let values = &schema["anyOf"][0]["enum"];
+        assert_eq!(Some(95), values.as_array().map(|v| v.len()));
+        assert!(!values.as_array().unwrap().contains(&31.into()));
+        assert!(schema["anyOf"][1]["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&"AAC_LC".into()));
+        let schema =
+            serde_json::to_value(schemars::schema_for!(crate::asc::AudioSpecificConfig)).unwrap();
+        assert!(schema["$defs"]["AudioObjectType"].is_object(), "{}", schema);
+    }
+}
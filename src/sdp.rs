@@ -0,0 +1,616 @@
+//! Helpers for describing MPEG-4 audio RTP payloads in SDP session descriptions (e.g. for RTSP
+//! servers).
+//!
+//! Both the RFC 3640 `mpeg4-generic` format ([`Mpeg4GenericFmtp`](struct.Mpeg4GenericFmtp.html))
+//! and the RFC 6416 `MP4A-LATM` format ([`Mp4aLatmFmtp`](struct.Mp4aLatmFmtp.html)) are
+//! supported.
+//!
+//! The RFC 3640 `mpeg4-generic` payload format carries access units (i.e. raw AAC frames)
+//! preceded by AU headers, whose layout is given by the format parameters of the `a=fmtp`
+//! attribute along with the decoder configuration.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::sdp::*;
+//! let asc = AudioSpecificConfig::from_bytes(&[0x11, 0x90]).unwrap();
+//! let fmtp = Mpeg4GenericFmtp::new(asc, Mode::AacHbr);
+//! assert_eq!(
+//!     "a=fmtp:96 streamType=5; profile-level-id=41; mode=AAC-hbr; config=1190; \
+//!      sizeLength=13; indexLength=3; indexDeltaLength=3",
+//!     fmtp.attribute(96)
+//! );
+//! ```
+//!
+//! Receivers can recover the parameters, including the decoder configuration, from the
+//! attribute,
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::sdp::*;
+//! let (payload_type, fmtp) = Mpeg4GenericFmtp::from_attribute(
+//!     "a=fmtp:97 streamtype=5;profile-level-id=15;mode=AAC-hbr;config=1210;\
+//!      SizeLength=13;IndexLength=3;IndexDeltaLength=3",
+//! )
+//! .unwrap();
+//! assert_eq!(97, payload_type);
+//! assert_eq!(Mode::AacHbr, fmtp.mode);
+//! assert_eq!(ChannelConfiguration::STEREO, fmtp.config.channel_configuration);
+//! ```
+
+use crate::asc::{AscError, AudioSpecificConfig};
+use crate::latm::{LatmError, StreamMuxConfig};
+use crate::rtp::AuHeaderLengths;
+use crate::{AudioObjectType, AudioProfileLevelIndication};
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
+
+/// The RTP payload format encoding name, as given in the `a=rtpmap` attribute, for RFC 3640
+pub const MPEG4_GENERIC: &str = "mpeg4-generic";
+
+/// The RTP payload format encoding name, as given in the `a=rtpmap` attribute, for RFC 6416
+/// LATM-multiplexed audio
+pub const MP4A_LATM: &str = "MP4A-LATM";
+
+/// The `streamType` value for audio streams
+pub const STREAM_TYPE_AUDIO: u8 = 0x05;
+
+/// Errors produced when parsing format parameters
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum FmtpError {
+    /// The attribute did not start with `a=fmtp:` (or `fmtp:`) followed by a payload type
+    /// number.
+    NotFmtpAttribute(String),
+    /// A required parameter was not given.
+    MissingParameter(&'static str),
+    /// A parameter's value could not be parsed.
+    BadParameterValue { name: String, value: String },
+    /// The `streamType` was not `5` (audio).
+    NotAudio(u8),
+    /// The `mode` was not one of the AAC modes.
+    UnsupportedMode(String),
+    /// The `config` parameter did not hold a valid `AudioSpecificConfig`.
+    Config(AscError),
+    /// The `config` parameter did not hold a valid `StreamMuxConfig`.
+    StreamMuxConfig(LatmError),
+}
+
+impl fmt::Display for FmtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FmtpError::NotFmtpAttribute(s) => write!(f, "{:?} is not an fmtp attribute", s),
+            FmtpError::MissingParameter(name) => write!(f, "missing parameter {}", name),
+            FmtpError::BadParameterValue { name, value } => {
+                write!(f, "bad value {:?} for parameter {}", value, name)
+            }
+            FmtpError::NotAudio(v) => write!(f, "streamType {} is not audio", v),
+            FmtpError::UnsupportedMode(m) => write!(f, "unsupported mode {:?}", m),
+            FmtpError::Config(_) => f.write_str("invalid config"),
+            FmtpError::StreamMuxConfig(_) => f.write_str("invalid StreamMuxConfig"),
+        }
+    }
+}
+
+impl core::error::Error for FmtpError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            FmtpError::Config(e) => Some(e),
+            FmtpError::StreamMuxConfig(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The RFC 3640 mode of operation for AAC
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Mode {
+    /// Low bit-rate AAC, for access units of at most 63 bytes
+    AacLbr,
+    /// High bit-rate AAC, for access units of up to 8191 bytes
+    AacHbr,
+}
+
+impl Mode {
+    /// The value of the `mode` parameter, e.g. `"AAC-hbr"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::AacLbr => "AAC-lbr",
+            Mode::AacHbr => "AAC-hbr",
+        }
+    }
+
+    /// The number of bits in the AU-size field of each AU header, as required by this mode.
+    pub fn size_length(self) -> u8 {
+        match self {
+            Mode::AacLbr => 6,
+            Mode::AacHbr => 13,
+        }
+    }
+
+    /// The number of bits in the AU-Index and AU-Index-delta fields of each AU header, as
+    /// required by this mode.
+    pub fn index_length(self) -> u8 {
+        match self {
+            Mode::AacLbr => 2,
+            Mode::AacHbr => 3,
+        }
+    }
+}
+
+impl FromStr for Mode {
+    type Err = FmtpError;
+
+    /// Parses the `mode` parameter value, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("AAC-lbr") {
+            Ok(Mode::AacLbr)
+        } else if s.eq_ignore_ascii_case("AAC-hbr") {
+            Ok(Mode::AacHbr)
+        } else {
+            Err(FmtpError::UnsupportedMode(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The format parameters of an RFC 3640 `mpeg4-generic` audio payload.
+///
+/// The `Display` implementation produces the parameter list, as given after the payload type
+/// in the `a=fmtp` attribute, and the `FromStr` implementation parses such a list.
+///
+/// Parsing ignores the case of parameter names (and of the `mode` value), and ignores
+/// parameters it does not interpret.  The `mode` and `config` parameters are required, but
+/// for compatibility with senders that omit them, a missing `profile-level-id` is taken to be
+/// `NOT_SPECIFIED`, and missing AU header field lengths take the values required by the mode.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::sdp::*;
+/// let fmtp: Mpeg4GenericFmtp = "mode=AAC-lbr; config=1190".parse().unwrap();
+/// assert_eq!(AudioProfileLevelIndication::NOT_SPECIFIED, fmtp.profile_level_id);
+/// assert_eq!(6, fmtp.size_length);
+/// assert_eq!(
+///     Err(FmtpError::MissingParameter("config")),
+///     "mode=AAC-lbr".parse::<Mpeg4GenericFmtp>()
+/// );
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Mpeg4GenericFmtp {
+    pub profile_level_id: AudioProfileLevelIndication,
+    pub mode: Mode,
+    /// The decoder configuration, given in hexadecimal by the `config` parameter
+    pub config: AudioSpecificConfig,
+    pub size_length: u8,
+    pub index_length: u8,
+    pub index_delta_length: u8,
+}
+
+impl Mpeg4GenericFmtp {
+    /// Parameters for the given configuration and mode, with the AU header field lengths that
+    /// the mode requires, and a `profile-level-id` chosen by
+    /// [`AudioSpecificConfig::audio_profile_level_indication()`](../asc/struct.AudioSpecificConfig.html#method.audio_profile_level_indication).
+    pub fn new(config: AudioSpecificConfig, mode: Mode) -> Mpeg4GenericFmtp {
+        Mpeg4GenericFmtp {
+            profile_level_id: config.audio_profile_level_indication(),
+            mode,
+            config,
+            size_length: mode.size_length(),
+            index_length: mode.index_length(),
+            index_delta_length: mode.index_length(),
+        }
+    }
+
+    /// The AU header layout described by these parameters, for use in de-packetizing with
+    /// [`rtp::AuHeaderSection`](../rtp/struct.AuHeaderSection.html).
+    pub fn au_header_lengths(&self) -> AuHeaderLengths {
+        AuHeaderLengths {
+            size_length: self.size_length,
+            index_length: self.index_length,
+            index_delta_length: self.index_delta_length,
+        }
+    }
+
+    /// Parses a complete `a=fmtp` attribute line (the `a=` prefix being optional), returning
+    /// the payload type along with the parameters.
+    pub fn from_attribute(attribute: &str) -> Result<(u8, Mpeg4GenericFmtp), FmtpError> {
+        let (payload_type, params) = split_attribute(attribute)?;
+        Ok((payload_type, params.parse()?))
+    }
+
+    /// The complete `a=fmtp` attribute line (without line terminator) for the given RTP
+    /// payload type.
+    pub fn attribute(&self, payload_type: u8) -> String {
+        format!("a=fmtp:{} {}", payload_type, self)
+    }
+}
+
+impl fmt::Display for Mpeg4GenericFmtp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "streamType={}; profile-level-id={}; mode={}; config=",
+            STREAM_TYPE_AUDIO,
+            u8::from(self.profile_level_id),
+            self.mode
+        )?;
+        write_hex(f, &self.config.to_bytes())?;
+        write!(
+            f,
+            "; sizeLength={}; indexLength={}; indexDeltaLength={}",
+            self.size_length, self.index_length, self.index_delta_length
+        )
+    }
+}
+
+impl FromStr for Mpeg4GenericFmtp {
+    type Err = FmtpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stream_type = None;
+        let mut profile_level_id: Option<u8> = None;
+        let mut mode = None;
+        let mut config = None;
+        let mut size_length = None;
+        let mut index_length = None;
+        let mut index_delta_length = None;
+        for (name, value) in params(s) {
+            match name.to_ascii_lowercase().as_str() {
+                "streamtype" => stream_type = Some(parse_number(name, value)?),
+                "profile-level-id" => profile_level_id = Some(parse_number(name, value)?),
+                "mode" => mode = Some(value.parse::<Mode>()?),
+                "config" => config = Some(parse_hex(name, value)?),
+                "sizelength" => size_length = Some(parse_field_length(name, value)?),
+                "indexlength" => index_length = Some(parse_field_length(name, value)?),
+                "indexdeltalength" => index_delta_length = Some(parse_field_length(name, value)?),
+                _ => (),
+            }
+        }
+        if let Some(stream_type) = stream_type {
+            if stream_type != STREAM_TYPE_AUDIO {
+                return Err(FmtpError::NotAudio(stream_type));
+            }
+        }
+        let mode = mode.ok_or(FmtpError::MissingParameter("mode"))?;
+        let config = config.ok_or(FmtpError::MissingParameter("config"))?;
+        Ok(Mpeg4GenericFmtp {
+            profile_level_id: profile_level_id
+                .map(AudioProfileLevelIndication::from)
+                .unwrap_or(AudioProfileLevelIndication::NOT_SPECIFIED),
+            mode,
+            config: AudioSpecificConfig::from_bytes(&config).map_err(FmtpError::Config)?,
+            size_length: size_length.unwrap_or_else(|| mode.size_length()),
+            index_length: index_length.unwrap_or_else(|| mode.index_length()),
+            index_delta_length: index_delta_length.unwrap_or_else(|| mode.index_length()),
+        })
+    }
+}
+
+/// The format parameters of an RFC 6416 `MP4A-LATM` audio payload.
+///
+/// When `cpresent` is `false` the `StreamMuxConfig` is not carried in-band, and is instead
+/// given (in hexadecimal) by the `config` parameter.
+///
+/// As with [`Mpeg4GenericFmtp`](struct.Mpeg4GenericFmtp.html), `Display` and `FromStr`
+/// produce and parse the parameter list.  A missing `profile-level-id` takes the default value
+/// of `30` (Natural Audio Profile, Level 1) given by the RFC, and a missing `cpresent` is
+/// taken to be `1`.
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::sdp::*;
+/// let (_, fmtp) = Mp4aLatmFmtp::from_attribute(
+///     "a=fmtp:96 profile-level-id=24; object=2; cpresent=0; config=400024203fc0",
+/// )
+/// .unwrap();
+/// let asc = fmtp.config.unwrap().audio_specific_config().unwrap().clone();
+/// assert_eq!(ChannelConfiguration::STEREO, asc.channel_configuration);
+///
+/// let fmtp = Mp4aLatmFmtp::new(asc);
+/// assert_eq!(
+///     "profile-level-id=41; object=2; cpresent=0; config=400024203fc0",
+///     fmtp.to_string()
+/// );
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Mp4aLatmFmtp {
+    pub profile_level_id: AudioProfileLevelIndication,
+    /// The audio object type, given by the `object` parameter
+    pub object: Option<AudioObjectType>,
+    /// The maximum bitrate in bits per second
+    pub bitrate: Option<u32>,
+    /// `true` if the `StreamMuxConfig` is present in-band
+    pub cpresent: bool,
+    /// The out-of-band `StreamMuxConfig`
+    pub config: Option<StreamMuxConfig>,
+    /// The `SBR-enabled` parameter
+    pub sbr_enabled: Option<bool>,
+}
+
+impl Mp4aLatmFmtp {
+    /// Parameters for a LATM stream carrying the given configuration, with an out-of-band
+    /// `StreamMuxConfig` as produced by
+    /// [`StreamMuxConfig::new()`](../latm/struct.StreamMuxConfig.html#method.new), and a
+    /// `profile-level-id` chosen by
+    /// [`AudioSpecificConfig::audio_profile_level_indication()`](../asc/struct.AudioSpecificConfig.html#method.audio_profile_level_indication).
+    pub fn new(config: AudioSpecificConfig) -> Mp4aLatmFmtp {
+        Mp4aLatmFmtp {
+            profile_level_id: config.audio_profile_level_indication(),
+            object: Some(config.audio_object_type),
+            bitrate: None,
+            cpresent: false,
+            config: Some(StreamMuxConfig::new(config)),
+            sbr_enabled: None,
+        }
+    }
+
+    /// Parses a complete `a=fmtp` attribute line (the `a=` prefix being optional), returning
+    /// the payload type along with the parameters.
+    pub fn from_attribute(attribute: &str) -> Result<(u8, Mp4aLatmFmtp), FmtpError> {
+        let (payload_type, params) = split_attribute(attribute)?;
+        Ok((payload_type, params.parse()?))
+    }
+
+    /// The complete `a=fmtp` attribute line (without line terminator) for the given RTP
+    /// payload type.
+    pub fn attribute(&self, payload_type: u8) -> String {
+        format!("a=fmtp:{} {}", payload_type, self)
+    }
+}
+
+impl fmt::Display for Mp4aLatmFmtp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "profile-level-id={}", u8::from(self.profile_level_id))?;
+        if let Some(object) = self.object {
+            write!(f, "; object={}", u8::from(object))?;
+        }
+        if let Some(bitrate) = self.bitrate {
+            write!(f, "; bitrate={}", bitrate)?;
+        }
+        write!(f, "; cpresent={}", if self.cpresent { 1 } else { 0 })?;
+        if let Some(ref config) = self.config {
+            f.write_str("; config=")?;
+            write_hex(f, &config.to_bytes())?;
+        }
+        if let Some(sbr_enabled) = self.sbr_enabled {
+            write!(f, "; SBR-enabled={}", if sbr_enabled { 1 } else { 0 })?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Mp4aLatmFmtp {
+    type Err = FmtpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut profile_level_id: Option<u8> = None;
+        let mut object = None;
+        let mut bitrate = None;
+        let mut cpresent = true;
+        let mut config = None;
+        let mut sbr_enabled = None;
+        for (name, value) in params(s) {
+            match name.to_ascii_lowercase().as_str() {
+                "profile-level-id" => profile_level_id = Some(parse_number(name, value)?),
+                "object" => {
+                    object = Some(
+                        AudioObjectType::try_from(parse_number::<u8>(name, value)?).map_err(
+                            |_| FmtpError::BadParameterValue {
+                                name: name.to_string(),
+                                value: value.to_string(),
+                            },
+                        )?,
+                    )
+                }
+                "bitrate" => bitrate = Some(parse_number(name, value)?),
+                "cpresent" => cpresent = parse_flag(name, value)?,
+                "config" => {
+                    config = Some(
+                        StreamMuxConfig::from_bytes(&parse_hex(name, value)?)
+                            .map_err(FmtpError::StreamMuxConfig)?,
+                    )
+                }
+                "sbr-enabled" => sbr_enabled = Some(parse_flag(name, value)?),
+                _ => (),
+            }
+        }
+        if !cpresent && config.is_none() {
+            return Err(FmtpError::MissingParameter("config"));
+        }
+        Ok(Mp4aLatmFmtp {
+            profile_level_id: profile_level_id
+                .map(AudioProfileLevelIndication::from)
+                .unwrap_or(AudioProfileLevelIndication::NATURAL_L1),
+            object,
+            bitrate,
+            cpresent,
+            config,
+            sbr_enabled,
+        })
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, data: &[u8]) -> fmt::Result {
+    for b in data {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
+/// Splits `a=fmtp:<format> <params>` into the payload type and the parameter list.
+fn split_attribute(attribute: &str) -> Result<(u8, &str), FmtpError> {
+    let bad = || FmtpError::NotFmtpAttribute(attribute.to_string());
+    let attribute = attribute.trim();
+    let rest = attribute.strip_prefix("a=").unwrap_or(attribute);
+    let rest = rest.strip_prefix("fmtp:").ok_or_else(bad)?;
+    let (format, params) = match rest.find(char::is_whitespace) {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, ""),
+    };
+    let payload_type = format.parse().map_err(|_| bad())?;
+    Ok((payload_type, params))
+}
+
+/// The `name=value` pairs of a `;`-separated parameter list, with surrounding whitespace
+/// removed.
+fn params(s: &str) -> impl Iterator<Item = (&str, &str)> {
+    s.split(';').filter_map(|param| {
+        let param = param.trim();
+        if param.is_empty() {
+            return None;
+        }
+        Some(match param.find('=') {
+            Some(i) => (param[..i].trim(), param[i + 1..].trim()),
+            None => (param, ""),
+        })
+    })
+}
+
+fn parse_number<T: FromStr>(name: &str, value: &str) -> Result<T, FmtpError> {
+    value.parse().map_err(|_| FmtpError::BadParameterValue {
+        name: name.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Parses an AU header field length, which must fit the `u32` fields of an `AuHeader`.
+fn parse_field_length(name: &str, value: &str) -> Result<u8, FmtpError> {
+    match parse_number(name, value)? {
+        len if len > AuHeaderLengths::MAX_FIELD_LENGTH => Err(FmtpError::BadParameterValue {
+            name: name.to_string(),
+            value: value.to_string(),
+        }),
+        len => Ok(len),
+    }
+}
+
+fn parse_flag(name: &str, value: &str) -> Result<bool, FmtpError> {
+    match value {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(FmtpError::BadParameterValue {
+            name: name.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+fn parse_hex(name: &str, value: &str) -> Result<Vec<u8>, FmtpError> {
+    let bad = || FmtpError::BadParameterValue {
+        name: name.to_string(),
+        value: value.to_string(),
+    };
+    if value.is_empty() || value.len() % 2 != 0 || !value.is_ascii() {
+        return Err(bad());
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).map_err(|_| bad()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_bitrate() {
+        // HE-AAC v2, signalled hierarchically
+        let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+        let fmtp = Mpeg4GenericFmtp::new(asc, Mode::AacLbr);
+        assert_eq!(
+            "streamType=5; profile-level-id=48; mode=AAC-lbr; config=eb098800; sizeLength=6; \
+             indexLength=2; indexDeltaLength=2",
+            fmtp.to_string()
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+        let fmtp = Mpeg4GenericFmtp::new(asc, Mode::AacHbr);
+        assert_eq!(
+            Ok((96, fmtp.clone())),
+            Mpeg4GenericFmtp::from_attribute(&fmtp.attribute(96))
+        );
+        assert_eq!(
+            Err(FmtpError::NotAudio(4)),
+            "streamType=4; mode=AAC-hbr; config=1190".parse::<Mpeg4GenericFmtp>()
+        );
+        assert_eq!(
+            Err(FmtpError::UnsupportedMode("CELP-cbr".to_string())),
+            "mode=CELP-cbr; config=1190".parse::<Mpeg4GenericFmtp>()
+        );
+        assert_eq!(
+            Err(FmtpError::BadParameterValue {
+                name: "config".to_string(),
+                value: "119".to_string()
+            }),
+            "mode=AAC-hbr; config=119".parse::<Mpeg4GenericFmtp>()
+        );
+        assert_eq!(
+            Err(FmtpError::BadParameterValue {
+                name: "sizeLength".to_string(),
+                value: "40".to_string()
+            }),
+            "mode=AAC-hbr; config=1190; sizeLength=40".parse::<Mpeg4GenericFmtp>()
+        );
+        assert!(matches!(
+            "mode=AAC-hbr; config=11".parse::<Mpeg4GenericFmtp>(),
+            Err(FmtpError::Config(AscError::NotEnoughData(_)))
+        ));
+        let err = "mode=AAC-hbr; config=11"
+            .parse::<Mpeg4GenericFmtp>()
+            .unwrap_err();
+        assert_eq!("invalid config", err.to_string());
+        assert_eq!(
+            "not enough data, reading bits 5..9 samplingFrequencyIndex",
+            core::error::Error::source(&err).unwrap().to_string()
+        );
+        assert_eq!(
+            Err(FmtpError::NotFmtpAttribute(
+                "a=rtpmap:96 mpeg4-generic/48000/2".to_string()
+            )),
+            Mpeg4GenericFmtp::from_attribute("a=rtpmap:96 mpeg4-generic/48000/2")
+        );
+    }
+
+    #[test]
+    fn latm() {
+        let fmtp: Mp4aLatmFmtp = "object=5; SBR-enabled=1".parse().unwrap();
+        assert_eq!(
+            AudioProfileLevelIndication::NATURAL_L1,
+            fmtp.profile_level_id
+        );
+        assert_eq!(Some(AudioObjectType::SBR), fmtp.object);
+        assert!(fmtp.cpresent);
+        assert_eq!(Some(true), fmtp.sbr_enabled);
+        assert_eq!(
+            Err(FmtpError::MissingParameter("config")),
+            "cpresent=0".parse::<Mp4aLatmFmtp>()
+        );
+        assert_eq!(
+            Err(FmtpError::BadParameterValue {
+                name: "cpresent".to_string(),
+                value: "yes".to_string()
+            }),
+            "cpresent=yes".parse::<Mp4aLatmFmtp>()
+        );
+        let asc = AudioSpecificConfig::from_bytes(&[0x11, 0x90]).unwrap();
+        let mut fmtp = Mp4aLatmFmtp::new(asc);
+        fmtp.bitrate = Some(128000);
+        assert_eq!(
+            Ok((98, fmtp.clone())),
+            Mp4aLatmFmtp::from_attribute(&fmtp.attribute(98))
+        );
+    }
+}
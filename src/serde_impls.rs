@@ -0,0 +1,132 @@
+//! `Serialize` and `Deserialize` implementations for the value types, which are represented
+//! by their numeric values.  Deserializing a value that the type can't represent (e.g. the
+//! audio object type escape value, `31`) fails.
+//!
+//! To suit hand-edited configuration files, values may also be deserialized from the name of
+//! the constant, ignoring case and treating `-` as `_` (so `"AAC_LC"` and `"aac-lc"` are both
+//! accepted).  See [`serde_names`](../serde_names/index.html) for serializing names.
+
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Accepts either a number or a constant name.
+struct NumberOrName<T>(PhantomData<T>);
+
+macro_rules! implement_serde {
+    ( $( $t:ident ),* $(,)? ) => {
+        $(
+            impl Serialize for $t {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_u8(u8::from(*self))
+                }
+            }
+
+            impl<'de> Visitor<'de> for NumberOrName<$t> {
+                type Value = $t;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a {} number or name", stringify!($t))
+                }
+
+                fn visit_u64<E: de::Error>(self, v: u64) -> Result<$t, E> {
+                    u8::try_from(v)
+                        .ok()
+                        .and_then(|v| $t::try_from(v).ok())
+                        .ok_or_else(|| {
+                            E::custom(format_args!("invalid {} value {}", stringify!($t), v))
+                        })
+                }
+
+                fn visit_i64<E: de::Error>(self, v: i64) -> Result<$t, E> {
+                    match u64::try_from(v) {
+                        Ok(v) => self.visit_u64(v),
+                        Err(_) => Err(E::custom(format_args!(
+                            "invalid {} value {}",
+                            stringify!($t),
+                            v
+                        ))),
+                    }
+                }
+
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<$t, E> {
+                    $t::from_const_name(v).ok_or_else(|| {
+                        E::custom(format_args!("unknown {} name {:?}", stringify!($t), v))
+                    })
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $t {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_any(NumberOrName::<$t>(PhantomData))
+                }
+            }
+        )*
+    };
+}
+
+implement_serde! {
+    AudioObjectType,
+    SamplingFrequencyIndex,
+    ChannelConfiguration,
+    AudioProfileLevelIndication,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let json = serde_json::to_string(&AudioObjectType::AAC_LC).unwrap();
+        assert_eq!("2", json);
+        let aot: AudioObjectType = serde_json::from_str(&json).unwrap();
+        assert_eq!(AudioObjectType::AAC_LC, aot);
+        assert!(serde_json::from_str::<AudioObjectType>("31").is_err());
+        assert!(serde_json::from_str::<AudioObjectType>("256").is_err());
+        assert!(serde_json::from_str::<AudioObjectType>("-1").is_err());
+        assert!(serde_json::from_str::<ChannelConfiguration>("16").is_err());
+        let apli: AudioProfileLevelIndication = serde_json::from_str("41").unwrap();
+        assert_eq!(AudioProfileLevelIndication::AAC_L2, apli);
+    }
+
+    #[test]
+    fn structs() {
+        for buf in &[
+            &[0x12, 0x10][..],
+            &[0x13, 0x10, 0x56, 0xe5, 0x9d, 0x48, 0x80],
+            &[0xf8, 0xe6, 0x21, 0x2a, 0xa0, 0x00],
+        ] {
+            let asc = crate::asc::AudioSpecificConfig::from_bytes(buf).unwrap();
+            let json = serde_json::to_string(&asc).unwrap();
+            assert_eq!(asc, serde_json::from_str(&json).unwrap());
+        }
+        let header =
+            crate::adts::AdtsHeader::from_bytes(&[0xff, 0xf1, 0x50, 0x80, 0x2e, 0x7f, 0xfc])
+                .unwrap();
+        let json = serde_json::to_string(&header).unwrap();
+        assert!(json.contains("\"audio_object_type\":2"), "{}", json);
+        assert_eq!(header, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn names() {
+        for name in &["\"AAC_LC\"", "\"aac-lc\"", "\"Aac_Lc\""] {
+            let aot: AudioObjectType = serde_json::from_str(name).unwrap();
+            assert_eq!(AudioObjectType::AAC_LC, aot);
+        }
+        let sfi: SamplingFrequencyIndex = serde_json::from_str("\"freq-44100\"").unwrap();
+        assert_eq!(SamplingFrequencyIndex::FREQ_44100, sfi);
+        let err = serde_json::from_str::<AudioObjectType>("\"aac lc\"").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown AudioObjectType name"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Serialization of the value types as the names of their constants, for use with
+//! `#[serde(with = "...")]` where the output is intended to be read or edited by people.
+//! Reserved values, which have no name, are serialized as numbers.
+//!
+//! Whether or not this is used, the types deserialize from either a number or a name (ignoring
+//! case, and treating `-` as `_`, so `"aac-lc"` is accepted), which requires a self-describing
+//! format such as JSON or TOML.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct EncoderSettings {
+//!     #[serde(with = "mpeg4_audio_const::serde_names")]
+//!     object_type: AudioObjectType,
+//!     channels: ChannelConfiguration,
+//! }
+//!
+//! let settings = EncoderSettings {
+//!     object_type: AudioObjectType::AAC_LC,
+//!     channels: ChannelConfiguration::STEREO,
+//! };
+//! let json = serde_json::to_string(&settings).unwrap();
+//! assert_eq!(r#"{"object_type":"AAC_LC","channels":2}"#, json);
+//! assert_eq!(settings, serde_json::from_str(&json).unwrap());
+//! ```
+
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, SamplingFrequencyIndex,
+};
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// A value type whose constants have names.
+pub trait NamedValue: Copy + Into<u8> {
+    /// The name of the constant for this value, or `None` if the value is reserved.
+    fn const_name(self) -> Option<&'static str>;
+}
+
+macro_rules! implement_named_value {
+    ( $( $t:ident ),* $(,)? ) => {
+        $(
+            impl NamedValue for $t {
+                fn const_name(self) -> Option<&'static str> {
+                    $t::name(self)
+                }
+            }
+        )*
+    };
+}
+
+implement_named_value! {
+    AudioObjectType,
+    SamplingFrequencyIndex,
+    ChannelConfiguration,
+    AudioProfileLevelIndication,
+}
+
+/// Serializes the value as the name of its constant, or as its number if it is reserved.
+pub fn serialize<T: NamedValue, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value.const_name() {
+        Some(name) => serializer.serialize_str(name),
+        None => serializer.serialize_u8((*value).into()),
+    }
+}
+
+/// Deserializes the value from either its name or its number (the same as the type's own
+/// `Deserialize` implementation).
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::deserialize(deserializer)
+}
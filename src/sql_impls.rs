@@ -0,0 +1,118 @@
+//! sqlx and diesel implementations for `AudioObjectType` and `ChannelConfiguration`, storing
+//! them as small integers.  Values read back are validated as by `TryFrom<u8>`, so that a
+//! corrupt or hand-edited row produces a decode error rather than an invalid value.
+
+use crate::{AudioObjectType, ChannelConfiguration};
+use core::convert::TryFrom;
+use core::fmt::Debug;
+
+/// The value for the given small integer column value, if valid
+fn from_small_int<T>(value: i16) -> Result<T, String>
+where
+    T: TryFrom<u8>,
+    T::Error: Debug,
+{
+    let byte = u8::try_from(value).map_err(|_| format!("value {} out of range", value))?;
+    T::try_from(byte).map_err(|e| format!("{:?}", e))
+}
+
+/// Every `u8` value as an `i16`, so that `ToSql` can lend a reference with the lifetime it
+/// requires
+#[cfg(feature = "diesel")]
+static SMALL_INTS: [i16; 256] = {
+    let mut values = [0; 256];
+    let mut i = 0;
+    while i < values.len() {
+        values[i] = i as i16;
+        i += 1;
+    }
+    values
+};
+
+macro_rules! implement_sql {
+    ( $( $t:ident ),* $(,)? ) => {
+        $(
+            #[cfg(feature = "sqlx")]
+            impl<DB: sqlx_core::database::Database> sqlx_core::types::Type<DB> for $t
+            where
+                i16: sqlx_core::types::Type<DB>,
+            {
+                fn type_info() -> DB::TypeInfo {
+                    <i16 as sqlx_core::types::Type<DB>>::type_info()
+                }
+
+                fn compatible(ty: &DB::TypeInfo) -> bool {
+                    <i16 as sqlx_core::types::Type<DB>>::compatible(ty)
+                }
+            }
+
+            #[cfg(feature = "sqlx")]
+            impl<'q, DB: sqlx_core::database::Database> sqlx_core::encode::Encode<'q, DB> for $t
+            where
+                i16: sqlx_core::encode::Encode<'q, DB>,
+            {
+                fn encode_by_ref(
+                    &self,
+                    buf: &mut DB::ArgumentBuffer<'q>,
+                ) -> Result<sqlx_core::encode::IsNull, sqlx_core::error::BoxDynError> {
+                    i16::from(u8::from(*self)).encode_by_ref(buf)
+                }
+            }
+
+            #[cfg(feature = "sqlx")]
+            impl<'r, DB: sqlx_core::database::Database> sqlx_core::decode::Decode<'r, DB> for $t
+            where
+                i16: sqlx_core::decode::Decode<'r, DB>,
+            {
+                fn decode(
+                    value: DB::ValueRef<'r>,
+                ) -> Result<Self, sqlx_core::error::BoxDynError> {
+                    Ok(from_small_int(i16::decode(value)?)?)
+                }
+            }
+
+            #[cfg(feature = "diesel")]
+            impl<DB: diesel::backend::Backend>
+                diesel::serialize::ToSql<diesel::sql_types::SmallInt, DB> for $t
+            where
+                i16: diesel::serialize::ToSql<diesel::sql_types::SmallInt, DB>,
+            {
+                fn to_sql<'b>(
+                    &'b self,
+                    out: &mut diesel::serialize::Output<'b, '_, DB>,
+                ) -> diesel::serialize::Result {
+                    SMALL_INTS[usize::from(u8::from(*self))].to_sql(out)
+                }
+            }
+
+            #[cfg(feature = "diesel")]
+            impl<DB: diesel::backend::Backend>
+                diesel::deserialize::FromSql<diesel::sql_types::SmallInt, DB> for $t
+            where
+                i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, DB>,
+            {
+                fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+                    Ok(from_small_int(i16::from_sql(bytes)?)?)
+                }
+            }
+        )*
+    }
+}
+
+implement_sql! {
+    AudioObjectType,
+    ChannelConfiguration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        assert_eq!(Ok(AudioObjectType::AAC_LC), from_small_int(2));
+        assert!(from_small_int::<AudioObjectType>(31).is_err());
+        assert!(from_small_int::<AudioObjectType>(-1).is_err());
+        assert!(from_small_int::<ChannelConfiguration>(16).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Mappings to the codec types and parameters of the
+//! [Symphonia](https://crates.io/crates/symphonia) decoding framework, allowing a parsed
+//! configuration to be handed to a Symphonia decoder.
+//!
+//! ```rust
+//! # use mpeg4_audio_const::asc::*;
+//! # use mpeg4_audio_const::symphonia::*;
+//! use symphonia_core::audio::Channels;
+//! use symphonia_core::codecs::CODEC_TYPE_AAC;
+//!
+//! let asc = AudioSpecificConfig::from_bytes(&[0x12, 0x10]).unwrap();
+//! let params = codec_parameters(&asc).unwrap();
+//! assert_eq!(CODEC_TYPE_AAC, params.codec);
+//! assert_eq!(Some(44100), params.sample_rate);
+//! assert_eq!(Some(Channels::FRONT_LEFT | Channels::FRONT_RIGHT), params.channels);
+//! assert_eq!(Some(&[0x12, 0x10][..]), params.extra_data.as_deref());
+//! ```
+
+use crate::asc::AudioSpecificConfig;
+use crate::{AudioObjectType, ChannelConfiguration};
+use symphonia_core::audio::Channels;
+use symphonia_core::codecs::{
+    CodecParameters, CodecType, CODEC_TYPE_AAC, CODEC_TYPE_MP1, CODEC_TYPE_MP2, CODEC_TYPE_MP3,
+};
+
+/// The Symphonia codec type for the given audio object type, or `None` if Symphonia defines
+/// no codec type for it.
+///
+/// All object types of the AAC family (including those using SBR and PS) map to
+/// `CODEC_TYPE_AAC`, with the exception of `USAC`.
+pub fn codec_type(audio_object_type: AudioObjectType) -> Option<CodecType> {
+    Some(match audio_object_type {
+        AudioObjectType::AAC_MAIN
+        | AudioObjectType::AAC_LC
+        | AudioObjectType::AAC_SSR
+        | AudioObjectType::AAC_LTP
+        | AudioObjectType::SBR
+        | AudioObjectType::AAC_SCALABLE
+        | AudioObjectType::ER_AAC_LC
+        | AudioObjectType::ER_AAC_LTP
+        | AudioObjectType::ER_AAC_SCALABLE
+        | AudioObjectType::ER_AAC_LD
+        | AudioObjectType::PS
+        | AudioObjectType::ER_AAC_ELD => CODEC_TYPE_AAC,
+        AudioObjectType::LAYER1 => CODEC_TYPE_MP1,
+        AudioObjectType::LAYER2 => CODEC_TYPE_MP2,
+        AudioObjectType::LAYER3 => CODEC_TYPE_MP3,
+        _ => return None,
+    })
+}
+
+/// The Symphonia channel positions for the given channel configuration, or `None` for `0`,
+/// for reserved values, and for the 22.2 layout (which uses positions Symphonia can't
+/// represent).
+pub fn channels(channel_configuration: ChannelConfiguration) -> Option<Channels> {
+    let front = Channels::FRONT_LEFT | Channels::FRONT_RIGHT;
+    let front_centre = front | Channels::FRONT_CENTRE;
+    let rear = Channels::REAR_LEFT | Channels::REAR_RIGHT;
+    Some(match channel_configuration {
+        ChannelConfiguration::MONO => Channels::FRONT_CENTRE,
+        ChannelConfiguration::STEREO => front,
+        ChannelConfiguration::THREE => front_centre,
+        ChannelConfiguration::FOUR => front_centre | Channels::REAR_CENTRE,
+        ChannelConfiguration::FIVE => front_centre | rear,
+        ChannelConfiguration::FIVE_ONE => front_centre | rear | Channels::LFE1,
+        ChannelConfiguration::SEVEN_ONE => {
+            front_centre
+                | Channels::FRONT_LEFT_CENTRE
+                | Channels::FRONT_RIGHT_CENTRE
+                | rear
+                | Channels::LFE1
+        }
+        ChannelConfiguration::SIX_ONE => {
+            front_centre | rear | Channels::REAR_CENTRE | Channels::LFE1
+        }
+        ChannelConfiguration::SEVEN_ONE_BACK => {
+            front_centre | Channels::SIDE_LEFT | Channels::SIDE_RIGHT | rear | Channels::LFE1
+        }
+        ChannelConfiguration::SEVEN_ONE_TOP_FRONT => {
+            front_centre
+                | rear
+                | Channels::LFE1
+                | Channels::TOP_FRONT_LEFT
+                | Channels::TOP_FRONT_RIGHT
+        }
+        _ => return None,
+    })
+}
+
+/// Symphonia codec parameters for the given configuration, or `None` if Symphonia defines no
+/// codec type for the object type.
+///
+/// The configuration is given as the `extra_data`.  The sample rate and channels describe the
+/// decoded output, so take account of any SBR or PS extension; parametric stereo applied to a
+/// mono core produces `FRONT_LEFT | FRONT_RIGHT`.  Channels are not given when the layout is
+/// described by a program config element.
+pub fn codec_parameters(asc: &AudioSpecificConfig) -> Option<CodecParameters> {
+    let mut params = CodecParameters::new();
+    params
+        .for_codec(codec_type(asc.signalled_object_type())?)
+        .with_extra_data(asc.to_bytes().into_boxed_slice());
+    if let Some(rate) = asc.output_sample_rate() {
+        params.with_sample_rate(rate);
+    }
+    let ps_present = asc.extension.as_ref().is_some_and(|e| e.ps_present());
+    let channels = if ps_present && asc.channel_configuration == ChannelConfiguration::MONO {
+        Some(Channels::FRONT_LEFT | Channels::FRONT_RIGHT)
+    } else {
+        channels(asc.channel_configuration)
+    };
+    if let Some(channels) = channels {
+        params.with_channels(channels);
+    }
+    Some(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn he_aac_v2() {
+        let asc = AudioSpecificConfig::from_bytes(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+        let params = codec_parameters(&asc).unwrap();
+        assert_eq!(Some(48000), params.sample_rate);
+        assert_eq!(
+            Some(Channels::FRONT_LEFT | Channels::FRONT_RIGHT),
+            params.channels
+        );
+        let usac = AudioSpecificConfig::from_bytes(&[0xf9, 0x46, 0x40, 0x00]).unwrap();
+        assert!(codec_parameters(&usac).is_none());
+        for c in 1..=14 {
+            let conf = ChannelConfiguration::try_from(c).unwrap();
+            if let Some(channels) = channels(conf) {
+                assert_eq!(conf.channel_count(), Some(channels.count() as u32));
+            }
+        }
+    }
+}
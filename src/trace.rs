@@ -0,0 +1,97 @@
+//! Bit-level traces of parsing, recording the position, width and value of each syntax
+//! element read, for diagnosing malformed data.
+//!
+//! The same [`TraceEntry`](struct.TraceEntry.html) type is carried by the parsing errors, to
+//! give the element at which parsing failed, and is passed to an
+//! [`AscVisitor`](trait.AscVisitor.html) as each element is read.
+//!
+//! ```rust
+//! # #[cfg(feature = "alloc")] {
+//! # use mpeg4_audio_const::asc::*;
+//! let (asc, trace) = AudioSpecificConfig::from_bytes_traced(&[0x12, 0x10]);
+//! assert!(asc.is_ok());
+//! assert_eq!("bits 0..5 audioObjectType=2", trace[0].to_string());
+//! assert_eq!("bits 5..9 samplingFrequencyIndex=4", trace[1].to_string());
+//! # }
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::ControlFlow;
+
+/// A syntax element read by a parser, with its location within the data
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TraceEntry {
+    /// The offset of the first bit of the element from the start of the data
+    pub bit_offset: usize,
+    /// The number of bits the element occupies (or would occupy, where the data ended before
+    /// its end)
+    pub bit_length: usize,
+    /// The name of the element, as given in the syntax tables of the spec (or empty for the few
+    /// elements that are not named)
+    pub field: &'static str,
+    /// The value read, or `None` for elements longer than 32 bits, skipped without being
+    /// interpreted, or not present in the data
+    pub value: Option<u32>,
+}
+
+/// Formats the entry as e.g. `bits 0..5 audioObjectType=2`.
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bits {}..{} {}",
+            self.bit_offset,
+            self.bit_offset + self.bit_length,
+            self.field
+        )?;
+        if let Some(value) = self.value {
+            write!(f, "={}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives each syntax element as it is read, for examining data without needing the whole
+/// structure to be parsed
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// # use mpeg4_audio_const::asc::*;
+/// # use mpeg4_audio_const::trace::*;
+/// # use core::ops::ControlFlow;
+/// /// Finds the `channelConfiguration`, stopping once it is found.
+/// #[derive(Default)]
+/// struct ChannelConfigurationFinder(Option<u32>);
+///
+/// impl AscVisitor for ChannelConfigurationFinder {
+///     fn field(&mut self, entry: TraceEntry) -> ControlFlow<()> {
+///         if entry.field == "channelConfiguration" {
+///             self.0 = entry.value;
+///             return ControlFlow::Break(());
+///         }
+///         ControlFlow::Continue(())
+///     }
+/// }
+///
+/// let mut finder = ChannelConfigurationFinder::default();
+/// AudioSpecificConfig::parse_with(&[0x12, 0x10], &mut finder).unwrap();
+/// assert_eq!(Some(2), finder.0);
+/// # }
+/// ```
+pub trait AscVisitor {
+    /// Called for each syntax element, in the order they appear in the data.  Returning
+    /// `ControlFlow::Break` stops parsing.
+    fn field(&mut self, entry: TraceEntry) -> ControlFlow<()>;
+}
+
+/// Collects the elements, giving a trace of the whole parse.
+#[cfg(feature = "alloc")]
+impl AscVisitor for Vec<TraceEntry> {
+    fn field(&mut self, entry: TraceEntry) -> ControlFlow<()> {
+        self.push(entry);
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,395 @@
+//! Parsing and serialization of the MPEG-2 Transport Stream (ISO/IEC 13818-1) descriptors that
+//! signal MPEG-4 audio in a program map table, and of the DVB `AAC_descriptor` (ETSI EN 300 468).
+//!
+//! Each descriptor is handled including its `descriptor_tag` and `descriptor_length` bytes,
+//!
+//! ```rust
+//! # use mpeg4_audio_const::*;
+//! # use mpeg4_audio_const::ts::*;
+//! let desc = Mpeg4AudioDescriptor::from_bytes(&[0x1c, 0x01, 0x29]).unwrap();
+//! assert_eq!(AudioProfileLevelIndication::AAC_L2, desc.profile_and_level);
+//! assert_eq!(vec![0x1c, 0x01, 0x29], desc.to_bytes());
+//! ```
+
+use crate::bits::{BitReader, EndOfData};
+use crate::trace::TraceEntry;
+use crate::{
+    AudioObjectType, AudioProfileLevelIndication, ChannelConfiguration, ChannelConfigurationError,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+/// The `stream_type` for ADTS-framed MPEG-2 AAC (ISO/IEC 13818-7) audio
+pub const STREAM_TYPE_ADTS: u8 = 0x0f;
+/// The `stream_type` for LATM/LOAS-framed MPEG-4 audio
+pub const STREAM_TYPE_LATM: u8 = 0x11;
+
+/// The tag of the `MPEG-4_audio_descriptor`
+pub const MPEG4_AUDIO_DESCRIPTOR_TAG: u8 = 0x1c;
+/// The tag of the `MPEG-2_AAC_audio_descriptor`
+pub const MPEG2_AAC_AUDIO_DESCRIPTOR_TAG: u8 = 0x2b;
+/// The tag of the DVB `AAC_descriptor`
+pub const DVB_AAC_DESCRIPTOR_TAG: u8 = 0x7c;
+
+/// The DVB `AAC_type` for HE-AAC mono audio
+pub const AAC_TYPE_HE_AAC_MONO: u8 = 0x01;
+/// The DVB `AAC_type` for HE-AAC stereo audio
+pub const AAC_TYPE_HE_AAC_STEREO: u8 = 0x03;
+/// The DVB `AAC_type` for HE-AAC surround audio
+pub const AAC_TYPE_HE_AAC_SURROUND: u8 = 0x05;
+/// The DVB `AAC_type` for HE-AAC v2 stereo audio
+pub const AAC_TYPE_HE_AAC_V2_STEREO: u8 = 0x43;
+
+/// Errors that may be produced while parsing descriptors
+#[derive(PartialEq, Debug)]
+#[non_exhaustive]
+pub enum DescriptorError {
+    /// The data ended before the end of the descriptor, while reading the given element.
+    NotEnoughData(TraceEntry),
+    /// The descriptor had a different tag to the one being parsed.
+    UnexpectedTag { expected: u8, actual: u8 },
+    /// The descriptor gave a channel configuration that can't be represented.
+    ChannelConfiguration(ChannelConfigurationError),
+}
+
+impl fmt::Display for DescriptorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DescriptorError::NotEnoughData(at) => write!(f, "not enough data, reading {}", at),
+            DescriptorError::UnexpectedTag { expected, actual } => write!(
+                f,
+                "unexpected descriptor tag {:#04x}, expected {:#04x}",
+                actual, expected
+            ),
+            DescriptorError::ChannelConfiguration(_) => {
+                f.write_str("unrepresentable channel configuration")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DescriptorError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DescriptorError::ChannelConfiguration(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EndOfData> for DescriptorError {
+    fn from(EndOfData(at): EndOfData) -> Self {
+        DescriptorError::NotEnoughData(at)
+    }
+}
+
+/// The `MPEG-4_audio_descriptor`, giving the profile and level of an MPEG-4 audio elementary
+/// stream
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Mpeg4AudioDescriptor {
+    pub profile_and_level: AudioProfileLevelIndication,
+}
+
+impl Mpeg4AudioDescriptor {
+    /// Parses the descriptor from the start of the given buffer.
+    pub fn from_bytes(buf: &[u8]) -> Result<Mpeg4AudioDescriptor, DescriptorError> {
+        read_descriptor(buf, MPEG4_AUDIO_DESCRIPTOR_TAG, |r| {
+            Ok(Mpeg4AudioDescriptor {
+                profile_and_level: r.named("MPEG-4_audio_profile_and_level").read_u8(8)?.into(),
+            })
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        vec![MPEG4_AUDIO_DESCRIPTOR_TAG, 1, self.profile_and_level.into()]
+    }
+}
+
+/// The `MPEG-2_AAC_additional_information` value of an `MPEG-2_AAC_audio_descriptor`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum AacAdditionalInformation {
+    /// `0x00`: AAC data as defined by ISO/IEC 13818-7
+    None,
+    /// `0x01`: AAC data with bandwidth extension (i.e. SBR) data present
+    BandwidthExtension,
+    Reserved(u8),
+}
+
+impl From<u8> for AacAdditionalInformation {
+    fn from(v: u8) -> Self {
+        match v {
+            0x00 => AacAdditionalInformation::None,
+            0x01 => AacAdditionalInformation::BandwidthExtension,
+            _ => AacAdditionalInformation::Reserved(v),
+        }
+    }
+}
+
+impl From<AacAdditionalInformation> for u8 {
+    fn from(v: AacAdditionalInformation) -> Self {
+        match v {
+            AacAdditionalInformation::None => 0x00,
+            AacAdditionalInformation::BandwidthExtension => 0x01,
+            AacAdditionalInformation::Reserved(v) => v,
+        }
+    }
+}
+
+/// The `MPEG-2_AAC_audio_descriptor`, describing an ADTS-framed MPEG-2 AAC elementary stream
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::ts::*;
+/// let desc = Mpeg2AacAudioDescriptor::from_bytes(&[0x2b, 0x03, 0x01, 0x02, 0x01]).unwrap();
+/// assert_eq!(Some(AudioObjectType::AAC_LC), desc.audio_object_type());
+/// assert_eq!(ChannelConfiguration::STEREO, desc.channel_configuration);
+/// assert_eq!(AacAdditionalInformation::BandwidthExtension, desc.additional_information);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Mpeg2AacAudioDescriptor {
+    /// The `MPEG-2_AAC_profile` value, where `0` is Main, `1` is LC and `2` is SSR (as for the
+    /// ADTS `profile` field)
+    pub profile: u8,
+    pub channel_configuration: ChannelConfiguration,
+    pub additional_information: AacAdditionalInformation,
+}
+
+impl Mpeg2AacAudioDescriptor {
+    /// A descriptor for the given object type, which must be one of those that an ADTS header
+    /// can signal (`AAC_MAIN`, `AAC_LC`, `AAC_SSR` or `AAC_LTP`).
+    pub fn new(
+        audio_object_type: AudioObjectType,
+        channel_configuration: ChannelConfiguration,
+        additional_information: AacAdditionalInformation,
+    ) -> Option<Mpeg2AacAudioDescriptor> {
+        let profile = u8::from(audio_object_type)
+            .checked_sub(1)
+            .filter(|p| *p <= 3)?;
+        Some(Mpeg2AacAudioDescriptor {
+            profile,
+            channel_configuration,
+            additional_information,
+        })
+    }
+
+    /// Parses the descriptor from the start of the given buffer.
+    pub fn from_bytes(buf: &[u8]) -> Result<Mpeg2AacAudioDescriptor, DescriptorError> {
+        read_descriptor(buf, MPEG2_AAC_AUDIO_DESCRIPTOR_TAG, |r| {
+            Ok(Mpeg2AacAudioDescriptor {
+                profile: r.named("MPEG-2_AAC_profile").read_u8(8)?,
+                channel_configuration: ChannelConfiguration::try_from(
+                    r.named("MPEG-2_AAC_channel_configuration").read_u8(8)?,
+                )
+                .map_err(DescriptorError::ChannelConfiguration)?,
+                additional_information: r
+                    .named("MPEG-2_AAC_additional_information")
+                    .read_u8(8)?
+                    .into(),
+            })
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        vec![
+            MPEG2_AAC_AUDIO_DESCRIPTOR_TAG,
+            3,
+            self.profile,
+            self.channel_configuration.into(),
+            self.additional_information.into(),
+        ]
+    }
+
+    /// The audio object type corresponding to the `profile`, derived by adding `1` as for ADTS
+    /// headers, or `None` if the value is out of range.
+    pub fn audio_object_type(&self) -> Option<AudioObjectType> {
+        if self.profile <= 3 {
+            AudioObjectType::try_from(self.profile + 1).ok()
+        } else {
+            None
+        }
+    }
+}
+
+/// The DVB `AAC_descriptor`, which a DVB service uses in place of the `MPEG-4_audio_descriptor`
+///
+/// ```rust
+/// # use mpeg4_audio_const::*;
+/// # use mpeg4_audio_const::ts::*;
+/// let desc = DvbAacDescriptor::from_bytes(&[0x7c, 0x03, 0x2c, 0x80, 0x03]).unwrap();
+/// assert_eq!(AudioProfileLevelIndication::HE_AAC_L2, desc.profile_and_level);
+/// assert_eq!(Some(AAC_TYPE_HE_AAC_STEREO), desc.aac_type);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct DvbAacDescriptor {
+    pub profile_and_level: AudioProfileLevelIndication,
+    /// The `AAC_type` value (e.g. [`AAC_TYPE_HE_AAC_STEREO`](constant.AAC_TYPE_HE_AAC_STEREO.html)),
+    /// present if the `AAC_type_flag` is set
+    pub aac_type: Option<u8>,
+    /// The `SAOC_DE_flag`, signalling that the stream carries SAOC-DE parametric data
+    pub saoc_de: bool,
+    /// Any `additional_info_byte`s following the fields above
+    pub additional_info: Vec<u8>,
+}
+
+impl DvbAacDescriptor {
+    /// Parses the descriptor from the start of the given buffer.  The optional fields are absent
+    /// if the descriptor holds only the `profile_and_level` byte.
+    pub fn from_bytes(buf: &[u8]) -> Result<DvbAacDescriptor, DescriptorError> {
+        read_descriptor(buf, DVB_AAC_DESCRIPTOR_TAG, |r| {
+            let mut desc = DvbAacDescriptor {
+                profile_and_level: r.named("profile_and_level").read_u8(8)?.into(),
+                aac_type: None,
+                saoc_de: false,
+                additional_info: vec![],
+            };
+            if r.remaining() > 0 {
+                let aac_type_flag = r.named("AAC_type_flag").read_bool()?;
+                desc.saoc_de = r.named("SAOC_DE_flag").read_bool()?;
+                r.named("reserved_future_use").skip(6)?;
+                if aac_type_flag {
+                    desc.aac_type = Some(r.named("AAC_type").read_u8(8)?);
+                }
+                desc.additional_info = r.read_remaining().0;
+            }
+            Ok(desc)
+        })
+    }
+
+    /// Serializes the descriptor, omitting the flags byte if there's no `AAC_type`, SAOC-DE flag
+    /// or additional info to signal.  The additional info is truncated if it would not otherwise
+    /// fit within the descriptor.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![DVB_AAC_DESCRIPTOR_TAG, 0, self.profile_and_level.into()];
+        if self.aac_type.is_some() || self.saoc_de || !self.additional_info.is_empty() {
+            let mut flags = 0b0011_1111;
+            if self.aac_type.is_some() {
+                flags |= 0b1000_0000;
+            }
+            if self.saoc_de {
+                flags |= 0b0100_0000;
+            }
+            buf.push(flags);
+            buf.extend(self.aac_type);
+            let space = 257 - buf.len();
+            buf.extend(self.additional_info.iter().take(space));
+        }
+        buf[1] = (buf.len() - 2) as u8;
+        buf
+    }
+}
+
+/// Reads a descriptor having the given tag from the start of the given buffer, reading its body
+/// with the given function, which sees the end of the data at the end of the descriptor.
+fn read_descriptor<T>(
+    buf: &[u8],
+    tag: u8,
+    read: impl FnOnce(&mut BitReader<'_>) -> Result<T, DescriptorError>,
+) -> Result<T, DescriptorError> {
+    let mut r = BitReader::new(buf);
+    let actual = r.named("descriptor_tag").read_u8(8)?;
+    if actual != tag {
+        return Err(DescriptorError::UnexpectedTag {
+            expected: tag,
+            actual,
+        });
+    }
+    let length = usize::from(r.named("descriptor_length").read_u8(8)?);
+    let start = r.position();
+    let data = r.named("descriptor").read_bits(length * 8)?;
+    r.nested(&data, length * 8, start, read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mpeg4_audio() {
+        let desc = Mpeg4AudioDescriptor {
+            profile_and_level: AudioProfileLevelIndication::HE_AAC_V2_L4,
+        };
+        assert_eq!(Ok(desc), Mpeg4AudioDescriptor::from_bytes(&desc.to_bytes()));
+        assert_eq!(
+            Err(DescriptorError::NotEnoughData(TraceEntry {
+                bit_offset: 16,
+                bit_length: 8,
+                field: "descriptor",
+                value: None,
+            })),
+            Mpeg4AudioDescriptor::from_bytes(&[0x1c, 0x01])
+        );
+        assert_eq!(
+            Err(DescriptorError::NotEnoughData(TraceEntry {
+                bit_offset: 16,
+                bit_length: 8,
+                field: "MPEG-4_audio_profile_and_level",
+                value: None,
+            })),
+            Mpeg4AudioDescriptor::from_bytes(&[0x1c, 0x00, 0x29])
+        );
+        assert_eq!(
+            Err(DescriptorError::UnexpectedTag {
+                expected: 0x1c,
+                actual: 0x2b
+            }),
+            Mpeg4AudioDescriptor::from_bytes(&[0x2b, 0x01, 0x29])
+        );
+    }
+
+    #[test]
+    fn mpeg2_aac_audio() {
+        let desc = Mpeg2AacAudioDescriptor::new(
+            AudioObjectType::AAC_MAIN,
+            ChannelConfiguration::FIVE_ONE,
+            AacAdditionalInformation::Reserved(0x7f),
+        )
+        .unwrap();
+        assert_eq!(vec![0x2b, 0x03, 0x00, 0x06, 0x7f], desc.to_bytes());
+        assert_eq!(
+            Ok(desc),
+            Mpeg2AacAudioDescriptor::from_bytes(&desc.to_bytes())
+        );
+        assert!(Mpeg2AacAudioDescriptor::new(
+            AudioObjectType::SBR,
+            ChannelConfiguration::MONO,
+            AacAdditionalInformation::None
+        )
+        .is_none());
+        assert_eq!(
+            Err(DescriptorError::ChannelConfiguration(
+                ChannelConfigurationError::TooLarge(0x10)
+            )),
+            Mpeg2AacAudioDescriptor::from_bytes(&[0x2b, 0x03, 0x01, 0x10, 0x00])
+        );
+    }
+
+    #[test]
+    fn dvb_aac() {
+        let desc = DvbAacDescriptor::from_bytes(&[0x7c, 0x01, 0x51]).unwrap();
+        assert_eq!(AudioProfileLevelIndication::AAC_L7, desc.profile_and_level);
+        assert_eq!(None, desc.aac_type);
+        assert_eq!(vec![0x7c, 0x01, 0x51], desc.to_bytes());
+        let desc = DvbAacDescriptor {
+            profile_and_level: AudioProfileLevelIndication::HE_AAC_V2_L2,
+            aac_type: None,
+            saoc_de: true,
+            additional_info: vec![0xaa, 0xbb],
+        };
+        assert_eq!(vec![0x7c, 0x04, 0x30, 0x7f, 0xaa, 0xbb], desc.to_bytes());
+        assert_eq!(
+            Ok(desc),
+            DvbAacDescriptor::from_bytes(&[0x7c, 0x04, 0x30, 0x7f, 0xaa, 0xbb])
+        );
+        assert_eq!(
+            Err(DescriptorError::NotEnoughData(TraceEntry {
+                bit_offset: 32,
+                bit_length: 8,
+                field: "AAC_type",
+                value: None,
+            })),
+            DvbAacDescriptor::from_bytes(&[0x7c, 0x02, 0x30, 0x80])
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! [wasm-bindgen](https://docs.rs/wasm-bindgen) bindings, so that JavaScript code such as
+//! browser-based stream analyzers and MSE players can share this crate's parsing.
+//!
+//! ```js
+//! import { parseAudioSpecificConfig } from "mpeg4_audio_const";
+//!
+//! const config = parseAudioSpecificConfig(new Uint8Array([0x12, 0x10]));
+//! if (MediaSource.isTypeSupported(`audio/mp4; codecs="${config.codecString}"`)) {
+//!     // ...
+//! }
+//! ```
+
+use crate::adts::{AdtsFrames, AdtsStats};
+use crate::asc::{AudioExtension, AudioSpecificConfig};
+use wasm_bindgen::prelude::*;
+
+/// The main properties of an `AudioSpecificConfig`
+#[wasm_bindgen(getter_with_clone)]
+pub struct AudioConfigInfo {
+    /// The object type of the core codec (i.e. `2` for HE-AAC, rather than `5`)
+    #[wasm_bindgen(js_name = audioObjectType)]
+    pub audio_object_type: u8,
+    /// The core sampling rate in Hz, if known
+    #[wasm_bindgen(js_name = sampleRate)]
+    pub sample_rate: Option<u32>,
+    /// The SBR output sampling rate in Hz, if signalled
+    #[wasm_bindgen(js_name = extensionSampleRate)]
+    pub extension_sample_rate: Option<u32>,
+    #[wasm_bindgen(js_name = channelConfiguration)]
+    pub channel_configuration: u8,
+    /// The number of channels, if known
+    #[wasm_bindgen(js_name = channelCount)]
+    pub channel_count: Option<u32>,
+    #[wasm_bindgen(js_name = sbrPresent)]
+    pub sbr_present: bool,
+    #[wasm_bindgen(js_name = psPresent)]
+    pub ps_present: bool,
+    /// The RFC 6381 codec string, e.g. `mp4a.40.2`
+    #[wasm_bindgen(js_name = codecString)]
+    pub codec_string: String,
+}
+
+/// The properties of an ADTS stream, as found by [`probe_adts()`](fn.probe_adts.html)
+#[wasm_bindgen(getter_with_clone)]
+pub struct AdtsProbe {
+    /// The object type signalled by the first frame header
+    #[wasm_bindgen(js_name = audioObjectType)]
+    pub audio_object_type: u8,
+    /// The sampling rate in Hz signalled by the first frame header, if known
+    #[wasm_bindgen(js_name = sampleRate)]
+    pub sample_rate: Option<u32>,
+    #[wasm_bindgen(js_name = channelConfiguration)]
+    pub channel_configuration: u8,
+    /// The number of complete frames in the data
+    #[wasm_bindgen(js_name = frameCount)]
+    pub frame_count: u32,
+    /// The duration of the complete frames, in seconds
+    #[wasm_bindgen(js_name = durationSeconds)]
+    pub duration_seconds: f64,
+    /// The average bitrate of the complete frames, in bits per second, if known
+    #[wasm_bindgen(js_name = averageBitrate)]
+    pub average_bitrate: Option<u32>,
+    /// The RFC 6381 codec string for the object type, e.g. `mp4a.40.2`
+    #[wasm_bindgen(js_name = codecString)]
+    pub codec_string: String,
+}
+
+/// Parses an `AudioSpecificConfig` (e.g. from an MP4 `esds` box), throwing an `Error` if it is
+/// malformed.
+#[wasm_bindgen(js_name = parseAudioSpecificConfig)]
+pub fn parse_audio_specific_config(data: &[u8]) -> Result<AudioConfigInfo, JsError> {
+    let asc = from_bytes(data)?;
+    let extension = asc.extension.as_ref();
+    Ok(AudioConfigInfo {
+        audio_object_type: u8::from(asc.audio_object_type),
+        sample_rate: asc.sample_rate(),
+        extension_sample_rate: extension
+            .and_then(AudioExtension::sampling_frequency)
+            .and_then(|f| f.frequency()),
+        channel_configuration: u8::from(asc.channel_configuration),
+        channel_count: asc.channel_count(),
+        sbr_present: extension.is_some_and(AudioExtension::sbr_present),
+        ps_present: extension.is_some_and(AudioExtension::ps_present),
+        codec_string: asc.codec_string(),
+    })
+}
+
+/// The RFC 6381 codec string (e.g. `mp4a.40.5`) for the given `AudioSpecificConfig`, throwing
+/// an `Error` if it is malformed.
+#[wasm_bindgen(js_name = codecString)]
+pub fn codec_string(audio_specific_config: &[u8]) -> Result<String, JsError> {
+    Ok(from_bytes(audio_specific_config)?.codec_string())
+}
+
+/// Examines data that may be an ADTS stream, returning `undefined` unless it starts with a
+/// complete ADTS frame.  A partial frame at the end of the data is ignored.
+#[wasm_bindgen(js_name = probeAdts)]
+pub fn probe_adts(data: &[u8]) -> Option<AdtsProbe> {
+    let mut frames = AdtsFrames::new(data).map_while(Result::ok);
+    let first = frames.next()?.header;
+    let mut stats = AdtsStats::new();
+    stats.push(&first);
+    for frame in frames {
+        stats.push(&frame.header);
+    }
+    Some(AdtsProbe {
+        audio_object_type: u8::from(first.audio_object_type),
+        sample_rate: first.sampling_frequency_index.frequency(),
+        channel_configuration: u8::from(first.channel_configuration),
+        frame_count: stats.frame_count().min(u64::from(u32::MAX)) as u32,
+        duration_seconds: stats.duration().as_secs_f64(),
+        average_bitrate: stats.average_bitrate(),
+        codec_string: first.audio_object_type.codec_string(),
+    })
+}
+
+fn from_bytes(data: &[u8]) -> Result<AudioSpecificConfig, JsError> {
+    AudioSpecificConfig::from_bytes(data)
+        .map_err(|e| JsError::new(&format!("invalid AudioSpecificConfig: {:?}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let info = parse_audio_specific_config(&[0xeb, 0x09, 0x88, 0x00]).unwrap();
+        assert_eq!(2, info.audio_object_type);
+        assert_eq!(Some(24000), info.sample_rate);
+        assert_eq!(Some(48000), info.extension_sample_rate);
+        assert_eq!(Some(2), info.channel_count);
+        assert!(info.sbr_present && info.ps_present);
+        assert_eq!("mp4a.40.29", info.codec_string);
+        assert_eq!("mp4a.40.2", codec_string(&[0x12, 0x10]).unwrap());
+    }
+
+    #[test]
+    fn probe() {
+        let frame = [
+            0xff, 0xf1, 0x50, 0x80, 0x02, 0x1f, 0xfc, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let mut data = frame.repeat(3);
+        data.truncate(40);
+        let probe = probe_adts(&data).unwrap();
+        assert_eq!(2, probe.frame_count);
+        assert_eq!(Some(44100), probe.sample_rate);
+        assert_eq!("mp4a.40.2", probe.codec_string);
+        assert!(probe_adts(&[0; 16]).is_none());
+    }
+}